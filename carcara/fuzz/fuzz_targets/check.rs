@@ -0,0 +1,12 @@
+#![no_main]
+
+use carcara::{checker::*, fuzz::FuzzInstance};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|instance: FuzzInstance| {
+    let FuzzInstance { problem, proof, mut pool } = instance;
+    for config in [Config::new(), Config::new().ignore_unknown_rules(true)] {
+        // We only care that the checker doesn't panic, not about the result
+        let _ = ProofChecker::new(&mut pool, config).check(&problem, &proof);
+    }
+});
@@ -0,0 +1,180 @@
+//! This module implements `ProofBuilder`, a helper to construct proofs programmatically.
+
+use super::*;
+use std::collections::HashMap;
+
+/// A reference to a command added to a [`ProofBuilder`], which can be used as a premise or
+/// discharged assumption of later steps.
+///
+/// This stores the premise index of the command, in the same form as [`ProofStep::premises`], so
+/// it is only valid while the subproof that contains the command is still open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PremiseRef(pub(crate) (usize, usize));
+
+impl PremiseRef {
+    /// Returns the premise index of the referenced command, of the form (depth, index in
+    /// subproof).
+    pub fn index(&self) -> (usize, usize) {
+        self.0
+    }
+}
+
+/// A builder to construct proofs programmatically, without having to manage command ids and
+/// premise indices by hand.
+///
+/// Assumptions are named `h1`, `h2`, etc. and steps are named `t1`, `t2`, etc. Commands inside a
+/// subproof are prefixed with the id of the subproof, so the steps in subproof `t3` are named
+/// `t3.t1`, `t3.t2`, etc., and the last one is named `t3`.
+///
+/// # Examples
+///
+/// ```
+/// # use carcara::{ast::*, checker};
+/// let mut pool = PrimitivePool::new();
+/// let int_sort = pool.add(Term::Sort(Sort::Int));
+/// let a = pool.add(Term::new_var("a", int_sort));
+/// let a_eq_a = pool.add(Term::Op(Operator::Equals, vec![a.clone(), a]));
+/// let not_a_eq_a = pool.add(Term::Op(Operator::Not, vec![a_eq_a.clone()]));
+///
+/// let mut builder = ProofBuilder::new();
+/// let h1 = builder.assume(not_a_eq_a.clone());
+/// let t1 = builder.step("refl", vec![a_eq_a], &[], Vec::new());
+/// builder.step("resolution", Vec::new(), &[h1, t1], Vec::new());
+/// let proof = builder.build();
+///
+/// let mut problem = Problem::new();
+/// problem.premises.insert(not_a_eq_a);
+/// let mut checker = checker::ProofChecker::new(&mut pool, checker::Config::new());
+/// let is_holey = checker.check(&problem, &proof).unwrap();
+/// assert!(!is_holey);
+/// ```
+#[derive(Debug)]
+pub struct ProofBuilder {
+    /// The stack of currently open subproofs. Each entry holds the id of the subproof (or an empty
+    /// string, for the root proof), its anchor arguments, and the commands added to it so far.
+    stack: Vec<(String, Vec<AnchorArg>, Vec<ProofCommand>)>,
+    num_assumes: Vec<usize>,
+    num_steps: Vec<usize>,
+    next_context_id: usize,
+}
+
+impl Default for ProofBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProofBuilder {
+    /// Constructs a new builder for an empty proof.
+    pub fn new() -> Self {
+        Self {
+            stack: vec![(String::new(), Vec::new(), Vec::new())],
+            num_assumes: vec![0],
+            num_steps: vec![0],
+            next_context_id: 0,
+        }
+    }
+
+    /// Adds an `assume` command with the given term to the current subproof.
+    pub fn assume(&mut self, term: Rc<Term>) -> PremiseRef {
+        *self.num_assumes.last_mut().unwrap() += 1;
+        let id = self.next_id('h', *self.num_assumes.last().unwrap());
+        self.push(ProofCommand::Assume { id, term })
+    }
+
+    /// Adds a `step` command to the current subproof.
+    pub fn step(
+        &mut self,
+        rule: &str,
+        clause: Vec<Rc<Term>>,
+        premises: &[PremiseRef],
+        args: Vec<Rc<Term>>,
+    ) -> PremiseRef {
+        self.step_with_discharge(rule, clause, premises, args, &[])
+    }
+
+    /// Similar to [`ProofBuilder::step`], but also discharges the given assumptions. This is
+    /// usually used by the step that ends a subproof.
+    pub fn step_with_discharge(
+        &mut self,
+        rule: &str,
+        clause: Vec<Rc<Term>>,
+        premises: &[PremiseRef],
+        args: Vec<Rc<Term>>,
+        discharge: &[PremiseRef],
+    ) -> PremiseRef {
+        *self.num_steps.last_mut().unwrap() += 1;
+        let id = self.next_id('t', *self.num_steps.last().unwrap());
+        self.push(ProofCommand::Step(ProofStep {
+            id,
+            clause,
+            rule: rule.to_owned(),
+            premises: premises.iter().map(PremiseRef::index).collect(),
+            args,
+            discharge: discharge.iter().map(PremiseRef::index).collect(),
+        }))
+    }
+
+    /// Adds a subproof with the given anchor arguments to the current subproof. Its commands are
+    /// added by `build`, which receives this builder, and the last of them is taken as the step
+    /// that ends the subproof. Returns a reference to the subproof, which can be used as a premise
+    /// to refer to its last step.
+    ///
+    /// # Panics
+    ///
+    /// This method panics if the last command added by `build` is not a `step`.
+    pub fn subproof(
+        &mut self,
+        args: Vec<AnchorArg>,
+        build: impl FnOnce(&mut ProofBuilder),
+    ) -> PremiseRef {
+        *self.num_steps.last_mut().unwrap() += 1;
+        let id = self.next_id('t', *self.num_steps.last().unwrap());
+        self.stack.push((id.clone(), args, Vec::new()));
+        self.num_assumes.push(0);
+        self.num_steps.push(0);
+
+        build(self);
+
+        let (_, args, mut commands) = self.stack.pop().unwrap();
+        self.num_assumes.pop();
+        self.num_steps.pop();
+        match commands.last_mut() {
+            Some(ProofCommand::Step(s)) => s.id = id,
+            _ => panic!("subproof must end in a step"),
+        }
+        let context_id = self.next_context_id;
+        self.next_context_id += 1;
+        self.push(ProofCommand::Subproof(Subproof {
+            commands,
+            args,
+            context_id,
+        }))
+    }
+
+    /// Finishes building the proof.
+    pub fn build(mut self) -> Proof {
+        let (_, _, commands) = self.stack.pop().unwrap();
+        Proof {
+            constant_definitions: Vec::new(),
+            commands,
+            comments: HashMap::new(),
+        }
+    }
+
+    fn next_id(&self, prefix: char, n: usize) -> String {
+        let (subproof_id, _, _) = self.stack.last().unwrap();
+        if subproof_id.is_empty() {
+            format!("{}{}", prefix, n)
+        } else {
+            format!("{}.{}{}", subproof_id, prefix, n)
+        }
+    }
+
+    fn push(&mut self, command: ProofCommand) -> PremiseRef {
+        let depth = self.stack.len() - 1;
+        let (_, _, commands) = self.stack.last_mut().unwrap();
+        commands.push(command);
+        PremiseRef((depth, commands.len() - 1))
+    }
+}
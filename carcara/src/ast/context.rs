@@ -77,6 +77,29 @@ impl ContextStack {
         self.len() == 0
     }
 
+    /// Returns an estimate of the number of bytes used by the contexts, including the anchor
+    /// arguments and cumulative substitutions of all contexts that are still alive.
+    pub fn memory_estimate(&self) -> usize {
+        use std::mem::size_of;
+
+        let live: usize = self
+            .context_vec
+            .iter()
+            .filter_map(|(_, context)| {
+                let context = context.read().unwrap();
+                context.as_ref().map(|c| {
+                    c.args.len() * size_of::<AnchorArg>()
+                        + c.cumulative_substitution
+                            .as_ref()
+                            .map_or(0, Substitution::memory_estimate)
+                })
+            })
+            .sum();
+        self.context_vec.len() * size_of::<ContextInfo>()
+            + self.stack.len() * size_of::<usize>()
+            + live
+    }
+
     pub fn last(&self) -> Option<RwLockReadGuard<Option<Context>>> {
         self.stack
             .last()
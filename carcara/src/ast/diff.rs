@@ -0,0 +1,153 @@
+//! This module implements `diff_proofs`, which finds where two proofs diverge.
+
+use super::*;
+
+/// A difference between two proofs, found by [`diff_proofs`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProofDiff {
+    /// The position of the differing commands. This contains the index of each enclosing subproof
+    /// command, followed by the index of the differing command in the innermost subproof.
+    pub path: Vec<usize>,
+
+    /// The id of the differing command in the first proof, or in the second proof if the first one
+    /// has no command in that position.
+    pub id: String,
+
+    /// How the two commands differ.
+    pub kind: ProofDiffKind,
+}
+
+/// The ways in which two commands in the same position may differ.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProofDiffKind {
+    /// The commands are of different kinds, for example, an `assume` and a `step`.
+    CommandKind,
+
+    /// The steps use different rules. This holds the rules of the first and second steps.
+    RuleChanged(String, String),
+
+    /// The conclusion clauses of the steps, or the terms of the assumptions, are different.
+    ClauseDiffers,
+
+    /// The steps reference different premises or discharge different assumptions. Premises are
+    /// compared by the ids of the commands they reference.
+    PremisesDiffer,
+
+    /// The steps have different arguments.
+    ArgsDiffer,
+
+    /// The subproofs have different anchor arguments.
+    AnchorDiffers,
+
+    /// Only the first proof has a command in this position.
+    OnlyInFirst,
+
+    /// Only the second proof has a command in this position.
+    OnlyInSecond,
+}
+
+/// Compares two proofs structurally, and returns where they diverge.
+///
+/// The commands of the two proofs are compared position by position, ignoring their ids, and only
+/// the first differing command in each subproof (and in the root proof) is reported. When both
+/// proofs have a subproof with the same anchor arguments in the same position, its commands are
+/// compared recursively, and the comparison continues after it.
+///
+/// Since terms are compared by identity, the two proofs must share the same term pool. This is the
+/// case, for example, when they are parsed together with [`parse_instance_multi`].
+///
+/// [`parse_instance_multi`]: crate::parser::parse_instance_multi
+pub fn diff_proofs(a: &Proof, b: &Proof) -> Vec<ProofDiff> {
+    let mut differ = ProofDiffer {
+        stacks: (vec![&a.commands], vec![&b.commands]),
+        path: Vec::new(),
+        result: Vec::new(),
+    };
+    differ.diff_commands();
+    differ.result
+}
+
+struct ProofDiffer<'a> {
+    stacks: (Vec<&'a [ProofCommand]>, Vec<&'a [ProofCommand]>),
+    path: Vec<usize>,
+    result: Vec<ProofDiff>,
+}
+
+impl ProofDiffer<'_> {
+    fn diff_commands(&mut self) {
+        let a = *self.stacks.0.last().unwrap();
+        let b = *self.stacks.1.last().unwrap();
+        for i in 0..a.len().max(b.len()) {
+            self.path.push(i);
+            let kind = match (a.get(i), b.get(i)) {
+                (Some(ProofCommand::Subproof(x)), Some(ProofCommand::Subproof(y)))
+                    if x.args == y.args =>
+                {
+                    self.stacks.0.push(&x.commands);
+                    self.stacks.1.push(&y.commands);
+                    self.diff_commands();
+                    self.stacks.0.pop();
+                    self.stacks.1.pop();
+                    None
+                }
+                (Some(x), Some(y)) => self.diff_command(x, y),
+                (Some(_), None) => Some(ProofDiffKind::OnlyInFirst),
+                (None, Some(_)) => Some(ProofDiffKind::OnlyInSecond),
+                (None, None) => unreachable!(),
+            };
+            if let Some(kind) = kind {
+                let id = a.get(i).or(b.get(i)).unwrap().id().to_owned();
+                let path = self.path.clone();
+                self.result.push(ProofDiff { path, id, kind });
+                self.path.pop();
+                return;
+            }
+            self.path.pop();
+        }
+    }
+
+    fn diff_command(&self, a: &ProofCommand, b: &ProofCommand) -> Option<ProofDiffKind> {
+        use ProofDiffKind::*;
+
+        let kind = match (a, b) {
+            (ProofCommand::Assume { term: x, .. }, ProofCommand::Assume { term: y, .. }) => {
+                if x == y {
+                    return None;
+                }
+                ClauseDiffers
+            }
+            (ProofCommand::Step(x), ProofCommand::Step(y)) => {
+                if x.rule != y.rule {
+                    RuleChanged(x.rule.clone(), y.rule.clone())
+                } else if x.clause != y.clause {
+                    ClauseDiffers
+                } else if premise_ids(&self.stacks.0, x) != premise_ids(&self.stacks.1, y) {
+                    PremisesDiffer
+                } else if x.args != y.args {
+                    ArgsDiffer
+                } else {
+                    return None;
+                }
+            }
+            (ProofCommand::Subproof(_), ProofCommand::Subproof(_)) => AnchorDiffers,
+            _ => CommandKind,
+        };
+        Some(kind)
+    }
+}
+
+/// Returns the ids of the commands referenced by the premises and discharged assumptions of a
+/// step, or `None` for each premise index that is invalid.
+fn premise_ids<'a>(
+    stack: &[&'a [ProofCommand]],
+    step: &ProofStep,
+) -> (Vec<Option<&'a str>>, Vec<Option<&'a str>>) {
+    let get_id = |&(depth, index): &(usize, usize)| {
+        let commands: &'a [ProofCommand] = stack.get(depth)?;
+        commands.get(index).map(ProofCommand::id)
+    };
+    (
+        step.premises.iter().map(get_id).collect(),
+        step.discharge.iter().map(get_id).collect(),
+    )
+}
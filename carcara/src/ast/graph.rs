@@ -0,0 +1,131 @@
+//! This module implements `build_dependency_graph`, which computes the graph of dependencies
+//! between the commands of a proof, and `critical_path`, which finds the longest chain of
+//! dependencies in it.
+
+use super::*;
+
+/// A node in a proof's dependency graph, representing an `assume` or `step` command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DepNode {
+    /// The id of the command.
+    pub id: String,
+
+    /// The rule of the step, or `"assume"` if the command is an assumption.
+    pub rule: String,
+
+    /// The position of the command in the proof, in the same form as premise indices, that is,
+    /// (depth, index in subproof).
+    pub index: (usize, usize),
+}
+
+/// The dependency graph of a proof. This is a DAG whose nodes are the `assume` and `step` commands
+/// in the proof, including the ones inside subproofs, and whose edges are the premise references
+/// between them.
+///
+/// Subproofs are not represented as nodes. Instead, a reference to a subproof is considered a
+/// reference to the step that ends it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DepGraph {
+    /// The nodes of the graph, in the order they appear in the proof.
+    pub nodes: Vec<DepNode>,
+
+    /// The edges of the graph, as pairs of node indices. An edge `(a, b)` means that the command
+    /// represented by node `a` references the command represented by node `b` as a premise or as a
+    /// discharged assumption.
+    pub edges: Vec<(usize, usize)>,
+}
+
+impl DepGraph {
+    /// Returns an iterator over the indices of the nodes that are referenced by a given node.
+    pub fn premises_of(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges
+            .iter()
+            .filter(move |&&(a, _)| a == node)
+            .map(|&(_, b)| b)
+    }
+
+    /// Returns the indices of the nodes in the longest path in the graph, starting from a node
+    /// with no premises and ending in the node that (transitively) depends on it. If there are
+    /// multiple longest paths, the one that ends earliest in the proof is returned.
+    pub fn critical_path(&self) -> Vec<usize> {
+        // Since a command can only reference commands that come before it, the nodes are already
+        // in topological order. For each node, we store the length of the longest path ending in
+        // it, and the previous node in that path
+        let mut longest: Vec<(usize, Option<usize>)> = vec![(1, None); self.nodes.len()];
+        for &(a, b) in &self.edges {
+            if b < a && longest[b].0 + 1 > longest[a].0 {
+                longest[a] = (longest[b].0 + 1, Some(b));
+            }
+        }
+
+        let Some(mut current) = (0..self.nodes.len()).rev().max_by_key(|&i| longest[i].0) else {
+            return Vec::new();
+        };
+        let mut path = vec![current];
+        while let Some(previous) = longest[current].1 {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Returns the ids of the commands in the longest chain of premise dependencies in a proof, in
+/// the order they appear in the proof. Since each command in this chain depends on the previous
+/// one, its length is a lower bound on the number of steps that must be checked sequentially.
+pub fn critical_path(proof: &Proof) -> Vec<String> {
+    let graph = build_dependency_graph(proof);
+    graph
+        .critical_path()
+        .into_iter()
+        .map(|i| graph.nodes[i].id.clone())
+        .collect()
+}
+
+/// Computes the dependency graph of a proof. See [`DepGraph`] for more details.
+pub fn build_dependency_graph(proof: &Proof) -> DepGraph {
+    let mut graph = DepGraph::default();
+
+    // For each open subproof, this maps the index of each command in the subproof to the node
+    // that represents it. Subproof commands are mapped to the node of their end step, once it is
+    // created
+    let mut scopes: Vec<Vec<Option<usize>>> = vec![Vec::new()];
+
+    let mut iter = proof.iter();
+    while let Some(command) = iter.next() {
+        let depth = match command {
+            ProofCommand::Subproof(_) => iter.depth() - 1,
+            _ => iter.depth(),
+        };
+        scopes.truncate(depth + 1);
+
+        let (rule, premises) = match command {
+            ProofCommand::Subproof(_) => {
+                scopes[depth].push(None);
+                scopes.push(Vec::new());
+                continue;
+            }
+            ProofCommand::Assume { .. } => ("assume", Vec::new()),
+            ProofCommand::Step(s) => {
+                let premises = s.premises.iter().chain(&s.discharge);
+                let premises = premises.filter_map(|&(d, i)| *scopes.get(d)?.get(i)?);
+                (s.rule.as_str(), premises.collect())
+            }
+        };
+
+        let node = graph.nodes.len();
+        graph.nodes.push(DepNode {
+            id: command.id().to_owned(),
+            rule: rule.to_owned(),
+            index: (depth, scopes[depth].len()),
+        });
+        graph.edges.extend(premises.into_iter().map(|p| (node, p)));
+        scopes[depth].push(Some(node));
+
+        if iter.is_end_step() {
+            *scopes[depth - 1].last_mut().unwrap() = Some(node);
+        }
+    }
+    graph
+}
@@ -44,6 +44,12 @@ impl<'a> ProofIter<'a> {
         Self { stack: vec![(0, commands)] }
     }
 
+    /// Constructs a new `ProofIter` that starts at the command with the given index in a slice of
+    /// root-level proof commands. The commands before it can still be referenced as premises.
+    pub(crate) fn starting_at(commands: &'a [ProofCommand], index: usize) -> Self {
+        Self { stack: vec![(index, commands)] }
+    }
+
     /// Returns the current nesting depth of the iterator, or more precisely, the nesting depth of
     /// the last command that was returned. This depth starts at zero, for commands in the root
     /// proof.
@@ -71,11 +77,27 @@ impl<'a> ProofIter<'a> {
         }
     }
 
+    /// Returns `true` if a premise index of the form (depth, index in subproof) refers to a command
+    /// that precedes the last command that was returned, in the same subproof or in one of the
+    /// subproofs that enclose it. This is the case for all premises that may be legally referenced
+    /// by that command.
+    pub fn precedes_current(&self, (depth, index): (usize, usize)) -> bool {
+        // For the innermost subproof, the last command returned is at index `i - 1`. For the
+        // enclosing subproofs, the command at index `i - 1` is the subproof that is currently open
+        self.stack.get(depth).is_some_and(|&(i, _)| index + 1 < i)
+    }
+
     /// Returns the command referenced by a premise index of the form (depth, index in subproof).
     /// This method may panic if the premise index does not refer to a valid command.
     pub fn get_premise(&self, (depth, index): (usize, usize)) -> &ProofCommand {
         &self.stack[depth].1[index]
     }
+
+    /// Similar to `get_premise`, but returns `None` if the premise index does not refer to a valid
+    /// command.
+    pub fn try_get_premise(&self, (depth, index): (usize, usize)) -> Option<&ProofCommand> {
+        self.stack.get(depth)?.1.get(index)
+    }
 }
 
 impl<'a> Iterator for ProofIter<'a> {
@@ -4,31 +4,53 @@
 
 #[macro_use]
 mod macros;
+mod builder;
 mod context;
+mod diff;
+mod graph;
 mod iter;
 mod node;
+mod order;
 mod polyeq;
 pub mod pool;
 pub(crate) mod printer;
 mod problem;
 mod proof;
 mod rc;
+mod redundancy;
+#[cfg(feature = "serde")]
+mod serialize;
+mod sharing;
 mod substitution;
 mod term;
 #[cfg(test)]
 mod tests;
 
+pub use builder::{PremiseRef, ProofBuilder};
 pub use context::{Context, ContextStack};
+pub use diff::{diff_proofs, ProofDiff, ProofDiffKind};
+pub use graph::{build_dependency_graph, critical_path, DepGraph, DepNode};
 pub use iter::ProofIter;
 pub use node::{ProofNode, StepNode, SubproofNode};
-pub use polyeq::{alpha_equiv, polyeq, Polyeq, PolyeqComparable, PolyeqConfig};
-pub use pool::{PrimitivePool, TermPool};
-pub use printer::{print_proof, USE_SHARING_IN_TERM_DISPLAY};
+pub use order::{StructuralOrder, TermOrder};
+pub use polyeq::{alpha_equiv, polyeq, polyeq_mod_ite, Polyeq, PolyeqComparable, PolyeqConfig};
+pub use pool::{PoolStats, PrimitivePool, TermPool};
+pub use printer::{
+    pretty_term, print_proof, print_proof_to_string, print_proof_to_string_in_dialect,
+    print_proof_to_string_with_format, print_proof_to_string_with_infix_arithmetic, write_proof,
+    Dialect, RealFormat, USE_SHARING_IN_TERM_DISPLAY,
+};
 pub use problem::*;
 pub use proof::*;
 pub use rc::Rc;
-pub use substitution::{Substitution, SubstitutionError};
-pub use term::{Binder, BindingList, Constant, Operator, ParamOperator, Sort, SortedVar, Term};
+pub use redundancy::find_redundant_structural_steps;
+#[cfg(feature = "serde")]
+pub use serialize::{InvalidTermIndex, SerializedProof};
+pub use sharing::duplicate_subterms;
+pub use substitution::{substitute, Substitution, SubstitutionError};
+pub use term::{
+    Binder, BindingList, BitVecRadix, Constant, Operator, ParamOperator, Sort, SortedVar, Term,
+};
 
 #[cfg(test)]
 pub(crate) use node::compare_nodes;
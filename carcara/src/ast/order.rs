@@ -0,0 +1,149 @@
+//! This module implements `TermOrder`, a trait for total orders over terms, and `StructuralOrder`,
+//! its default implementation.
+
+use super::*;
+use std::cmp::Ordering;
+
+/// A total order over terms, used wherever operands or literals need to be sorted, so that the
+/// result doesn't depend on the memory layout of the terms or on the iteration order of hash-based
+/// collections.
+///
+/// Implementations must be consistent with term equality, that is, `compare(a, b)` must return
+/// `Ordering::Equal` if and only if `a == b`, given that both terms come from the same pool.
+pub trait TermOrder {
+    /// Compares two terms.
+    fn compare(&self, a: &Rc<Term>, b: &Rc<Term>) -> Ordering;
+
+    /// Sorts a slice of terms according to this order.
+    fn sort(&self, terms: &mut [Rc<Term>]) {
+        terms.sort_by(|a, b| self.compare(a, b));
+    }
+}
+
+/// The default order over terms. Terms are ordered first by their kind (constants, variables,
+/// applications, operations, sorts, binders, `let` terms and parameterized operations, in that
+/// order), and then by their structure, comparing their components from left to right.
+///
+/// Since this order only depends on the structure of the terms, two terms are placed in the same
+/// order in every run, even if they are added to the pool in a different order.
+///
+/// # Examples
+///
+/// ```
+/// # use carcara::ast::*;
+/// let mut pool = PrimitivePool::new();
+/// let int_sort = pool.add(Term::Sort(Sort::Int));
+/// let a = pool.add(Term::new_var("a", int_sort.clone()));
+/// let b = pool.add(Term::new_var("b", int_sort));
+/// let one = pool.add(Term::new_int(1));
+///
+/// let mut terms = vec![b.clone(), a.clone(), one.clone()];
+/// StructuralOrder.sort(&mut terms);
+/// assert_eq!(terms, [one, a, b]);
+/// ```
+#[derive(Debug, Default, Clone, Copy)]
+pub struct StructuralOrder;
+
+impl TermOrder for StructuralOrder {
+    fn compare(&self, a: &Rc<Term>, b: &Rc<Term>) -> Ordering {
+        if a == b {
+            return Ordering::Equal;
+        }
+        match (a.as_ref(), b.as_ref()) {
+            (Term::Const(a), Term::Const(b)) => compare_constants(a, b),
+            (Term::Var(a_name, a_sort), Term::Var(b_name, b_sort)) => a_name
+                .cmp(b_name)
+                .then_with(|| self.compare(a_sort, b_sort)),
+            (Term::App(a_func, a_args), Term::App(b_func, b_args)) => self
+                .compare(a_func, b_func)
+                .then_with(|| self.compare_all(a_args, b_args)),
+            (Term::Op(a_op, a_args), Term::Op(b_op, b_args)) => (*a_op as usize)
+                .cmp(&(*b_op as usize))
+                .then_with(|| self.compare_all(a_args, b_args)),
+            (Term::Sort(a), Term::Sort(b)) => a.to_string().cmp(&b.to_string()),
+            (Term::Binder(a_binder, a_bindings, a_inner), Term::Binder(b, b_bindings, b_inner)) => {
+                (*a_binder as usize)
+                    .cmp(&(*b as usize))
+                    .then_with(|| self.compare_bindings(a_bindings, b_bindings))
+                    .then_with(|| self.compare(a_inner, b_inner))
+            }
+            (Term::Let(a_bindings, a_inner), Term::Let(b_bindings, b_inner)) => self
+                .compare_bindings(a_bindings, b_bindings)
+                .then_with(|| self.compare(a_inner, b_inner)),
+            (
+                Term::ParamOp {
+                    op: a_op,
+                    op_args: a_op_args,
+                    args: a_args,
+                },
+                Term::ParamOp {
+                    op: b_op,
+                    op_args: b_op_args,
+                    args: b_args,
+                },
+            ) => (*a_op as usize)
+                .cmp(&(*b_op as usize))
+                .then_with(|| self.compare_all(a_op_args, b_op_args))
+                .then_with(|| self.compare_all(a_args, b_args)),
+            (a, b) => term_kind(a).cmp(&term_kind(b)),
+        }
+    }
+}
+
+impl StructuralOrder {
+    fn compare_all(self, a: &[Rc<Term>], b: &[Rc<Term>]) -> Ordering {
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| self.compare(x, y))
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len()))
+    }
+
+    fn compare_bindings(self, a: &BindingList, b: &BindingList) -> Ordering {
+        a.iter()
+            .zip(b.iter())
+            .map(|((x_name, x_value), (y_name, y_value))| {
+                x_name
+                    .cmp(y_name)
+                    .then_with(|| self.compare(x_value, y_value))
+            })
+            .find(|o| o.is_ne())
+            .unwrap_or_else(|| a.len().cmp(&b.len()))
+    }
+}
+
+fn term_kind(term: &Term) -> usize {
+    match term {
+        Term::Const(_) => 0,
+        Term::Var(..) => 1,
+        Term::App(..) => 2,
+        Term::Op(..) => 3,
+        Term::Sort(_) => 4,
+        Term::Binder(..) => 5,
+        Term::Let(..) => 6,
+        Term::ParamOp { .. } => 7,
+    }
+}
+
+fn compare_constants(a: &Constant, b: &Constant) -> Ordering {
+    fn kind(c: &Constant) -> usize {
+        match c {
+            Constant::Integer(_) => 0,
+            Constant::Real(_) => 1,
+            Constant::String(_) => 2,
+            Constant::BitVec(..) => 3,
+        }
+    }
+
+    match (a, b) {
+        (Constant::Integer(a), Constant::Integer(b)) => a.cmp(b),
+        (Constant::Real(a), Constant::Real(b)) => a.cmp(b),
+        (Constant::String(a), Constant::String(b)) => a.cmp(b),
+        // The radix in which a bitvector literal was written is ignored, to be consistent with
+        // equality
+        (Constant::BitVec(a_value, a_width, _), Constant::BitVec(b_value, b_width, _)) => {
+            a_width.cmp(b_width).then_with(|| a_value.cmp(b_value))
+        }
+        (a, b) => kind(a).cmp(&kind(b)),
+    }
+}
@@ -67,6 +67,19 @@ pub fn alpha_equiv(a: &Rc<Term>, b: &Rc<Term>, time: &mut Duration) -> bool {
         .eq_with_time(a, b, time)
 }
 
+/// Similar to `polyeq`, but also compares terms modulo trivial simplifications of `ite` terms.
+///
+/// That is, `(ite true a b)` and `(ite false b a)` are considered equal to `a`, and so is
+/// `(ite c a a)`, for any condition `c`.
+///
+/// This function records how long it takes to run, and adds that duration to the `time` argument.
+pub fn polyeq_mod_ite(a: &Rc<Term>, b: &Rc<Term>, time: &mut Duration) -> bool {
+    Polyeq::new()
+        .mod_reordering(true)
+        .mod_ite(true)
+        .eq_with_time(a, b, time)
+}
+
 /// Configuration for a `Polyeq`.
 ///
 /// - If `is_mod_reordering` is `true`, the comparator will compare terms modulo reordering of
@@ -78,12 +91,15 @@ pub fn alpha_equiv(a: &Rc<Term>, b: &Rc<Term>, time: &mut Duration) -> bool {
 /// - If `is_mod_string_concat` is `true`, the comparator will compare terms modulo the collection of
 ///
 /// String constants arguments in the String concatenation.
+/// - If `is_mod_ite` is `true`, the comparator will compare terms modulo the simplification of
+///   `ite` terms whose condition is a boolean constant or whose branches are equal.
 #[derive(Default)]
 pub struct PolyeqConfig {
     pub is_mod_reordering: bool,
     pub is_alpha_equivalence: bool,
     pub is_mod_nary: bool,
     pub is_mod_string_concat: bool,
+    pub is_mod_ite: bool,
 }
 
 impl PolyeqConfig {
@@ -122,6 +138,7 @@ pub struct Polyeq {
     de_bruijn_map: Option<DeBruijnMap>,
     is_mod_nary: bool,
     is_mod_string_concat: bool,
+    is_mod_ite: bool,
 
     current_depth: usize,
     max_depth: usize,
@@ -147,6 +164,7 @@ impl Polyeq {
             de_bruijn_map: config.is_alpha_equivalence.then(DeBruijnMap::new),
             is_mod_nary: config.is_mod_nary,
             is_mod_string_concat: config.is_mod_string_concat,
+            is_mod_ite: config.is_mod_ite,
             current_depth: 0,
             max_depth: 0,
         }
@@ -172,6 +190,11 @@ impl Polyeq {
         self
     }
 
+    pub fn mod_ite(mut self, value: bool) -> Self {
+        self.is_mod_ite = value;
+        self
+    }
+
     pub fn eq<T>(&mut self, a: &T, b: &T) -> bool
     where
         T: PolyeqComparable + ?Sized,
@@ -483,6 +506,13 @@ impl PolyeqComparable for Rc<Term> {
             return true;
         }
 
+        if comp.is_mod_ite {
+            let (a_simp, b_simp) = (simplify_ite(a), simplify_ite(b));
+            if !std::ptr::eq(a_simp, a) || !std::ptr::eq(b_simp, b) {
+                return comp.eq(a_simp, b_simp);
+            }
+        }
+
         comp.current_depth += 1;
         comp.max_depth = std::cmp::max(comp.max_depth, comp.current_depth);
         let result = comp.eq(a.as_ref(), b.as_ref());
@@ -494,6 +524,24 @@ impl PolyeqComparable for Rc<Term> {
     }
 }
 
+/// Removes any trivially simplifiable `ite` terms from the top of the given term, that is, `ite`
+/// terms whose condition is a boolean constant, or whose two branches are the same term.
+fn simplify_ite(mut term: &Rc<Term>) -> &Rc<Term> {
+    while let Term::Op(Operator::Ite, args) = term.as_ref() {
+        let [cond, then_branch, else_branch] = args.as_slice() else {
+            break;
+        };
+        term = if cond.is_bool_true() || then_branch == else_branch {
+            then_branch
+        } else if cond.is_bool_false() {
+            else_branch
+        } else {
+            break;
+        };
+    }
+    term
+}
+
 impl PolyeqComparable for Term {
     fn eq(comp: &mut Polyeq, a: &Self, b: &Self) -> bool {
         match (a, b) {
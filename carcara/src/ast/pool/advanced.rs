@@ -1,5 +1,5 @@
 use super::super::{Rc, Term};
-use super::{PrimitivePool, TermPool};
+use super::{PoolStats, PrimitivePool, TermPool};
 use indexmap::IndexSet;
 use std::sync::{Arc, RwLock};
 
@@ -65,6 +65,10 @@ impl TermPool for ContextPool {
             .unwrap()
             .free_vars_with_priorities(term, [&self.global_pool])
     }
+
+    fn stats(&self) -> PoolStats {
+        self.global_pool.stats() + self.inner.read().unwrap().stats()
+    }
 }
 
 // =========================================================================
@@ -133,4 +137,8 @@ impl TermPool for LocalPool {
             ],
         )
     }
+
+    fn stats(&self) -> PoolStats {
+        self.ctx_pool.stats() + self.inner.stats()
+    }
 }
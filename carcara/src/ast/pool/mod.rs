@@ -3,7 +3,7 @@
 pub mod advanced;
 mod storage;
 
-use super::{Binder, Operator, Rc, Sort, Substitution, Term};
+use super::{Binder, Operator, Rc, Sort, SortedVar, Substitution, Term};
 use crate::ast::{Constant, ParamOperator};
 use indexmap::{IndexMap, IndexSet};
 use rug::Integer;
@@ -45,6 +45,75 @@ pub trait TermPool {
     /// This method uses a cache, so there is no additional cost to computing the free variables of
     /// a term multiple times.
     fn free_vars(&mut self, term: &Rc<Term>) -> IndexSet<Rc<Term>>;
+    /// Returns statistics about the terms interned in the pool, such as how many terms of each
+    /// kind it contains.
+    fn stats(&self) -> PoolStats;
+}
+
+/// Statistics about the terms interned in a term pool. See [`TermPool::stats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PoolStats {
+    /// The total number of distinct terms in the pool.
+    pub total: usize,
+
+    /// The number of constant terms.
+    pub constants: usize,
+
+    /// The number of variable terms.
+    pub variables: usize,
+
+    /// The number of function application terms.
+    pub applications: usize,
+
+    /// The number of operation terms.
+    pub operations: usize,
+
+    /// The number of sorts.
+    pub sorts: usize,
+
+    /// The number of binder terms, that is, quantifiers, `choice` and `lambda` terms.
+    pub binders: usize,
+
+    /// The number of `let` terms.
+    pub lets: usize,
+
+    /// The number of parameterized operation terms.
+    pub param_operations: usize,
+}
+
+impl PoolStats {
+    fn count(&mut self, term: &Term) {
+        self.total += 1;
+        let counter = match term {
+            Term::Const(_) => &mut self.constants,
+            Term::Var(..) => &mut self.variables,
+            Term::App(..) => &mut self.applications,
+            Term::Op(..) => &mut self.operations,
+            Term::Sort(_) => &mut self.sorts,
+            Term::Binder(..) => &mut self.binders,
+            Term::Let(..) => &mut self.lets,
+            Term::ParamOp { .. } => &mut self.param_operations,
+        };
+        *counter += 1;
+    }
+}
+
+impl std::ops::Add for PoolStats {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            total: self.total + other.total,
+            constants: self.constants + other.constants,
+            variables: self.variables + other.variables,
+            applications: self.applications + other.applications,
+            operations: self.operations + other.operations,
+            sorts: self.sorts + other.sorts,
+            binders: self.binders + other.binders,
+            lets: self.lets + other.lets,
+            param_operations: self.param_operations + other.param_operations,
+        }
+    }
 }
 
 /// A structure to store and manage all allocated terms.
@@ -70,6 +139,82 @@ impl PrimitivePool {
         Self::default()
     }
 
+    /// Constructs a new `PrimitivePool` with space for at least `capacity` terms, avoiding
+    /// reallocations of its internal tables while they are added.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            storage: Storage::with_capacity(capacity),
+            free_vars_cache: IndexMap::new(),
+            sorts_cache: IndexMap::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the number of terms in the pool.
+    pub fn len(&self) -> usize {
+        self.storage.len()
+    }
+
+    /// Returns `true` if the pool contains no terms.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of terms the pool can hold without reallocating its internal tables.
+    pub fn capacity(&self) -> usize {
+        self.storage.capacity()
+    }
+
+    /// Removes all terms from the pool, but keeps the memory allocated for its internal tables.
+    /// This allows the same pool to be reused for many proofs, such as when checking a batch of
+    /// instances, without growing its tables from scratch for each one.
+    ///
+    /// Terms that were taken from the pool before it was cleared are still valid, but they will
+    /// not be shared with terms added afterwards.
+    pub fn clear(&mut self) {
+        self.storage.clear();
+        self.free_vars_cache.clear();
+        self.sorts_cache.clear();
+    }
+
+    /// Returns an estimate of the number of bytes used by the pool, including the terms themselves
+    /// and the sort and free variables caches. This only accounts for the data structures that
+    /// make up the terms, not for the contents of big integers and rationals, so it should be
+    /// taken as a rough lower bound.
+    pub fn memory_estimate(&self) -> usize {
+        use std::mem::size_of;
+
+        let rc_size = size_of::<Rc<Term>>();
+        let terms: usize = self
+            .storage
+            .iter()
+            .map(|term| {
+                // Each term is stored in an `Rc` allocation, which also holds the two reference
+                // counts, and is referenced by an entry in the storage
+                let heap = match term.as_ref() {
+                    Term::Const(Constant::String(s)) => s.len(),
+                    Term::Const(_) => 0,
+                    Term::Var(name, _) => name.len(),
+                    Term::App(_, args) | Term::Op(_, args) => args.len() * rc_size,
+                    Term::Sort(Sort::Function(args) | Sort::Atom(_, args)) => args.len() * rc_size,
+                    Term::Sort(Sort::ParamSort(args, _)) => args.len() * rc_size,
+                    Term::Sort(_) => 0,
+                    Term::Binder(_, bindings, _) | Term::Let(bindings, _) => bindings
+                        .iter()
+                        .map(|(name, _)| name.len() + size_of::<SortedVar>())
+                        .sum(),
+                    Term::ParamOp { op_args, args, .. } => (op_args.len() + args.len()) * rc_size,
+                };
+                size_of::<Term>() + 2 * size_of::<usize>() + rc_size + heap
+            })
+            .sum();
+        let free_vars: usize = self
+            .free_vars_cache
+            .values()
+            .map(|vars| (vars.len() + 1) * rc_size)
+            .sum();
+        terms + free_vars + self.sorts_cache.len() * 2 * rc_size
+    }
+
     /// Computes the sort of a term and adds it to the sort cache.
     fn compute_sort(&mut self, term: &Rc<Term>) -> Rc<Term> {
         if let Some(sort) = self.sorts_cache.get(term) {
@@ -81,7 +226,7 @@ impl PrimitivePool {
                 Constant::Integer(_) => Sort::Int,
                 Constant::Real(_) => Sort::Real,
                 Constant::String(_) => Sort::String,
-                Constant::BitVec(_, w) => Sort::BitVec(w.clone()),
+                Constant::BitVec(_, w, _) => Sort::BitVec(w.clone()),
             },
             Term::Var(_, sort) => sort.as_sort().unwrap().clone(),
             Term::Op(op, args) => match op {
@@ -209,40 +354,44 @@ impl PrimitivePool {
                 Operator::RareList => Sort::RareList,
             },
             Term::App(f, args) => {
-                match self.compute_sort(f).as_sort().unwrap() {
+                // Applications built outside the parser may be ill-sorted. Instead of panicking,
+                // we give them the best sort we can, and leave it to `checker::sort_of` to report
+                // the error
+                let f_sort = self.compute_sort(f);
+                match f_sort.as_sort().unwrap() {
                     Sort::Function(sorts) => sorts.last().unwrap().as_sort().unwrap().clone(),
-                    Sort::ParamSort(_, p_sort) => {
-                        let p_function_sort = p_sort.as_sort().unwrap();
-                        if let Sort::Function(sorts) = p_function_sort {
-                            // match with sorts of args, apply the resulting substitution on the return sort
-                            let mut map = IndexMap::new();
-                            for i in 0..args.len() {
-                                let sort_i = sorts[i].as_sort().unwrap();
-                                let arg_sort_i =
-                                    self.compute_sort(&args[i]).as_sort().unwrap().clone();
-                                if !sort_i.match_with(&arg_sort_i, &mut map) {
-                                    unreachable!();
-                                }
-                            }
-                            let substitution: IndexMap<_, _> = map
-                                .into_iter()
-                                .map(|(var_name, sort)| {
-                                    let var = Term::Sort(Sort::Var(var_name));
-                                    let sort_t = Term::Sort(sort);
-                                    (self.add(var), self.add(sort_t))
-                                })
-                                .collect();
-                            Substitution::new(self, substitution)
-                                .unwrap()
-                                .apply(self, sorts.last().unwrap())
-                                .as_sort()
-                                .unwrap()
-                                .clone()
-                        } else {
+                    Sort::ParamSort(_, p_sort)
+                        if matches!(p_sort.as_sort(), Some(Sort::Function(_))) =>
+                    {
+                        let Some(Sort::Function(sorts)) = p_sort.as_sort() else {
                             unreachable!()
+                        };
+                        // match with sorts of args, apply the resulting substitution on the return sort
+                        let mut map = IndexMap::new();
+                        for (sort_i, arg) in sorts.iter().zip(args) {
+                            let sort_i = sort_i.as_sort().unwrap();
+                            let arg_sort_i = self.compute_sort(arg).as_sort().unwrap().clone();
+                            if !sort_i.match_with(&arg_sort_i, &mut map) {
+                                break;
+                            }
                         }
+                        let substitution: IndexMap<_, _> = map
+                            .into_iter()
+                            .map(|(var_name, sort)| {
+                                let var = Term::Sort(Sort::Var(var_name));
+                                let sort_t = Term::Sort(sort);
+                                (self.add(var), self.add(sort_t))
+                            })
+                            .collect();
+                        Substitution::new(self, substitution)
+                            .unwrap()
+                            .apply(self, sorts.last().unwrap())
+                            .as_sort()
+                            .unwrap()
+                            .clone()
                     }
-                    _ => unreachable!(), // We assume that the function is correctly sorted
+                    // The head is not a function, so we use its own sort
+                    other => other.clone(),
                 }
             }
             Term::Sort(_) => Sort::Type,
@@ -412,4 +561,12 @@ impl TermPool for PrimitivePool {
     fn free_vars(&mut self, term: &Rc<Term>) -> IndexSet<Rc<Term>> {
         self.free_vars_with_priorities(term, [])
     }
+
+    fn stats(&self) -> PoolStats {
+        let mut stats = PoolStats::default();
+        for term in self.storage.iter() {
+            stats.count(term);
+        }
+        stats
+    }
 }
@@ -38,6 +38,10 @@ impl Borrow<Term> for ByValue {
 pub struct Storage(IndexSet<ByValue>);
 
 impl Storage {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(IndexSet::with_capacity(capacity))
+    }
+
     pub fn add(&mut self, term: Term) -> Rc<Term> {
         // If the `hash_set_entry` feature was stable, this would be much simpler to do using
         // `get_or_insert_with` (and would avoid rehashing the term)
@@ -55,6 +59,22 @@ impl Storage {
         self.0.get(term).map(|t| &t.0)
     }
 
+    pub fn iter(&self) -> impl Iterator<Item = &Rc<Term>> {
+        self.0.iter().map(|t| &t.0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
     // This method is only necessary for the hash consing tests
     #[cfg(test)]
     pub fn into_vec(self) -> Vec<Rc<Term>> {
@@ -6,6 +6,7 @@ use crate::{
     utils::{is_symbol_character, DedupIterator},
 };
 use indexmap::IndexMap;
+use rug::{Integer, Rational};
 use std::{
     borrow::Cow,
     collections::{HashMap, HashSet},
@@ -15,6 +16,39 @@ use std::{
 
 pub static USE_SHARING_IN_TERM_DISPLAY: AtomicBool = AtomicBool::new(false);
 
+/// How the printer renders real constants.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RealFormat {
+    /// Real constants are printed exactly, as a fraction. This is the default, since the printed
+    /// proof can be parsed back without any loss of precision. Normally, GMP notation is used, so
+    /// one third is printed as `1/3`. When the output must be compliant with SMT-LIB, it is
+    /// instead printed as `(/ 1.0 3.0)`.
+    #[default]
+    Fraction,
+
+    /// Real constants are printed as decimals, rounded to the given number of places after the
+    /// decimal point, so one third is printed as `0.333` with three places. Negative constants are
+    /// printed as `(- 0.333)`. Note that this may lose precision, so the printed proof may not be
+    /// equivalent to the original one.
+    Decimal { places: usize },
+}
+
+/// The proof format dialect in which the printer writes proofs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Dialect {
+    /// The Alethe proof format. This is the default.
+    #[default]
+    Alethe,
+
+    /// The older proof format used by veriT, which is accepted by some tools that consume veriT
+    /// proofs. It differs from Alethe in that:
+    /// - assignment arguments of anchors omit the sort of the variable, so `(:= (x Int) y)` is
+    ///   printed as `(:= x y)`
+    /// - the `:discharge` attribute of steps is omitted, since the assumptions of a subproof are
+    ///   implicitly discharged by the step that ends it
+    VeriT,
+}
+
 /// Prints a proof to the standard output.
 ///
 /// If `use_sharing` is `true`, terms that are used multiple times will make use of sharing. The
@@ -30,6 +64,142 @@ pub fn print_proof(
     AlethePrinter::new(pool, prelude, use_sharing, &mut stdout).write_proof(proof)
 }
 
+/// Prints a proof to a `String`, instead of the standard output. See [`print_proof`].
+pub fn print_proof_to_string(
+    pool: &mut PrimitivePool,
+    prelude: &ProblemPrelude,
+    proof: &Proof,
+    use_sharing: bool,
+) -> String {
+    print_proof_to_string_with_format(pool, prelude, proof, use_sharing, RealFormat::default())
+}
+
+/// Similar to [`print_proof_to_string`], but renders real constants using the given format.
+pub fn print_proof_to_string_with_format(
+    pool: &mut PrimitivePool,
+    prelude: &ProblemPrelude,
+    proof: &Proof,
+    use_sharing: bool,
+    real_format: RealFormat,
+) -> String {
+    let mut buf = Vec::new();
+    let mut printer = AlethePrinter::new(pool, prelude, use_sharing, &mut buf);
+    printer.real_format = real_format;
+    // Writing to a `Vec<u8>` never fails, and the printer only ever outputs valid UTF-8
+    printer.write_proof(proof).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+/// Similar to [`print_proof_to_string`], but writes the proof in the given dialect.
+pub fn print_proof_to_string_in_dialect(
+    pool: &mut PrimitivePool,
+    prelude: &ProblemPrelude,
+    proof: &Proof,
+    use_sharing: bool,
+    dialect: Dialect,
+) -> String {
+    let mut buf = Vec::new();
+    let mut printer = AlethePrinter::new(pool, prelude, use_sharing, &mut buf);
+    printer.dialect = dialect;
+    printer.write_proof(proof).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+/// Similar to [`print_proof_to_string`], but prints arithmetic operations and comparisons with two
+/// or more arguments in infix form, so `(+ a b c)` is printed as `(a + b + c)`, and `(<= a b)` as
+/// `(a <= b)`. All other terms are still printed as S-expressions. Unary operations, like `(- a)`,
+/// are also not affected.
+///
+/// This is only meant for display purposes, since the output is not valid Alethe, and can't be
+/// parsed back.
+pub fn print_proof_to_string_with_infix_arithmetic(
+    pool: &mut PrimitivePool,
+    prelude: &ProblemPrelude,
+    proof: &Proof,
+    use_sharing: bool,
+) -> String {
+    let mut buf = Vec::new();
+    let mut printer = AlethePrinter::new(pool, prelude, use_sharing, &mut buf);
+    printer.infix_arithmetic = true;
+    printer.write_proof(proof).unwrap();
+    String::from_utf8(buf).unwrap()
+}
+
+/// Formats a term over multiple lines, so that, where possible, no line is longer than `width`.
+///
+/// Terms that fit in the remaining width are printed in a single line. Otherwise, the head of the
+/// S-expression is printed on the first line, and each of its arguments is printed in a new line,
+/// indented by two spaces. Sharing is never used, and atomic terms are never broken, so lines may
+/// still exceed `width` if they contain long atoms or deeply nested terms.
+pub fn pretty_term(term: &Rc<Term>, width: usize) -> String {
+    let mut result = String::new();
+    write_pretty_term(&mut result, term, 0, width);
+    result
+}
+
+fn write_pretty_term(out: &mut String, term: &Rc<Term>, indent: usize, width: usize) {
+    let flat = format!("{:#}", term);
+    let (head, args) = match term.as_ref() {
+        _ if indent + flat.len() <= width => (None, &[][..]),
+        Term::App(func, args) => (Some(format!("{:#}", func)), args.as_slice()),
+        Term::Op(op, args) if !args.is_empty() => (Some(op.to_string()), args.as_slice()),
+        Term::ParamOp { op, op_args, args } if !args.is_empty() => {
+            let op = Term::ParamOp {
+                op: *op,
+                op_args: op_args.clone(),
+                args: Vec::new(),
+            };
+            (Some(format!("{:#}", op)), args.as_slice())
+        }
+        Term::Binder(binder, bindings, inner) => (
+            Some(format!("{} {}", binder, bindings)),
+            std::slice::from_ref(inner),
+        ),
+        Term::Let(bindings, inner) => (
+            Some(format!("let {}", bindings)),
+            std::slice::from_ref(inner),
+        ),
+        _ => (None, &[][..]),
+    };
+    let Some(head) = head else {
+        out.push_str(&flat);
+        return;
+    };
+    out.push('(');
+    out.push_str(&head);
+    for arg in args {
+        out.push('\n');
+        out.push_str(&" ".repeat(indent + 2));
+        write_pretty_term(out, arg, indent + 2, width);
+    }
+    out.push(')');
+}
+
+/// Writes a proof to `dest`, flushing it after every `flush_every` commands, and once more after the
+/// whole proof is written. See [`print_proof`].
+///
+/// This is useful when `dest` is buffered (for example, an `io::BufWriter`) and the output is being
+/// consumed by another process, since it allows the consumer to start processing the proof before
+/// it is completely printed, while still avoiding a flush after every write.
+///
+/// # Panics
+///
+/// This function panics if `flush_every` is zero.
+pub fn write_proof(
+    pool: &mut PrimitivePool,
+    prelude: &ProblemPrelude,
+    proof: &Proof,
+    use_sharing: bool,
+    dest: &mut dyn io::Write,
+    flush_every: usize,
+) -> io::Result<()> {
+    assert!(flush_every > 0, "`flush_every` must be greater than zero");
+    let mut printer = AlethePrinter::new(pool, prelude, use_sharing, dest);
+    printer.flush_every = Some(flush_every);
+    printer.write_proof(proof)?;
+    printer.inner.flush()
+}
+
 /// Given the conclusion clause of a `lia_generic` step, this method will write to `dest` the
 /// corresponding SMT problem instance.
 pub fn write_lia_smt_instance(
@@ -149,6 +319,15 @@ struct AlethePrinter<'a> {
     global_vars: HashSet<Rc<Term>>,
     defined_constants: HashMap<Rc<Term>, String>,
     smt_lib_strict: bool,
+    real_format: RealFormat,
+    dialect: Dialect,
+
+    /// If this is `true`, arithmetic operations and comparisons are printed in infix form. See
+    /// [`print_proof_to_string_with_infix_arithmetic`].
+    infix_arithmetic: bool,
+
+    /// If this is `Some(n)`, the printer will flush its output after every `n` proof commands.
+    flush_every: Option<usize>,
 }
 
 impl PrintProof for AlethePrinter<'_> {
@@ -167,7 +346,18 @@ impl PrintProof for AlethePrinter<'_> {
             .map(|(name, term)| (term, name))
             .collect();
         let mut iter = proof.iter();
+        let mut num_written = 0;
         while let Some(command) = iter.next() {
+            // When the iterator returns a subproof, it has already entered it
+            let depth = match command {
+                ProofCommand::Subproof(_) => iter.depth() - 1,
+                _ => iter.depth(),
+            };
+            if depth == 0 {
+                for comment in proof.comments.get(command.id()).into_iter().flatten() {
+                    writeln!(self.inner, "; {}", comment)?;
+                }
+            }
             match command {
                 ProofCommand::Assume { id, term } => {
                     write!(self.inner, "(assume {} ", quote_symbol(id))?;
@@ -195,7 +385,11 @@ impl PrintProof for AlethePrinter<'_> {
                                 }
                                 AnchorArg::Assign(var, value) => {
                                     write!(self.inner, "(:= ")?;
-                                    var.print_with_sharing(self)?;
+                                    if self.dialect == Dialect::VeriT {
+                                        write!(self.inner, "{}", quote_symbol(&var.0))?;
+                                    } else {
+                                        var.print_with_sharing(self)?;
+                                    }
                                     write!(self.inner, " ")?;
                                     value.print_with_sharing(self)?;
                                     write!(self.inner, ")")?;
@@ -209,6 +403,11 @@ impl PrintProof for AlethePrinter<'_> {
                 }
             }
             writeln!(self.inner)?;
+
+            num_written += 1;
+            if self.flush_every.is_some_and(|n| num_written % n == 0) {
+                self.inner.flush()?;
+            }
         }
         self.defined_constants.clear();
         Ok(())
@@ -239,6 +438,10 @@ impl<'a> AlethePrinter<'a> {
             global_vars: global_variables,
             defined_constants: HashMap::new(),
             smt_lib_strict: false,
+            real_format: RealFormat::default(),
+            dialect: Dialect::default(),
+            infix_arithmetic: false,
+            flush_every: None,
         }
     }
 
@@ -260,9 +463,22 @@ impl<'a> AlethePrinter<'a> {
         write!(self.inner, ")")
     }
 
+    fn write_infix(&mut self, op: Operator, args: &[Rc<Term>]) -> io::Result<()> {
+        write!(self.inner, "(")?;
+        args[0].print_with_sharing(self)?;
+        for arg in &args[1..] {
+            write!(self.inner, " {} ", op)?;
+            arg.print_with_sharing(self)?;
+        }
+        write!(self.inner, ")")
+    }
+
     fn write_raw_term(&mut self, term: &Term) -> io::Result<()> {
         match term {
             Term::Const(c) => {
+                if let (Constant::Real(r), RealFormat::Decimal { places }) = (c, self.real_format) {
+                    return write_decimal(self.inner, r, places);
+                }
                 if self.smt_lib_strict {
                     if let Constant::Integer(i) = c {
                         if i.is_negative() {
@@ -300,6 +516,8 @@ impl<'a> AlethePrinter<'a> {
             Term::Op(op, args) => {
                 if args.is_empty() {
                     write!(self.inner, "{}", op)
+                } else if self.infix_arithmetic && args.len() > 1 && is_infix_operator(*op) {
+                    self.write_infix(*op, args)
                 } else {
                     self.write_s_expr(op, args)
                 }
@@ -364,7 +582,7 @@ impl<'a> AlethePrinter<'a> {
             write!(self.inner, ")")?;
         }
 
-        if let [head, tail @ ..] = step.discharge.as_slice() {
+        if let ([head, tail @ ..], Dialect::Alethe) = (step.discharge.as_slice(), self.dialect) {
             let id = iter.get_premise(*head).id();
             write!(self.inner, " :discharge ({}", id)?;
             for discharge in tail {
@@ -388,6 +606,23 @@ impl<'a> AlethePrinter<'a> {
     }
 }
 
+/// Returns `true` if the operator is printed in infix form when `infix_arithmetic` is enabled.
+fn is_infix_operator(op: Operator) -> bool {
+    matches!(
+        op,
+        Operator::Add
+            | Operator::Sub
+            | Operator::Mult
+            | Operator::IntDiv
+            | Operator::RealDiv
+            | Operator::Mod
+            | Operator::LessThan
+            | Operator::GreaterThan
+            | Operator::LessEq
+            | Operator::GreaterEq
+    )
+}
+
 fn write_s_expr<H, T>(f: &mut fmt::Formatter, head: H, tail: &[T]) -> fmt::Result
 where
     H: fmt::Display,
@@ -447,6 +682,10 @@ impl fmt::Display for Term {
             global_vars: HashSet::new(),
             defined_constants: HashMap::new(),
             smt_lib_strict: false,
+            real_format: RealFormat::default(),
+            dialect: Dialect::default(),
+            infix_arithmetic: false,
+            flush_every: None,
         };
         printer.write_raw_term(self).unwrap();
         let result = std::str::from_utf8(&buf).unwrap();
@@ -473,7 +712,25 @@ impl fmt::Display for Constant {
                 }
             }
             Constant::String(s) => write!(f, "\"{}\"", escape_string(s)),
-            Constant::BitVec(val, width) => write!(f, "(_ bv{} {})", val, width), // TODO: comeback to this
+            Constant::BitVec(val, width, radix) => {
+                write_bitvector(f, val, width.to_usize().unwrap(), *radix)
+            }
+        }
+    }
+}
+
+/// Writes a bitvector literal with the given value and width, in the given notation.
+fn write_bitvector(
+    f: &mut fmt::Formatter,
+    value: &Integer,
+    width: usize,
+    radix: BitVecRadix,
+) -> fmt::Result {
+    match radix {
+        BitVecRadix::Decimal => write!(f, "(_ bv{} {})", value, width),
+        BitVecRadix::Binary => write!(f, "#b{:0>w$}", value.to_string_radix(2), w = width),
+        BitVecRadix::Hexadecimal => {
+            write!(f, "#x{:0>w$}", value.to_string_radix(16), w = width / 4)
         }
     }
 }
@@ -539,8 +796,8 @@ impl fmt::Display for Token {
             Token::Keyword(k) => write!(f, ":{}", k),
             Token::Numeral(n) => write!(f, "{}", n),
             Token::Decimal(r) => write!(f, "{}", r),
-            Token::Bitvector { value, width } => {
-                write!(f, "#b{v:0>w$b}", v = value, w = *width as usize)
+            Token::Bitvector { value, width, radix } => {
+                write_bitvector(f, value, *width as usize, *radix)
             }
             Token::String(s) => write!(f, "\"{}\"", escape_string(s)),
             Token::ReservedWord(r) => write!(f, "{}", r),
@@ -570,6 +827,23 @@ impl fmt::Display for ProblemPrelude {
     }
 }
 
+/// Writes a real constant as a decimal, rounded to the given number of places after the decimal
+/// point. See [`RealFormat::Decimal`].
+fn write_decimal(dest: &mut dyn io::Write, r: &Rational, places: usize) -> io::Result<()> {
+    let scale = Integer::from(Integer::u_pow_u(10, places as u32));
+    let rounded = (Rational::from(r.abs_ref()) * scale).round();
+
+    // We pad the digits with zeros, so that there is at least one digit before the point
+    let digits = format!("{:0>w$}", rounded.numer().to_string(), w = places + 1);
+    let (int_part, frac_part) = digits.split_at(digits.len() - places);
+    let frac_part = if places == 0 { "0" } else { frac_part };
+    if r.is_negative() {
+        write!(dest, "(- {}.{})", int_part, frac_part)
+    } else {
+        write!(dest, "{}.{}", int_part, frac_part)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -617,4 +891,245 @@ mod tests {
 
         assert_eq!(expected, std::str::from_utf8(&buf).unwrap());
     }
+
+    #[test]
+    fn test_pretty_term() {
+        use crate::parser::tests::parse_terms;
+
+        let mut pool = PrimitivePool::new();
+        let definitions = "
+            (declare-const p Bool)
+            (declare-const q Bool)
+            (declare-const r Bool)
+            (declare-fun f (Int) Bool)
+        ";
+        let [term, small] = parse_terms(
+            &mut pool,
+            definitions,
+            [
+                "(and p (and q (or r (f 1) (not (and p q r))) (forall ((x Int)) (f x))) r)",
+                "(and p q)",
+            ],
+        );
+        let expected = "\
+            (and\n  \
+              p\n  \
+              (and\n    \
+                q\n    \
+                (or r (f 1) (not (and p q r)))\n    \
+                (forall ((x Int)) (f x)))\n  \
+              r)";
+        let got = pretty_term(&term, 40);
+        assert_eq!(expected, got);
+        assert!(got.lines().all(|l| l.len() <= 40));
+
+        // Terms that fit in the given width are printed in a single line
+        assert_eq!(pretty_term(&small, 40), "(and p q)");
+        assert_eq!(pretty_term(&term, 1000), format!("{:#}", term));
+    }
+
+    #[test]
+    fn test_periodic_flushing() {
+        use crate::parser;
+        use std::io::BufWriter;
+
+        /// A writer that records the number of bytes written at each call to `flush`.
+        #[derive(Default)]
+        struct FlushRecorder {
+            buf: Vec<u8>,
+            flushes: Vec<usize>,
+        }
+
+        impl io::Write for FlushRecorder {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.buf.extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                self.flushes.push(self.buf.len());
+                Ok(())
+            }
+        }
+
+        let definitions: &[u8] = b"(declare-const p Bool)";
+        let proof: &[u8] = b"
+            (assume h1 p)
+            (step t2 (cl p) :rule hole)
+            (step t3 (cl p) :rule hole)
+            (step t4 (cl p) :rule hole)
+            (step t5 (cl) :rule hole)
+        ";
+        let (problem, proof, mut pool) =
+            parser::parse_instance(definitions, proof, parser::Config::new()).unwrap();
+
+        let mut recorder = FlushRecorder::default();
+        {
+            let mut writer = BufWriter::new(&mut recorder);
+            write_proof(&mut pool, &problem.prelude, &proof, false, &mut writer, 2).unwrap();
+        }
+        let output = std::str::from_utf8(&recorder.buf).unwrap();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), 5);
+
+        // The output should be flushed after the second and fourth commands, and once more at the
+        // end. Each flush must happen exactly at a command boundary
+        let line_end = |n: usize| lines[..n].iter().map(|l| l.len() + 1).sum::<usize>();
+        assert_eq!(
+            recorder.flushes,
+            vec![line_end(2), line_end(4), line_end(5)]
+        );
+    }
+
+    #[test]
+    fn test_dialect() {
+        use crate::parser;
+
+        let definitions: &[u8] = b"(declare-const p Bool) (declare-const y Int)";
+        let proof: &[u8] = b"
+            (anchor :step t1 :args ((:= (x Int) y)))
+            (step t1.t1 (cl (= x y)) :rule hole)
+            (step t1 (cl (= x y)) :rule hole)
+            (anchor :step t2)
+            (assume t2.h1 p)
+            (step t2.t1 (cl p) :rule hole)
+            (step t2 (cl (not p) p) :rule subproof :discharge (t2.h1))
+        ";
+        let (problem, proof, mut pool) =
+            parser::parse_instance(definitions, proof, parser::Config::new()).unwrap();
+        let mut print = |dialect| {
+            print_proof_to_string_in_dialect(&mut pool, &problem.prelude, &proof, false, dialect)
+        };
+
+        let alethe = print(Dialect::Alethe);
+        let lines: Vec<_> = alethe.lines().collect();
+        assert_eq!(lines[0], "(anchor :step t1 :args ((:= (x Int) y)))");
+        assert_eq!(
+            lines[6],
+            "(step t2 (cl (not p) p) :rule subproof :discharge (t2.h1))"
+        );
+
+        // The Alethe output can be parsed back
+        parser::parse_instance(definitions, alethe.as_bytes(), parser::Config::new()).unwrap();
+
+        let verit = print(Dialect::VeriT);
+        let lines: Vec<_> = verit.lines().collect();
+        assert_eq!(lines[0], "(anchor :step t1 :args ((:= x y)))");
+        assert_eq!(lines[6], "(step t2 (cl (not p) p) :rule subproof)");
+    }
+
+    #[test]
+    fn test_comments_round_trip() {
+        use crate::parser;
+
+        let definitions: &[u8] = b"(declare-const p Bool)";
+        let proof: &[u8] = b"
+            ; author: someone
+            (assume h1 p)
+            ;; checked by hand
+            ; see issue #1
+            (step t1 (cl p) :rule hole :premises (h1)) ; trailing comment
+            (anchor :step t2)
+            ; this comment is inside a subproof, and is dropped
+            (assume t2.h1 p)
+            (step t2 (cl (not p) p) :rule subproof :discharge (t2.h1))
+        ";
+        let config = parser::Config {
+            keep_comments: true,
+            ..parser::Config::new()
+        };
+        let (problem, proof, mut pool) =
+            parser::parse_instance(definitions, proof, config).unwrap();
+        assert_eq!(proof.comments["h1"], ["author: someone"]);
+        assert_eq!(proof.comments["t1"], ["checked by hand", "see issue #1"]);
+        assert_eq!(proof.comments["t2"], ["trailing comment"]);
+
+        let printed = print_proof_to_string(&mut pool, &problem.prelude, &proof, false);
+        let lines: Vec<_> = printed.lines().collect();
+        assert_eq!(lines[0], "; author: someone");
+        assert_eq!(lines[1], "(assume h1 p)");
+        assert_eq!(
+            lines[2..5],
+            [
+                "; checked by hand",
+                "; see issue #1",
+                "(step t1 (cl p) :rule hole :premises (h1))"
+            ]
+        );
+        assert!(!printed.contains("dropped"));
+
+        let (_, reparsed, _) =
+            parser::parse_instance(definitions, printed.as_bytes(), config).unwrap();
+        assert_eq!(reparsed.comments, proof.comments);
+
+        // Comments are not kept by default
+        let (_, proof, _) =
+            parser::parse_instance(definitions, printed.as_bytes(), parser::Config::new()).unwrap();
+        assert!(proof.comments.is_empty());
+    }
+
+    #[test]
+    fn test_real_format() {
+        use crate::parser;
+
+        let definitions: &[u8] = b"(declare-const x Real)";
+        let proof: &[u8] = b"
+            (step t1 (cl (= x 1/3)) :rule hole)
+            (step t2 (cl (= x (- 2/3))) :rule hole)
+            (step t3 (cl (= x 2.5)) :rule hole)
+        ";
+        let config = parser::Config::new();
+        let (problem, proof, mut pool) =
+            parser::parse_instance(definitions, proof, config).unwrap();
+        let mut print = |format| {
+            print_proof_to_string_with_format(&mut pool, &problem.prelude, &proof, false, format)
+        };
+
+        assert_eq!(
+            print(RealFormat::Fraction),
+            "(step t1 (cl (= x 1/3)) :rule hole)\n\
+            (step t2 (cl (= x (- 2/3))) :rule hole)\n\
+            (step t3 (cl (= x 5/2)) :rule hole)\n"
+        );
+        assert_eq!(
+            print(RealFormat::Decimal { places: 4 }),
+            "(step t1 (cl (= x 0.3333)) :rule hole)\n\
+            (step t2 (cl (= x (- 0.6667))) :rule hole)\n\
+            (step t3 (cl (= x 2.5000)) :rule hole)\n"
+        );
+        assert_eq!(
+            print(RealFormat::Decimal { places: 0 }),
+            "(step t1 (cl (= x 0.0)) :rule hole)\n\
+            (step t2 (cl (= x (- 1.0))) :rule hole)\n\
+            (step t3 (cl (= x 3.0)) :rule hole)\n"
+        );
+    }
+
+    #[test]
+    fn test_infix_arithmetic() {
+        use crate::parser;
+
+        let definitions: &[u8] =
+            b"(declare-const a Int) (declare-const b Int) (declare-const p Bool)";
+        let proof: &[u8] = b"
+            (step t1 (cl (= (+ a b) (* 2 (- a) b))) :rule hole)
+            (step t2 (cl (or p (<= (+ a b 1) (div a 2)))) :rule hole)
+        ";
+        let config = parser::Config::new();
+        let (problem, proof, mut pool) =
+            parser::parse_instance(definitions, proof, config).unwrap();
+
+        assert_eq!(
+            print_proof_to_string_with_infix_arithmetic(&mut pool, &problem.prelude, &proof, false),
+            "(step t1 (cl (= (a + b) (2 * (- a) * b))) :rule hole)\n\
+            (step t2 (cl (or p ((a + b + 1) <= (a div 2)))) :rule hole)\n"
+        );
+
+        // By default, the output is not affected
+        assert_eq!(
+            print_proof_to_string(&mut pool, &problem.prelude, &proof, false),
+            "(step t1 (cl (= (+ a b) (* 2 (- a) b))) :rule hole)\n\
+            (step t2 (cl (or p (<= (+ a b 1) (div a 2)))) :rule hole)\n"
+        );
+    }
 }
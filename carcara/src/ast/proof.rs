@@ -1,7 +1,8 @@
-use super::{ProofIter, Rc, SortedVar, Term};
+use super::{ProofIter, ProofNode, Rc, SortedVar, Term};
+use std::collections::HashMap;
 
 /// A proof in the Alethe format.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Proof {
     /// The constants defined in the proof using `define-fun` with arity zero.
     ///
@@ -10,6 +11,13 @@ pub struct Proof {
 
     /// The proof commands.
     pub commands: Vec<ProofCommand>,
+
+    /// The comments that precede root-level commands, indexed by the command id. These are only
+    /// kept by the parser if [`parser::Config::keep_comments`] is enabled, and are printed back
+    /// before their commands.
+    ///
+    /// [`parser::Config::keep_comments`]: crate::parser::Config::keep_comments
+    pub comments: HashMap<String, Vec<String>>,
 }
 
 /// A proof command.
@@ -45,6 +53,9 @@ pub struct ProofStep {
     pub premises: Vec<(usize, usize)>,
 
     /// The step arguments, given via the `:args` attribute.
+    ///
+    /// Step arguments are always terms. Assignments of the form `(:= (<symbol> <sort>) <term>)` are
+    /// only allowed as `anchor` arguments, and are rejected by the parser if given to a step.
     pub args: Vec<Rc<Term>>,
 
     /// The local premises that this step discharges, given via the `:discharge` attribute, and
@@ -87,6 +98,33 @@ impl Proof {
     pub fn iter(&self) -> ProofIter {
         ProofIter::new(&self.commands)
     }
+
+    /// Returns `true` if the last command of the proof is a step concluding the empty clause, that
+    /// is, if the proof is a complete refutation.
+    pub fn concludes_empty_clause(&self) -> bool {
+        matches!(self.commands.last(), Some(ProofCommand::Step(s)) if s.clause.is_empty())
+    }
+
+    /// Returns a copy of this proof that contains only the commands that are needed to reach its
+    /// conclusion, that is, the first step that concludes the empty clause, or the last command if
+    /// no step does so. All other commands, including unused ones inside subproofs, are removed,
+    /// and the premise indices are adjusted accordingly.
+    ///
+    /// This is done by converting the proof to a [`ProofNode`], which implicitly prunes unused
+    /// commands, and back. Because of that, the commands in the resulting proof may be in a
+    /// different order than in the original proof, though every command still comes after all of
+    /// its premises.
+    pub fn minimize(&self) -> Proof {
+        if self.commands.is_empty() {
+            return self.clone();
+        }
+        let node = ProofNode::from_commands(self.commands.clone());
+        Proof {
+            constant_definitions: self.constant_definitions.clone(),
+            commands: node.into_commands(),
+            comments: self.comments.clone(),
+        }
+    }
 }
 
 impl ProofCommand {
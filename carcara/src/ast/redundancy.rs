@@ -0,0 +1,28 @@
+//! This module implements `find_redundant_structural_steps`, which finds steps that can be removed
+//! from a proof without changing it.
+
+use super::{Proof, ProofCommand};
+
+/// Returns the ids of the `reordering` and `contraction` steps in a proof that don't change their
+/// premise, that is, whose conclusion is exactly equal to the clause of their single premise, in
+/// the same order. These steps can be removed from the proof, replacing every reference to them by
+/// a reference to their premise.
+pub fn find_redundant_structural_steps(proof: &Proof) -> Vec<String> {
+    let mut result = Vec::new();
+    let mut iter = proof.iter();
+    while let Some(command) = iter.next() {
+        let ProofCommand::Step(step) = command else {
+            continue;
+        };
+        if !matches!(step.rule.as_str(), "reordering" | "contraction") {
+            continue;
+        }
+        if let [premise] = step.premises.as_slice() {
+            let premise = iter.get_premise(*premise);
+            if premise.clause() == step.clause.as_slice() {
+                result.push(step.id.clone());
+            }
+        }
+    }
+    result
+}
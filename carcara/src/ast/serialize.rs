@@ -0,0 +1,336 @@
+//! Serialization of proofs, available with the `serde` feature.
+//!
+//! Since terms are hash consed, serializing them directly would duplicate every shared subterm,
+//! which can make the output exponentially larger than the proof. Instead, a proof is serialized as
+//! a flat table of terms, in which each term refers to its subterms by their index in the table.
+//! When loading the proof back, every term in the table is added to a term pool, so the sharing
+//! between terms is reconstructed.
+
+use super::*;
+use indexmap::IndexSet;
+use rug::Integer;
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// An error that can occur when loading a serialized proof.
+#[derive(Debug, PartialEq, Eq, Error)]
+#[error("invalid term index in serialized proof: {0}")]
+pub struct InvalidTermIndex(pub usize);
+
+#[derive(Debug, Serialize, Deserialize)]
+enum FlatTerm {
+    Const(Constant),
+    Var(String, usize),
+    App(usize, Vec<usize>),
+    Op(Operator, Vec<usize>),
+    Sort(FlatSort),
+    Binder(Binder, Vec<(String, usize)>, usize),
+    Let(Vec<(String, usize)>, usize),
+    ParamOp {
+        op: ParamOperator,
+        op_args: Vec<usize>,
+        args: Vec<usize>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum FlatSort {
+    Function(Vec<usize>),
+    Atom(String, Vec<usize>),
+    Var(String),
+    Bool,
+    Int,
+    Real,
+    String,
+    RegLan,
+    Array(usize, usize),
+    BitVec(Integer),
+    ParamSort(Vec<usize>, usize),
+    RareList,
+    Type,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum FlatCommand {
+    Assume {
+        id: String,
+        term: usize,
+    },
+    Step {
+        id: String,
+        clause: Vec<usize>,
+        rule: String,
+        premises: Vec<(usize, usize)>,
+        args: Vec<usize>,
+        discharge: Vec<(usize, usize)>,
+    },
+    Subproof {
+        commands: Vec<FlatCommand>,
+        args: Vec<FlatAnchorArg>,
+        context_id: usize,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+enum FlatAnchorArg {
+    Variable(String, usize),
+    Assign(String, usize, usize),
+}
+
+/// A proof in a form that can be serialized and deserialized with `serde`.
+///
+/// To serialize a proof, it is usually enough to serialize the `Proof` itself, which is converted
+/// to this type internally. To load a serialized proof, it must be deserialized into this type, and
+/// then converted back into a `Proof` using [`SerializedProof::into_proof`], which adds all of its
+/// terms to a term pool.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SerializedProof {
+    terms: Vec<FlatTerm>,
+    constant_definitions: Vec<(String, usize)>,
+    commands: Vec<FlatCommand>,
+    #[serde(default)]
+    comments: HashMap<String, Vec<String>>,
+}
+
+impl From<&Proof> for SerializedProof {
+    fn from(proof: &Proof) -> Self {
+        let mut table = TermTable::default();
+        let constant_definitions = proof
+            .constant_definitions
+            .iter()
+            .map(|(name, value)| (name.clone(), table.index(value)))
+            .collect();
+        let commands = table.flatten_commands(&proof.commands);
+        Self {
+            terms: table.flat,
+            constant_definitions,
+            commands,
+            comments: proof.comments.clone(),
+        }
+    }
+}
+
+impl Serialize for Proof {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        SerializedProof::from(self).serialize(serializer)
+    }
+}
+
+impl SerializedProof {
+    /// Converts the serialized proof back into a `Proof`, adding all of its terms to `pool`.
+    ///
+    /// Since the terms are added to the pool, they will be shared with any other terms in the pool,
+    /// such as the ones in the problem that the proof refers to.
+    pub fn into_proof(self, pool: &mut dyn TermPool) -> Result<Proof, InvalidTermIndex> {
+        let mut terms: Vec<Rc<Term>> = Vec::with_capacity(self.terms.len());
+        for flat in self.terms {
+            let term = {
+                // Subterms always appear in the table before the terms that contain them, so every
+                // index must refer to a term that was already added
+                let get = |i: usize| terms.get(i).cloned().ok_or(InvalidTermIndex(i));
+                let get_all = |v: Vec<usize>| v.into_iter().map(get).collect::<Result<_, _>>();
+                let get_bindings = |v: Vec<(String, usize)>| {
+                    v.into_iter()
+                        .map(|(name, i)| Ok((name, get(i)?)))
+                        .collect::<Result<_, _>>()
+                        .map(BindingList)
+                };
+                match flat {
+                    FlatTerm::Const(c) => Term::Const(c),
+                    FlatTerm::Var(name, sort) => Term::Var(name, get(sort)?),
+                    FlatTerm::App(f, args) => Term::App(get(f)?, get_all(args)?),
+                    FlatTerm::Op(op, args) => Term::Op(op, get_all(args)?),
+                    FlatTerm::Sort(sort) => Term::Sort(match sort {
+                        FlatSort::Function(v) => Sort::Function(get_all(v)?),
+                        FlatSort::Atom(name, v) => Sort::Atom(name, get_all(v)?),
+                        FlatSort::Var(name) => Sort::Var(name),
+                        FlatSort::Bool => Sort::Bool,
+                        FlatSort::Int => Sort::Int,
+                        FlatSort::Real => Sort::Real,
+                        FlatSort::String => Sort::String,
+                        FlatSort::RegLan => Sort::RegLan,
+                        FlatSort::Array(x, y) => Sort::Array(get(x)?, get(y)?),
+                        FlatSort::BitVec(w) => Sort::BitVec(w),
+                        FlatSort::ParamSort(v, s) => Sort::ParamSort(get_all(v)?, get(s)?),
+                        FlatSort::RareList => Sort::RareList,
+                        FlatSort::Type => Sort::Type,
+                    }),
+                    FlatTerm::Binder(b, bindings, inner) => {
+                        Term::Binder(b, get_bindings(bindings)?, get(inner)?)
+                    }
+                    FlatTerm::Let(bindings, inner) => {
+                        Term::Let(get_bindings(bindings)?, get(inner)?)
+                    }
+                    FlatTerm::ParamOp { op, op_args, args } => Term::ParamOp {
+                        op,
+                        op_args: get_all(op_args)?,
+                        args: get_all(args)?,
+                    },
+                }
+            };
+            terms.push(pool.add(term));
+        }
+
+        let get = |i: usize| terms.get(i).cloned().ok_or(InvalidTermIndex(i));
+        let constant_definitions = self
+            .constant_definitions
+            .into_iter()
+            .map(|(name, i)| Ok((name, get(i)?)))
+            .collect::<Result<_, _>>()?;
+        let commands = unflatten_commands(self.commands, &get)?;
+        Ok(Proof {
+            constant_definitions,
+            commands,
+            comments: self.comments,
+        })
+    }
+}
+
+fn unflatten_commands(
+    commands: Vec<FlatCommand>,
+    get: &impl Fn(usize) -> Result<Rc<Term>, InvalidTermIndex>,
+) -> Result<Vec<ProofCommand>, InvalidTermIndex> {
+    let get_all = |v: Vec<usize>| v.into_iter().map(get).collect::<Result<_, _>>();
+    commands
+        .into_iter()
+        .map(|c| {
+            Ok(match c {
+                FlatCommand::Assume { id, term } => ProofCommand::Assume { id, term: get(term)? },
+                FlatCommand::Step {
+                    id,
+                    clause,
+                    rule,
+                    premises,
+                    args,
+                    discharge,
+                } => ProofCommand::Step(ProofStep {
+                    id,
+                    clause: get_all(clause)?,
+                    rule,
+                    premises,
+                    args: get_all(args)?,
+                    discharge,
+                }),
+                FlatCommand::Subproof { commands, args, context_id } => {
+                    let args = args
+                        .into_iter()
+                        .map(|a| {
+                            Ok(match a {
+                                FlatAnchorArg::Variable(name, sort) => {
+                                    AnchorArg::Variable((name, get(sort)?))
+                                }
+                                FlatAnchorArg::Assign(name, sort, value) => {
+                                    AnchorArg::Assign((name, get(sort)?), get(value)?)
+                                }
+                            })
+                        })
+                        .collect::<Result<_, _>>()?;
+                    ProofCommand::Subproof(Subproof {
+                        commands: unflatten_commands(commands, get)?,
+                        args,
+                        context_id,
+                    })
+                }
+            })
+        })
+        .collect()
+}
+
+#[derive(Default)]
+struct TermTable {
+    indices: IndexSet<Rc<Term>>,
+    flat: Vec<FlatTerm>,
+}
+
+impl TermTable {
+    fn index(&mut self, term: &Rc<Term>) -> usize {
+        if let Some(i) = self.indices.get_index_of(term) {
+            return i;
+        }
+        let flat = match term.as_ref() {
+            Term::Const(c) => FlatTerm::Const(c.clone()),
+            Term::Var(name, sort) => FlatTerm::Var(name.clone(), self.index(sort)),
+            Term::App(f, args) => FlatTerm::App(self.index(f), self.index_all(args)),
+            Term::Op(op, args) => FlatTerm::Op(*op, self.index_all(args)),
+            Term::Sort(sort) => FlatTerm::Sort(match sort {
+                Sort::Function(v) => FlatSort::Function(self.index_all(v)),
+                Sort::Atom(name, v) => FlatSort::Atom(name.clone(), self.index_all(v)),
+                Sort::Var(name) => FlatSort::Var(name.clone()),
+                Sort::Bool => FlatSort::Bool,
+                Sort::Int => FlatSort::Int,
+                Sort::Real => FlatSort::Real,
+                Sort::String => FlatSort::String,
+                Sort::RegLan => FlatSort::RegLan,
+                Sort::Array(x, y) => FlatSort::Array(self.index(x), self.index(y)),
+                Sort::BitVec(w) => FlatSort::BitVec(w.clone()),
+                Sort::ParamSort(v, s) => FlatSort::ParamSort(self.index_all(v), self.index(s)),
+                Sort::RareList => FlatSort::RareList,
+                Sort::Type => FlatSort::Type,
+            }),
+            Term::Binder(b, bindings, inner) => {
+                FlatTerm::Binder(*b, self.index_bindings(&bindings.0), self.index(inner))
+            }
+            Term::Let(bindings, inner) => {
+                FlatTerm::Let(self.index_bindings(&bindings.0), self.index(inner))
+            }
+            Term::ParamOp { op, op_args, args } => FlatTerm::ParamOp {
+                op: *op,
+                op_args: self.index_all(op_args),
+                args: self.index_all(args),
+            },
+        };
+        let (i, _) = self.indices.insert_full(term.clone());
+        self.flat.push(flat);
+        i
+    }
+
+    fn index_all(&mut self, terms: &[Rc<Term>]) -> Vec<usize> {
+        terms.iter().map(|t| self.index(t)).collect()
+    }
+
+    fn index_bindings(&mut self, bindings: &[SortedVar]) -> Vec<(String, usize)> {
+        bindings
+            .iter()
+            .map(|(name, value)| (name.clone(), self.index(value)))
+            .collect()
+    }
+
+    fn flatten_commands(&mut self, commands: &[ProofCommand]) -> Vec<FlatCommand> {
+        commands
+            .iter()
+            .map(|c| match c {
+                ProofCommand::Assume { id, term } => FlatCommand::Assume {
+                    id: id.clone(),
+                    term: self.index(term),
+                },
+                ProofCommand::Step(s) => FlatCommand::Step {
+                    id: s.id.clone(),
+                    clause: self.index_all(&s.clause),
+                    rule: s.rule.clone(),
+                    premises: s.premises.clone(),
+                    args: self.index_all(&s.args),
+                    discharge: s.discharge.clone(),
+                },
+                ProofCommand::Subproof(s) => FlatCommand::Subproof {
+                    commands: self.flatten_commands(&s.commands),
+                    args: s
+                        .args
+                        .iter()
+                        .map(|a| match a {
+                            AnchorArg::Variable((name, sort)) => {
+                                FlatAnchorArg::Variable(name.clone(), self.index(sort))
+                            }
+                            AnchorArg::Assign((name, sort), value) => FlatAnchorArg::Assign(
+                                name.clone(),
+                                self.index(sort),
+                                self.index(value),
+                            ),
+                        })
+                        .collect(),
+                    context_id: s.context_id,
+                },
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,86 @@
+//! This module implements `duplicate_subterms`, which finds the subterms that are repeated the
+//! most in a proof.
+
+use super::{AnchorArg, Proof, ProofCommand, Rc, Term};
+use indexmap::{IndexMap, IndexSet};
+
+/// Returns the subterms that appear at least `min_count` times in the proof, together with how
+/// many times they appear, sorted from the most to the least duplicated. Among terms with the same
+/// count, a term always comes before its own subterms.
+///
+/// A subterm is counted every time it would be written if the proof was printed without sharing,
+/// so a term that appears twice inside a term that appears three times is counted six times.
+/// Constants, variables and sorts are never reported, since they are not worth naming. This can be
+/// used to find good candidates for `let` bindings or term sharing when writing a proof.
+pub fn duplicate_subterms(proof: &Proof, min_count: usize) -> Vec<(Rc<Term>, usize)> {
+    let mut counts: IndexMap<Rc<Term>, usize> = IndexMap::new();
+    for command in proof.iter() {
+        let roots: Vec<&Rc<Term>> = match command {
+            ProofCommand::Assume { term, .. } => vec![term],
+            ProofCommand::Step(s) => s.clause.iter().chain(&s.args).collect(),
+            ProofCommand::Subproof(s) => s
+                .args
+                .iter()
+                .filter_map(AnchorArg::as_assign)
+                .map(|(_, value)| value)
+                .collect(),
+        };
+        for term in roots {
+            *counts.entry(term.clone()).or_default() += 1;
+        }
+    }
+
+    // Since terms are shared, a term may be reached from many different parents. To avoid
+    // traversing it once for every occurrence, we visit the terms in topological order, so that each
+    // term is only visited after all of its parents, and add its count to each of its children
+    let mut order = IndexSet::new();
+    for root in counts.keys() {
+        postorder(root, &mut order);
+    }
+    for term in order.iter().rev() {
+        let count = counts.get(term).copied().unwrap_or_default();
+        for child in children(term) {
+            *counts.entry(child.clone()).or_default() += count;
+        }
+    }
+
+    let mut result: Vec<_> = order
+        .into_iter()
+        .rev()
+        .map(|term| {
+            let count = counts[&term];
+            (term, count)
+        })
+        .filter(|(term, count)| {
+            *count >= min_count && !(term.is_const() || term.is_var() || term.is_sort())
+        })
+        .collect();
+    result.sort_by(|(_, a), (_, b)| b.cmp(a));
+    result
+}
+
+fn postorder(term: &Rc<Term>, visited: &mut IndexSet<Rc<Term>>) {
+    if visited.contains(term) {
+        return;
+    }
+    for child in children(term) {
+        postorder(child, visited);
+    }
+    visited.insert(term.clone());
+}
+
+/// Returns the direct subterms of a term, not including the sorts of bound variables.
+fn children(term: &Rc<Term>) -> Vec<&Rc<Term>> {
+    match term.as_ref() {
+        Term::Const(_) | Term::Var(..) | Term::Sort(_) => Vec::new(),
+        Term::App(f, args) => std::iter::once(f).chain(args).collect(),
+        Term::Op(_, args) => args.iter().collect(),
+        Term::Binder(_, _, inner) => vec![inner],
+        Term::Let(bindings, inner) => bindings
+            .iter()
+            .map(|(_, value)| value)
+            .chain(std::iter::once(inner))
+            .collect(),
+        Term::ParamOp { op_args, args, .. } => op_args.iter().chain(args).collect(),
+    }
+}
@@ -71,7 +71,9 @@ impl Substitution {
         map: IndexMap<Rc<Term>, Rc<Term>>,
     ) -> SubstitutionResult<Self> {
         for (k, v) in &map {
-            if !k.is_var() && !k.is_sort_var() {
+            // Sort variables of parametric sorts may also be substituted, to instantiate them
+            let is_param_sort_var = matches!(k.as_sort(), Some(Sort::Var(_)));
+            if !k.is_var() && !k.is_sort_var() && !is_param_sort_var {
                 return Err(SubstitutionError::NotAVariable(k.clone()));
             }
             if pool.sort(k) != pool.sort(v) {
@@ -91,6 +93,18 @@ impl Substitution {
         self.map.is_empty()
     }
 
+    /// Returns an estimate of the number of bytes used by the substitution's mappings and cache.
+    pub(crate) fn memory_estimate(&self) -> usize {
+        let entries = self.map.len() + self.cache.len();
+        let renamed: usize = self
+            .should_be_renamed
+            .iter()
+            .flatten()
+            .map(String::len)
+            .sum();
+        entries * 2 * std::mem::size_of::<Rc<Term>>() + renamed
+    }
+
     /// Extends the substitution by adding a new mapping from `x` to `t`. This returns an error if
     /// the sorts of the given terms are not the same, or if `x` is not a variable term.
     pub(crate) fn insert(
@@ -211,8 +225,21 @@ impl Substitution {
                 self.apply_to_binder(pool, term, *binder, binding_list.as_ref(), inner)
             }
             Term::Let(binding_list, inner) => {
+                self.compute_should_be_renamed(pool);
                 let (new_bindings, mut renaming) =
                     self.rename_binding_list(pool, binding_list, true);
+
+                // The values in the binding list are not in the scope of the `let` term, so they
+                // may not refer to the variables it introduces, but they may contain free
+                // variables in the domain of the substitution. Therefore, we apply the
+                // substitution to them, and not the renaming
+                let new_bindings = BindingList(
+                    new_bindings
+                        .0
+                        .into_iter()
+                        .map(|(var, value)| (var, self.apply(pool, &value)))
+                        .collect(),
+                );
                 let new_term = if renaming.is_empty() {
                     self.apply(pool, inner)
                 } else {
@@ -333,7 +360,8 @@ impl Substitution {
     /// new binding list, with the bindings renamed. If no variable needs to be renamed, this just
     /// returns a clone of the binding list and an empty substitution. The name chosen when renaming
     /// a variable is the old name with `'` appended. If the binding list is a "value" list, like in
-    /// a `let` or `lambda` term, `is_value_list` should be true.
+    /// a `let` term, `is_value_list` should be true. Note that the values in such a list are
+    /// returned unchanged, since they are not in the scope of the variables being renamed.
     fn rename_binding_list(
         &mut self,
         pool: &mut dyn TermPool,
@@ -378,21 +406,33 @@ impl Substitution {
                     new_substitution.insert(pool, old, new).unwrap();
                     new_vars.insert(new_var.clone());
                 }
-
-                // If the binding list is a "value" list, we need to apply the current substitution
-                // to each variable's value
-                let new_value = if is_value_list {
-                    new_substitution.apply(pool, value)
-                } else {
-                    value.clone()
-                };
-                (new_var, new_value)
+                (new_var, value.clone())
             })
             .collect();
         (BindingList(new_binding_list), new_substitution)
     }
 }
 
+/// Applies the substitution given by `map` to `term`, and returns the result.
+///
+/// This is the same kind of substitution that the checker applies when entering a subproof
+/// context. In particular, the substitution is capture-avoiding: if a binder in `term` binds a
+/// variable that appears free in one of the terms being substituted in, that bound variable is
+/// renamed (by appending `'` to its name) before the substitution is applied. For example, applying
+/// `{x -> y}` to `(forall ((y Int)) (> y x))` results in `(forall ((y' Int)) (> y' y))`.
+///
+/// This returns an error if any term in the domain of `map` is not a variable, or if any term is
+/// mapped to a term of a different sort. To apply the same substitution to many terms, construct a
+/// [`Substitution`] instead, so the work of checking and preparing it is not repeated.
+pub fn substitute(
+    pool: &mut dyn TermPool,
+    term: &Rc<Term>,
+    map: &IndexMap<Rc<Term>, Rc<Term>>,
+) -> SubstitutionResult<Rc<Term>> {
+    let mut substitution = Substitution::new(pool, map.clone())?;
+    Ok(substitution.apply(pool, term))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -472,7 +512,54 @@ mod tests {
             "(forall ((y Bool)) (and y (> x 0)))" [x -> y] =>
                 "(forall ((y' Bool)) (and y' (> y 0)))",
 
-            // TODO: Add tests for `choice`, `let`, and `lambda` terms
+            // The values in a `let` term are in the outer scope, so the substitution is applied to
+            // them, but not to the variables that they are bound to
+            "(let ((z x)) (> z x))" [x -> y] => "(let ((z y)) (> z y))",
+            "(let ((x 1)) (> x 0))" [x -> y] => "(let ((x' 1)) (> x' 0))",
+            "(let ((x x)) (> x 0))" [x -> y] => "(let ((x' y)) (> x' 0))",
+            "(let ((y x)) (> y 0))" [x -> y] => "(let ((y' y)) (> y' 0))",
+
+            // Nested `let` terms that shadow a variable
+            "(let ((z x)) (let ((z (+ z x))) (> z x)))" [x -> y] =>
+                "(let ((z y)) (let ((z (+ z y))) (> z y)))",
+
+            // TODO: Add tests for `choice` and `lambda` terms
         }
     }
+
+    #[test]
+    fn test_substitute() {
+        let definitions = "
+            (declare-fun x () Int)
+            (declare-fun y () Int)
+            (declare-fun p () Bool)
+        ";
+        let mut pool = PrimitivePool::new();
+        let mut parser = Parser::new(&mut pool, Config::new(), definitions.as_bytes()).unwrap();
+        parser.parse_problem().unwrap();
+
+        let [x, y, p, original, expected] = [
+            "x",
+            "y",
+            "p",
+            "(forall ((y Int)) (and p (> y x)))",
+            "(forall ((y' Int)) (and p (> y' y)))",
+        ]
+        .map(|s| {
+            parser.reset(s.as_bytes()).unwrap();
+            parser.parse_term().unwrap()
+        });
+
+        // The bound `y` must be renamed, or else it would capture the substituted `y`
+        let map = IndexMap::from([(x.clone(), y.clone())]);
+        assert_eq!(expected, substitute(&mut pool, &original, &map).unwrap());
+
+        // Substituting a non-variable term is an error
+        let map = IndexMap::from([(original.clone(), y)]);
+        assert!(substitute(&mut pool, &original, &map).is_err());
+
+        // Mapping a variable to a term of a different sort is an error
+        let map = IndexMap::from([(x, p)]);
+        assert!(substitute(&mut pool, &original, &map).is_err());
+    }
 }
@@ -7,6 +7,14 @@ use std::{collections::HashSet, hash::Hash, ops::Deref};
 /// A term.
 ///
 /// Many additional methods are implemented in [`Rc<Term>`].
+///
+/// `Term` implements `Eq` and `Hash` structurally, so it can be used as a key in hash maps and
+/// sets. Since subterms are compared and hashed by their pointers (see [`Rc`]), this is only
+/// equivalent to a full structural comparison for terms whose subterms were added to the same
+/// [`TermPool`]. Terms from the same pool can also be used as keys directly through `Rc<Term>`,
+/// which is cheaper to compare and hash. Note that this equality is stricter than the one
+/// implemented by [`Polyeq`](super::Polyeq), which also considers terms that are equal modulo
+/// reordering of equalities, for example.
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub enum Term {
     /// A constant term.
@@ -47,6 +55,8 @@ pub enum Term {
 }
 
 /// The sort of a term.
+///
+/// Like [`Term`], this implements `Eq` and `Hash` structurally.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Sort {
     /// A function sort.
@@ -103,6 +113,7 @@ pub type SortedVar = (String, Rc<Term>);
 
 /// A constant term.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Constant {
     /// An integer constant term.
     Integer(Integer),
@@ -113,12 +124,46 @@ pub enum Constant {
     /// A string literal term.
     String(String),
 
-    /// A bitvector literal term.
-    BitVec(Integer, Integer),
+    /// A bitvector literal term, with its value, width, and the radix in which it was written.
+    BitVec(Integer, Integer, BitVecRadix),
+}
+
+/// The notation used to write a bitvector literal.
+///
+/// This is only used to print the literal back in the same notation it was written in. Two
+/// bitvector literals with the same value and width are considered equal regardless of their
+/// radix, so all instances of this type compare as equal, and hash to the same value. Since terms
+/// are hash-consed, this means that, in a given pool, every occurrence of a literal is printed in
+/// the notation in which it was first added to the pool.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BitVecRadix {
+    /// The indexed notation, e.g. `(_ bv10 4)`.
+    #[default]
+    Decimal,
+
+    /// The binary notation, e.g. `#b1010`.
+    Binary,
+
+    /// The hexadecimal notation, e.g. `#x0a`.
+    Hexadecimal,
+}
+
+impl PartialEq for BitVecRadix {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for BitVecRadix {}
+
+impl std::hash::Hash for BitVecRadix {
+    fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
 }
 
 /// A binder, either a quantifier (`forall` or `exists`), `choice`, or `lambda`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Binder {
     /// The `forall` quantifier.
     Forall,
@@ -143,6 +188,7 @@ pub struct BindingList(pub Vec<SortedVar>);
 
 /// The operator of an operation term.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Operator {
     /// The `true` boolean constant.
     True,
@@ -379,6 +425,7 @@ pub enum Operator {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ParamOperator {
     // Indexed operators
     BvExtract,
@@ -658,7 +705,11 @@ impl Term {
 
     /// Constructs a new bv term.
     pub fn new_bv(value: impl Into<Integer>, width: impl Into<Integer>) -> Self {
-        Term::Const(Constant::BitVec(value.into(), width.into()))
+        Term::Const(Constant::BitVec(
+            value.into(),
+            width.into(),
+            BitVecRadix::default(),
+        ))
     }
 
     /// Constructs a new variable term.
@@ -747,7 +798,7 @@ impl Term {
     /// term is a bitvector constant.
     pub fn as_bitvector(&self) -> Option<(Integer, Integer)> {
         match self {
-            Term::Const(Constant::BitVec(v, w)) => Some((v.clone(), w.clone())),
+            Term::Const(Constant::BitVec(v, w, _)) => Some((v.clone(), w.clone())),
             _ => None,
         }
     }
@@ -982,7 +1033,7 @@ impl Constant {
             Constant::Integer(_) => Sort::Int,
             Constant::Real(_) => Sort::Real,
             Constant::String(_) => Sort::String,
-            Constant::BitVec(_, width) => Sort::BitVec(width.clone()),
+            Constant::BitVec(_, width, _) => Sort::BitVec(width.clone()),
         }
     }
 
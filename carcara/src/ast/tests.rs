@@ -36,6 +36,26 @@ fn test_free_vars() {
     );
 }
 
+#[test]
+fn test_pool_stats() {
+    let mut pool = PrimitivePool::new();
+    let before = pool.stats();
+
+    let [_] = parse_terms(
+        &mut pool,
+        "(declare-fun f (Int) Int) (declare-fun a () Int)",
+        ["(forall ((x Int)) (= (f x) (f a)))"],
+    );
+    let after = pool.stats();
+    assert!(after.total > before.total);
+    assert_eq!(after.binders, before.binders + 1);
+    assert_eq!(after.applications, before.applications + 2);
+
+    // Adding a term that is already in the pool doesn't change the statistics
+    let [_] = parse_terms(&mut pool, "(declare-fun a () Int)", ["a"]);
+    assert_eq!(after, pool.stats());
+}
+
 #[test]
 fn test_polyeq() {
     enum TestType {
@@ -123,6 +143,97 @@ fn test_polyeq() {
     );
 }
 
+#[test]
+fn test_polyeq_var_sorts() {
+    use crate::ast::{Rc, Sort, Term};
+
+    // Two variables with the same name but different sorts are distinct, in every comparison mode
+    let mut pool = PrimitivePool::new();
+    let [int, real] = [Sort::Int, Sort::Real].map(|s| pool.add(Term::Sort(s)));
+    let x_int = pool.add(Term::new_var("x", int));
+    let x_real = pool.add(Term::new_var("x", real));
+    let modes = || {
+        [
+            Polyeq::new(),
+            Polyeq::new().mod_reordering(true),
+            Polyeq::new().mod_reordering(true).alpha_equiv(true),
+            Polyeq::new().mod_nary(true),
+        ]
+    };
+    for mut comp in modes() {
+        assert!(!comp.eq(&x_int, &x_real));
+        assert!(comp.eq(&x_int, &x_int));
+    }
+
+    // The same holds for bound variables when checking for alpha-equivalence
+    let [a, b]: [Rc<Term>; 2] = parse_terms(
+        &mut pool,
+        "",
+        ["(forall ((x Int)) (= x x))", "(forall ((x Real)) (= x x))"],
+    );
+    for mut comp in modes() {
+        assert!(!comp.eq(&a, &b));
+    }
+}
+
+#[test]
+fn test_atom_sort_equality() {
+    use crate::ast::{Sort, Term};
+
+    let mut pool = PrimitivePool::new();
+    let int = pool.add(Term::Sort(Sort::Int));
+    let bool_sort = pool.add(Term::Sort(Sort::Bool));
+    let mut list = |args: Vec<_>| pool.add(Term::Sort(Sort::Atom("List".to_owned(), args)));
+    let list_int = list(vec![int.clone()]);
+    let other_list_int = list(vec![int]);
+    let list_bool = list(vec![bool_sort]);
+    let nullary_list = list(Vec::new());
+
+    // User sorts with the same name are only equal if their arguments are also equal
+    assert_eq!(list_int, other_list_int);
+    assert_ne!(list_int, list_bool);
+    assert_ne!(list_int, nullary_list);
+    assert_ne!(list_bool, nullary_list);
+    assert!(Polyeq::new().eq(&list_int, &other_list_int));
+    assert!(!Polyeq::new().eq(&list_int, &list_bool));
+    assert!(!Polyeq::new().eq(&list_int, &nullary_list));
+    assert!(!Polyeq::new().alpha_equiv(true).eq(&list_int, &list_bool));
+}
+
+#[test]
+fn test_polyeq_choice() {
+    let mut pool = PrimitivePool::new();
+    let definitions = "(declare-fun p (Int) Bool) (declare-fun y () Int)";
+    let mut alpha_equiv = |a, b| {
+        let [a, b] = parse_terms(&mut pool, definitions, [a, b]);
+        Polyeq::new().alpha_equiv(true).eq(&a, &b)
+    };
+
+    // Choice terms are equivalent modulo renaming of their bound variable
+    assert!(alpha_equiv(
+        "(choice ((x Int)) (p x))",
+        "(choice ((z Int)) (p z))"
+    ));
+    assert!(alpha_equiv(
+        "(choice ((x Int)) (exists ((z Int)) (= x z)))",
+        "(choice ((z Int)) (exists ((x Int)) (= z x)))",
+    ));
+
+    // But the renaming must not capture free variables, and the binder and sort must match
+    assert!(!alpha_equiv(
+        "(choice ((x Int)) (= x y))",
+        "(choice ((y Int)) (= y y))"
+    ));
+    assert!(!alpha_equiv(
+        "(choice ((x Int)) (p x))",
+        "(exists ((x Int)) (p x))"
+    ));
+    assert!(!alpha_equiv(
+        "(choice ((x Int)) (= x x))",
+        "(choice ((x Real)) (= x x))"
+    ));
+}
+
 #[test]
 fn test_node() {
     use crate::parser::tests::*;
@@ -162,3 +273,395 @@ fn test_node() {
     let got = node.into_commands();
     assert_eq!(expected.commands, got);
 }
+
+#[test]
+fn test_dependency_graph() {
+    use crate::{ast::build_dependency_graph, parser::tests::*};
+
+    let proof = "
+        (assume h0 (= 0 0))
+        (assume h1 (= 1 1))
+        (step t2 (cl true) :rule blah :premises (h0 h1))
+        (step t3 (cl true) :rule blah)
+        (anchor :step t4)
+            (assume t4.h1 (= 2 2))
+            (step t4.t2 (cl true) :rule blah :premises (t3 t4.h1))
+            (step t4 (cl true) :rule blah :premises (t4.t2) :discharge (t4.h1))
+        (step t5 (cl) :rule other :premises (t2 t4 h0))
+    ";
+    let mut pool = PrimitivePool::new();
+    let proof = parse_proof(&mut pool, proof);
+    let graph = build_dependency_graph(&proof);
+
+    let nodes: Vec<_> = graph
+        .nodes
+        .iter()
+        .map(|n| (n.id.as_str(), n.rule.as_str(), n.index))
+        .collect();
+    assert_eq!(
+        nodes,
+        [
+            ("h0", "assume", (0, 0)),
+            ("h1", "assume", (0, 1)),
+            ("t2", "blah", (0, 2)),
+            ("t3", "blah", (0, 3)),
+            ("t4.h1", "assume", (1, 0)),
+            ("t4.t2", "blah", (1, 1)),
+            ("t4", "blah", (1, 2)),
+            ("t5", "other", (0, 5)),
+        ]
+    );
+    assert_eq!(graph.edges.len(), 9);
+    assert_eq!(graph.premises_of(5).collect::<Vec<_>>(), [3, 4]);
+    assert_eq!(graph.premises_of(6).collect::<Vec<_>>(), [5, 4]);
+    assert_eq!(graph.premises_of(7).collect::<Vec<_>>(), [2, 6, 0]);
+}
+
+#[test]
+fn test_critical_path() {
+    use crate::{
+        ast::{critical_path, Proof},
+        parser::tests::*,
+    };
+
+    let proof = "
+        (assume h0 (= 0 0))
+        (assume h1 (= 1 1))
+        (step t2 (cl true) :rule blah :premises (h1))
+        (step t3 (cl true) :rule blah :premises (h0))
+        (step t4 (cl true) :rule blah :premises (t2 t3))
+        (anchor :step t5)
+            (step t5.t1 (cl true) :rule blah :premises (t4))
+            (step t5 (cl true) :rule blah :premises (t5.t1))
+        (step t6 (cl true) :rule blah :premises (h0))
+        (step t7 (cl) :rule other :premises (t6 t5))
+    ";
+    let mut pool = PrimitivePool::new();
+    let proof = parse_proof(&mut pool, proof);
+    assert_eq!(
+        critical_path(&proof),
+        ["h1", "t2", "t4", "t5.t1", "t5", "t7"]
+    );
+
+    let proof = parse_proof(&mut pool, "(assume h0 (= 0 0))");
+    assert_eq!(critical_path(&proof), ["h0"]);
+    let empty = Proof {
+        constant_definitions: Vec::new(),
+        commands: Vec::new(),
+        comments: Default::default(),
+    };
+    assert!(critical_path(&empty).is_empty());
+}
+
+#[test]
+fn test_find_redundant_structural_steps() {
+    use crate::{ast::find_redundant_structural_steps, parser};
+
+    let definitions = "(declare-const p Bool) (declare-const q Bool)";
+    let proof = "
+        (assume h1 (or p q))
+        (step t1 (cl p q) :rule or :premises (h1))
+        (step t2 (cl p q) :rule reordering :premises (t1))
+        (step t3 (cl q p) :rule reordering :premises (t2))
+        (step t4 (cl q p) :rule contraction :premises (t3))
+        (step t5 (cl q p p) :rule hole)
+        (step t6 (cl q p) :rule contraction :premises (t5))
+        (anchor :step t7)
+            (step t7.t1 (cl q p) :rule reordering :premises (t4))
+            (step t7 (cl q p) :rule hole :premises (t7.t1))
+    ";
+    let (_, proof, _) = parser::parse_instance(
+        definitions.as_bytes(),
+        proof.as_bytes(),
+        parser::Config::new(),
+    )
+    .unwrap();
+    assert_eq!(
+        find_redundant_structural_steps(&proof),
+        ["t2", "t4", "t7.t1"]
+    );
+}
+
+#[test]
+fn test_terms_as_map_keys() {
+    use crate::ast::{Operator, Rc, Sort, Term};
+    use std::collections::HashMap;
+
+    let mut pool = PrimitivePool::new();
+    let definitions = "(declare-fun f (Int) Int) (declare-fun a () Int)";
+    let [first, second] = parse_terms(&mut pool, definitions, ["(+ (f a) 1)", "(+ (f a) 1)"]);
+
+    // Structurally equal terms added to the same pool are a single key
+    let mut map: HashMap<Rc<Term>, usize> = HashMap::new();
+    *map.entry(first.clone()).or_default() += 1;
+    *map.entry(second.clone()).or_default() += 1;
+    assert_eq!(map.len(), 1);
+    assert_eq!(map[&first], 2);
+
+    // The same holds for the `Term` and `Sort` values themselves, even if they were not added to
+    // the pool
+    let Term::Op(Operator::Add, args) = first.as_ref() else {
+        unreachable!()
+    };
+    let rebuilt = Term::Op(Operator::Add, args.clone());
+    let mut map: HashMap<Term, usize> = HashMap::new();
+    *map.entry(first.as_ref().clone()).or_default() += 1;
+    *map.entry(rebuilt).or_default() += 1;
+    assert_eq!(map.len(), 1);
+
+    let mut map: HashMap<Sort, usize> = HashMap::new();
+    for t in [&first, &second] {
+        *map.entry(pool.sort(t).as_sort().unwrap().clone())
+            .or_default() += 1;
+    }
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn test_duplicate_subterms() {
+    use crate::{ast::duplicate_subterms, parser::tests::*};
+
+    let proof = "
+        (assume h0 (= (* (+ 1 2) (+ 1 2)) (* (+ 1 2) (+ 1 2))))
+        (step t1 (cl (= (+ 1 2) 3)) :rule blah)
+        (anchor :step t2 :args ((:= (x Int) (+ 1 2))))
+            (step t2.t1 (cl (= x 3)) :rule blah)
+            (step t2 (cl (= x 3)) :rule blah)
+    ";
+    let mut pool = PrimitivePool::new();
+    let proof = parse_proof(&mut pool, proof);
+    let result: Vec<_> = duplicate_subterms(&proof, 2)
+        .into_iter()
+        .map(|(term, count)| (term.to_string(), count))
+        .collect();
+    assert_eq!(
+        result,
+        [
+            ("(+ 1 2)".to_owned(), 6),
+            ("(= x 3)".to_owned(), 2),
+            ("(* (+ 1 2) (+ 1 2))".to_owned(), 2),
+        ]
+    );
+
+    // Terms that appear only once are reported if `min_count` is 1, but constants never are
+    let result = duplicate_subterms(&proof, 1);
+    assert_eq!(result.len(), 5);
+    assert!(result.iter().all(|(term, _)| !term.is_const()));
+}
+
+#[test]
+fn test_diff_proofs() {
+    use crate::{
+        ast::{diff_proofs, ProofDiff, ProofDiffKind},
+        parser::{self, parse_instance_multi},
+    };
+
+    let first = "
+        (assume h1 p)
+        (assume h2 (not p))
+        (anchor :step t3)
+            (step t3.t1 (cl (= p p)) :rule refl)
+            (step t3 (cl (= p p)) :rule subproof)
+        (step t4 (cl) :rule resolution :premises (h1 h2))
+    ";
+    let second = "
+        (assume a1 p)
+        (assume a2 (not p))
+        (anchor :step t3)
+            (step t3.t1 (cl (= p p)) :rule refl)
+            (step t3 (cl (= p p)) :rule subproof)
+        (step t4 (cl) :rule th_resolution :premises (a1 a2))
+    ";
+
+    let (_, proofs, _) = parse_instance_multi(
+        "(declare-fun p () Bool)".as_bytes(),
+        format!("{first} unsat {second}").as_bytes(),
+        parser::Config::new(),
+    )
+    .unwrap();
+    let [first, second] = proofs.try_into().unwrap();
+
+    assert_eq!(diff_proofs(&first, &first), []);
+    assert_eq!(
+        diff_proofs(&first, &second),
+        [ProofDiff {
+            path: vec![3],
+            id: "t4".to_owned(),
+            kind: ProofDiffKind::RuleChanged("resolution".to_owned(), "th_resolution".to_owned()),
+        }]
+    );
+}
+
+#[test]
+fn test_proof_builder() {
+    use crate::{
+        ast::{Operator, ProofBuilder, ProofCommand, Sort, Term},
+        checker,
+    };
+
+    let mut pool = PrimitivePool::new();
+    let bool_sort = pool.add(Term::Sort(Sort::Bool));
+    let p = pool.add(Term::new_var("p", bool_sort));
+    let not_p = pool.add(Term::Op(Operator::Not, vec![p.clone()]));
+    let p_eq_p = pool.add(Term::Op(Operator::Equals, vec![p.clone(), p.clone()]));
+
+    let mut builder = ProofBuilder::new();
+    let h1 = builder.assume(p.clone());
+    let h2 = builder.assume(not_p.clone());
+    let t1 = builder.subproof(Vec::new(), |b| {
+        let inner = b.assume(p.clone());
+        let t = b.step("refl", vec![p_eq_p.clone()], &[], Vec::new());
+        assert_eq!(t.index(), (1, 1));
+        b.step_with_discharge(
+            "subproof",
+            vec![not_p.clone(), p_eq_p.clone()],
+            &[],
+            Vec::new(),
+            &[inner],
+        );
+    });
+    builder.step("resolution", Vec::new(), &[h1, h2], Vec::new());
+    let proof = builder.build();
+
+    assert_eq!(t1.index(), (0, 2));
+    let ids: Vec<_> = proof.iter().map(ProofCommand::id).collect();
+    assert_eq!(ids, ["h1", "h2", "t1", "t1.h1", "t1.t1", "t1", "t2"]);
+
+    let mut problem = crate::ast::Problem::new();
+    problem.premises.extend([p, not_p]);
+    let mut checker = checker::ProofChecker::new(&mut pool, checker::Config::new());
+    assert!(checker.check(&problem, &proof).is_ok());
+}
+
+#[test]
+fn test_structural_order() {
+    use crate::ast::{StructuralOrder, TermOrder};
+
+    let definitions = "
+        (declare-fun f (Int) Int)
+        (declare-fun a () Int)
+        (declare-fun b () Int)
+        (declare-fun p () Bool)
+    ";
+    let terms = [
+        "1",
+        "2",
+        "2.0",
+        "\"a\"",
+        "#b01",
+        "#b0001",
+        "a",
+        "b",
+        "p",
+        "(f a)",
+        "(f b)",
+        "(not p)",
+        "(+ a b)",
+        "(+ a b 1)",
+        "(forall ((x Int)) (= x a))",
+        "(let ((x a)) (= x b))",
+    ];
+    let mut pool = PrimitivePool::new();
+    let parsed: Vec<_> = terms
+        .iter()
+        .map(|t| parse_terms(&mut pool, definitions, [*t])[0].clone())
+        .collect();
+
+    // The terms are listed in increasing order
+    for (i, a) in parsed.iter().enumerate() {
+        for (j, b) in parsed.iter().enumerate() {
+            assert_eq!(StructuralOrder.compare(a, b), i.cmp(&j), "{} and {}", a, b);
+        }
+    }
+
+    let mut shuffled: Vec<_> = parsed.iter().rev().cloned().collect();
+    shuffled.swap(0, 7);
+    StructuralOrder.sort(&mut shuffled);
+    assert_eq!(shuffled, parsed);
+}
+
+#[test]
+fn test_pool_clear() {
+    use crate::ast::{Operator, Sort, Term};
+
+    let add_distinct_terms = |pool: &mut PrimitivePool| {
+        let int_sort = pool.add(Term::Sort(Sort::Int));
+        let x = pool.add(Term::new_var("x", int_sort));
+        (0..1000)
+            .map(|i| {
+                let n = pool.add(Term::new_int(i));
+                pool.add(Term::Op(Operator::Add, vec![x.clone(), n]))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut pool = PrimitivePool::new();
+    let first = add_distinct_terms(&mut pool);
+    let (len, capacity) = (pool.len(), pool.capacity());
+    assert!(len > 2000);
+
+    pool.clear();
+    assert!(pool.is_empty());
+    assert_eq!(pool.capacity(), capacity);
+
+    // Terms are hash consed and have their sorts computed as usual after the pool is cleared
+    let second = add_distinct_terms(&mut pool);
+    assert_eq!(pool.len(), len);
+    assert_eq!(pool.capacity(), capacity);
+    assert_eq!(second, add_distinct_terms(&mut pool));
+    assert_eq!(pool.sort(&second[0]).as_sort(), Some(&Sort::Int));
+
+    // Terms taken from the pool before it was cleared are still valid, but no longer shared
+    assert_eq!(first[0].to_string(), second[0].to_string());
+    assert_ne!(first[0], second[0]);
+
+    let pool = PrimitivePool::with_capacity(len);
+    assert!(pool.capacity() >= len);
+}
+
+/// Compares the time to add the terms of many proofs to a new pool for each proof, and to a single
+/// pool that is cleared between proofs. Since this only measures time, it is ignored by default,
+/// and should be run in release mode with:
+///
+/// ```text
+/// cargo test --release -p carcara -- --ignored bench_pool_reuse --nocapture
+/// ```
+#[test]
+#[ignore]
+fn bench_pool_reuse() {
+    use crate::ast::{Operator, Sort, Term};
+    use std::time::{Duration, Instant};
+
+    const NUM_PROOFS: usize = 50;
+    const TERMS_PER_PROOF: i32 = 20_000;
+
+    // Adds many distinct terms to the pool, similar to what happens when parsing a large proof
+    let add_distinct_terms = |pool: &mut PrimitivePool| {
+        let int_sort = pool.add(Term::Sort(Sort::Int));
+        let x = pool.add(Term::new_var("x", int_sort));
+        for i in 0..TERMS_PER_PROOF {
+            let n = pool.add(Term::new_int(i));
+            let sum = pool.add(Term::Op(Operator::Add, vec![x.clone(), n.clone()]));
+            let term = pool.add(Term::Op(Operator::LessThan, vec![sum, n]));
+            pool.sort(&term);
+        }
+        pool.len()
+    };
+
+    let (mut fresh_time, mut reused_time) = (Duration::ZERO, Duration::ZERO);
+    let mut reused_pool = PrimitivePool::new();
+    for _ in 0..NUM_PROOFS {
+        let start = Instant::now();
+        let fresh_len = add_distinct_terms(&mut PrimitivePool::new());
+        fresh_time += start.elapsed();
+
+        let start = Instant::now();
+        reused_pool.clear();
+        let reused_len = add_distinct_terms(&mut reused_pool);
+        reused_time += start.elapsed();
+
+        assert_eq!(fresh_len, reused_len);
+    }
+    println!("new pool for each proof: {:?}", fresh_time);
+    println!("cleared and reused pool: {:?}", reused_time);
+}
@@ -0,0 +1,224 @@
+//! An asynchronous interface to the checker, enabled by the `async` feature. See [`check_async`].
+
+use crate::{check, checker, parser, CarcaraResult};
+use std::{
+    collections::VecDeque,
+    future::Future,
+    io,
+    num::NonZeroUsize,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+/// Checks a proof without blocking the calling task. This takes the same arguments as [`check`],
+/// except for statistics collection, which is not supported.
+///
+/// Since checking is CPU-bound, the proof is parsed and checked on a pool of worker threads, and
+/// the returned future completes when the check finishes. The pool has at most one thread for each
+/// available CPU core, and checks that are started while all of them are busy wait for one of them
+/// to be free. The future doesn't depend on any particular async runtime, so it can be awaited in
+/// `tokio`, `async-std`, or any other executor.
+///
+/// Dropping the future doesn't interrupt the check, which still runs to completion in the
+/// background, with its result discarded. If the check panics, the panic is resumed when the future
+/// is polled.
+pub fn check_async<T>(
+    problem: T,
+    proof: T,
+    parser_config: parser::Config,
+    checker_config: checker::Config,
+) -> CheckFuture
+where
+    T: io::BufRead + Send + 'static,
+{
+    let shared = Arc::new(Mutex::new(SharedState::default()));
+    let worker_state = shared.clone();
+    spawn_job(Box::new(move || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            check(problem, proof, parser_config, checker_config, false)
+        }));
+        let mut state = worker_state.lock().unwrap();
+        state.result = Some(result);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }));
+    CheckFuture { shared }
+}
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// The jobs waiting for a free worker thread, and the number of worker threads that are running.
+struct WorkerPool {
+    queue: VecDeque<Job>,
+    num_workers: usize,
+}
+
+static WORKER_POOL: Mutex<WorkerPool> = Mutex::new(WorkerPool {
+    queue: VecDeque::new(),
+    num_workers: 0,
+});
+
+/// Runs a job on a worker thread, starting a new one if there are fewer workers than available CPU
+/// cores, or queueing the job otherwise. Jobs must not panic.
+fn spawn_job(job: Job) {
+    let max_workers = thread::available_parallelism().map_or(1, NonZeroUsize::get);
+    let mut pool = WORKER_POOL.lock().unwrap();
+    if pool.num_workers < max_workers {
+        pool.num_workers += 1;
+        thread::spawn(move || run_worker(job));
+    } else {
+        pool.queue.push_back(job);
+    }
+}
+
+/// Runs a job, and then the queued jobs, until the queue is empty.
+fn run_worker(mut job: Job) {
+    loop {
+        job();
+        let mut pool = WORKER_POOL.lock().unwrap();
+        match pool.queue.pop_front() {
+            Some(next) => job = next,
+            None => {
+                pool.num_workers -= 1;
+                return;
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct SharedState {
+    result: Option<thread::Result<CarcaraResult<bool>>>,
+    waker: Option<Waker>,
+}
+
+/// The future returned by [`check_async`]. It resolves to the same result that [`check`] would
+/// return.
+pub struct CheckFuture {
+    shared: Arc<Mutex<SharedState>>,
+}
+
+impl Future for CheckFuture {
+    type Output = CarcaraResult<bool>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.lock().unwrap();
+        match state.result.take() {
+            Some(Ok(result)) => Poll::Ready(result),
+            Some(Err(payload)) => {
+                drop(state);
+                panic::resume_unwind(payload)
+            }
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::task::Wake;
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// A minimal executor, so the tests don't depend on an async runtime.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = std::pin::pin!(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_async() {
+        let problem = "
+            (declare-const p Bool)
+            (assert p)
+            (assert (not p))
+        ";
+        let proof = "
+            (assume h1 p)
+            (assume h2 (not p))
+            (step t1 (cl) :rule resolution :premises (h1 h2))
+        ";
+        let future = check_async(
+            problem.as_bytes(),
+            proof.as_bytes(),
+            parser::Config::new(),
+            checker::Config::new(),
+        );
+        assert!(!block_on(future).unwrap());
+
+        let proof = "
+            (assume h1 p)
+            (step t1 (cl) :rule resolution :premises (h1))
+        ";
+        let future = check_async(
+            problem.as_bytes(),
+            proof.as_bytes(),
+            parser::Config::new(),
+            checker::Config::new(),
+        );
+        assert!(matches!(
+            block_on(future),
+            Err(crate::Error::Checker { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_async_many() {
+        // There are more checks than worker threads, so some of them must wait in the queue
+        let num_checks = 4 * thread::available_parallelism().map_or(1, NonZeroUsize::get);
+        let problem = "(declare-const p Bool) (assert p) (assert (not p))";
+        let proof = "
+            (assume h1 p)
+            (assume h2 (not p))
+            (step t1 (cl) :rule resolution :premises (h1 h2))
+        ";
+        let futures: Vec<_> = (0..num_checks)
+            .map(|_| {
+                check_async(
+                    problem.as_bytes(),
+                    proof.as_bytes(),
+                    parser::Config::new(),
+                    checker::Config::new(),
+                )
+            })
+            .collect();
+        for future in futures {
+            assert!(!block_on(future).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_check_async_panic() {
+        // If the check panics, the future must still complete, resuming the panic
+        let config = checker::Config::new().rule_override("refl", |_| panic!("rule panicked"));
+        let future = check_async(
+            "(declare-const a Int)".as_bytes(),
+            "(step t1 (cl (= a a)) :rule refl)".as_bytes(),
+            parser::Config::new(),
+            config,
+        );
+        let result = panic::catch_unwind(AssertUnwindSafe(|| block_on(future)));
+        assert!(result.is_err());
+    }
+}
@@ -426,3 +426,104 @@ impl<K: Clone, T: MetricsUnit> Metrics<K, T> for NullMetrics {
         null_metrics_panic()
     }
 }
+
+/// A histogram of durations, bucketed into logarithmic bins. The first bin holds all durations
+/// under one microsecond, and each following bin holds durations that are up to ten times longer
+/// than the ones in the previous bin, with the last bin holding all durations of one second or
+/// more. This is useful to see the distribution of a set of samples, for example to distinguish a
+/// set of uniformly slow steps from a few catastrophic ones.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DurationHistogram {
+    counts: [usize; DurationHistogram::NUM_BINS],
+}
+
+impl DurationHistogram {
+    /// The number of bins in the histogram.
+    pub const NUM_BINS: usize = 8;
+
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Returns the index of the bin that a duration falls into.
+    pub fn bin_of(value: Duration) -> usize {
+        match value.as_micros() {
+            0 => 0,
+            micros => cmp::min(1 + micros.ilog10() as usize, Self::NUM_BINS - 1),
+        }
+    }
+
+    /// Returns the lower bound of a bin, inclusive.
+    pub fn lower_bound(bin: usize) -> Duration {
+        match bin {
+            0 => Duration::ZERO,
+            _ => Duration::from_micros(10u64.pow(bin as u32 - 1)),
+        }
+    }
+
+    pub fn add_sample(&mut self, value: Duration) {
+        self.counts[Self::bin_of(value)] += 1;
+    }
+
+    pub fn combine(mut self, other: Self) -> Self {
+        for (a, b) in self.counts.iter_mut().zip(other.counts) {
+            *a += b;
+        }
+        self
+    }
+
+    /// Returns the number of samples in each bin.
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    pub fn count(&self) -> usize {
+        self.counts.iter().sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count() == 0
+    }
+}
+
+impl FromIterator<Duration> for DurationHistogram {
+    fn from_iter<I: IntoIterator<Item = Duration>>(iter: I) -> Self {
+        let mut result = Self::new();
+        for value in iter {
+            result.add_sample(value);
+        }
+        result
+    }
+}
+
+impl fmt::Display for DurationHistogram {
+    /// Renders the histogram as one line per bin, each containing the bin range, a bar
+    /// proportional to the number of samples in the bin, and the number of samples itself.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const BAR_WIDTH: usize = 40;
+
+        let max = self.counts.iter().copied().max().unwrap_or(0);
+        for (bin, &count) in self.counts.iter().enumerate() {
+            let range = if bin == Self::NUM_BINS - 1 {
+                format!(">= {:?}", Self::lower_bound(bin))
+            } else {
+                format!("< {:?}", Self::lower_bound(bin + 1))
+            };
+
+            // Any bin with at least one sample gets a bar with at least one character, so that
+            // small but non-empty bins are still visible
+            let bar_len = match count {
+                0 => 0,
+                _ => cmp::max(1, count * BAR_WIDTH / max),
+            };
+            writeln!(
+                f,
+                "{: >10} |{: <BAR_WIDTH$}| {}",
+                range,
+                "#".repeat(bar_len),
+                count
+            )?;
+        }
+        Ok(())
+    }
+}
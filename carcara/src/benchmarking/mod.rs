@@ -68,6 +68,7 @@ pub struct OnlineBenchmarkResults {
     pub step_time: OnlineMetrics<StepId>,
     pub step_time_by_file: IndexMap<String, OnlineMetrics<StepId>>,
     pub step_time_by_rule: IndexMap<String, OnlineMetrics<StepId>>,
+    pub step_time_histogram: DurationHistogram,
 
     pub polyeq_time: OnlineMetrics<RunId>,
     pub polyeq_time_ratio: OnlineMetrics<RunId, f64>,
@@ -138,6 +139,11 @@ impl OnlineBenchmarkResults {
         &self.step_time_by_rule
     }
 
+    /// The time spent checking each step, bucketed into logarithmic bins.
+    pub fn step_time_histogram(&self) -> &DurationHistogram {
+        &self.step_time_histogram
+    }
+
     /// Prints the benchmark results
     pub fn print(&self, sort_by_total: bool) {
         let [parsing, checking, elaborating, scheduling, accounted_for, total, assume_time, assume_core_time, polyeq_time] =
@@ -198,6 +204,13 @@ impl OnlineBenchmarkResults {
             }
         }
 
+        if !self.step_time_histogram().is_empty() {
+            println!("step time histogram:");
+            for line in self.step_time_histogram().to_string().lines() {
+                println!("    {}", line);
+            }
+        }
+
         println!("worst cases:");
         if !self.step_time().is_empty() {
             let worst_step = self.step_time().max();
@@ -410,6 +423,7 @@ impl CollectResults for OnlineBenchmarkResults {
             .entry(rule)
             .or_default()
             .add_sample(&id, time);
+        self.step_time_histogram.add_sample(time);
     }
 
     fn add_assume_measurement(&mut self, file: &str, id: &str, is_easy: bool, time: Duration) {
@@ -464,6 +478,7 @@ impl CollectResults for OnlineBenchmarkResults {
             step_time: a.step_time.combine(b.step_time),
             step_time_by_file: combine_map(a.step_time_by_file, b.step_time_by_file),
             step_time_by_rule: combine_map(a.step_time_by_rule, b.step_time_by_rule),
+            step_time_histogram: a.step_time_histogram.combine(b.step_time_histogram),
 
             polyeq_time: a.polyeq_time.combine(b.polyeq_time),
             polyeq_time_ratio: a.polyeq_time_ratio.combine(b.polyeq_time_ratio),
@@ -1,4 +1,4 @@
-use super::{Duration, Metrics, MetricsUnit, OfflineMetrics, OnlineMetrics};
+use super::{Duration, DurationHistogram, Metrics, MetricsUnit, OfflineMetrics, OnlineMetrics};
 use rand::{prelude::ThreadRng, Rng};
 use std::fmt;
 
@@ -135,3 +135,33 @@ fn test_metrics_combine() {
     // `Metrics::add` with that entry, which makes the numerical error small again
     run_tests(10_000, 1, 1.0e-6);
 }
+
+#[test]
+fn test_duration_histogram() {
+    let samples = [
+        Duration::from_nanos(0),
+        Duration::from_nanos(999),
+        Duration::from_micros(1),
+        Duration::from_micros(9),
+        Duration::from_micros(50),
+        Duration::from_micros(999),
+        Duration::from_millis(1),
+        Duration::from_millis(150),
+        Duration::from_secs(1),
+        Duration::from_secs(100),
+    ];
+    let histogram: DurationHistogram = samples.into_iter().collect();
+    assert_eq!(histogram.counts(), [2, 2, 1, 1, 1, 0, 1, 2]);
+    assert_eq!(histogram.count(), samples.len());
+
+    let combined = histogram.clone().combine(histogram);
+    assert_eq!(combined.counts(), [4, 4, 2, 2, 2, 0, 2, 4]);
+
+    let rendered = combined.to_string();
+    let lines: Vec<_> = rendered.lines().collect();
+    assert_eq!(lines.len(), DurationHistogram::NUM_BINS);
+    assert!(lines[0].trim_start().starts_with("< 1µs |"));
+    assert!(lines[0].ends_with(&format!("|{}| 4", "#".repeat(40))));
+    assert!(lines[5].ends_with(&format!("|{}| 0", " ".repeat(40))));
+    assert!(lines[7].trim_start().starts_with(">= 1s |"));
+}
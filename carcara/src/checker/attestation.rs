@@ -0,0 +1,146 @@
+//! Attestations for successfully checked proofs. See [`Attestation`].
+
+use super::{Config, ProofChecker};
+use crate::ast::{Rc, Term};
+use indexmap::IndexSet;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// A record that a proof was successfully checked, which can be stored and later used to trust the
+/// proof without checking it again.
+///
+/// The proof is identified by a SHA-256 hash of its canonical printed form, that is, the proof as
+/// printed by Carcara, without term sharing. This means that two proofs that are structurally
+/// equal have the same hash, regardless of how they were originally written. Similarly, the
+/// problem is identified by a hash of its premises, printed in the order they were asserted, so
+/// that the attestation is bound to the problem the proof was checked against. Note that the
+/// attestation is not signed, so it only guarantees the integrity of the proof, not the
+/// authenticity of the attestation itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attestation {
+    /// The SHA-256 hash of the proof's canonical printed form.
+    pub proof_hash: [u8; 32],
+
+    /// The SHA-256 hash of the problem's premises, each printed as an `assert` command.
+    pub problem_hash: [u8; 32],
+
+    /// The version of Carcara that checked the proof.
+    pub checker_version: &'static str,
+
+    /// A description of the checker options that affect which proofs are accepted, such as the
+    /// strictness options and the rules that were allowed as holes.
+    pub rule_set: String,
+
+    /// Whether the proof contains holes, that is, steps that were not actually checked.
+    pub is_holey: bool,
+}
+
+impl Attestation {
+    pub(super) fn new(
+        premises: &IndexSet<Rc<Term>>,
+        printed_proof: &str,
+        config: &Config,
+        is_holey: bool,
+    ) -> Self {
+        let printed_premises: String = premises
+            .iter()
+            .map(|p| format!("(assert {:#})\n", p))
+            .collect();
+        Self {
+            proof_hash: sha256(printed_proof.as_bytes()),
+            problem_hash: sha256(printed_premises.as_bytes()),
+            checker_version: ProofChecker::version(),
+            rule_set: describe_rule_set(config),
+            is_holey,
+        }
+    }
+
+    /// Returns a SHA-256 hash of the whole attestation, including the problem hash, the checker
+    /// version and the rule set. This can be used as a compact identifier of the attestation.
+    pub fn digest(&self) -> [u8; 32] {
+        sha256(self.to_string().as_bytes())
+    }
+}
+
+impl fmt::Display for Attestation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "sha256:")?;
+        for byte in self.proof_hash {
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(f, " problem-sha256:")?;
+        for byte in self.problem_hash {
+            write!(f, "{:02x}", byte)?;
+        }
+        write!(
+            f,
+            " carcara-{} [{}]{}",
+            self.checker_version,
+            self.rule_set,
+            if self.is_holey { " holey" } else { "" }
+        )
+    }
+}
+
+fn describe_rule_set(config: &Config) -> String {
+    let mut options = Vec::new();
+    for (name, enabled) in [
+        ("elaborated", config.elaborated),
+        ("strict", config.strict),
+        ("check-sorts", config.check_sorts),
+        ("normalize-ite", config.normalize_ite),
+        ("expand-distinct", config.expand_distinct),
+        ("ignore-unknown-rules", config.ignore_unknown_rules),
+        ("record-skipped", config.record_skipped),
+        ("require-empty-clause", config.require_empty_clause),
+        ("forbid-trust", config.forbid_trust),
+    ] {
+        if enabled {
+            options.push(name.to_owned());
+        }
+    }
+
+    // The allowed and overridden rules are stored in hash-based collections, so we need to sort
+    // them to make the description deterministic
+    let mut allowed: Vec<_> = config.allowed_rules.iter().map(String::as_str).collect();
+    if !allowed.is_empty() {
+        allowed.sort_unstable();
+        options.push(format!("allowed-rules={}", allowed.join(",")));
+    }
+    let mut overridden: Vec<_> = config.rule_overrides.keys().map(String::as_str).collect();
+    if !overridden.is_empty() {
+        overridden.sort_unstable();
+        options.push(format!("overridden-rules={}", overridden.join(",")));
+    }
+    options.join(" ")
+}
+
+/// Computes the SHA-256 hash of `data`.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256() {
+        fn hex(data: &[u8]) -> String {
+            sha256(data).iter().map(|b| format!("{:02x}", b)).collect()
+        }
+
+        assert_eq!(
+            hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+}
@@ -0,0 +1,201 @@
+//! Checking a proof in several sessions, saving the progress in between. See [`CheckpointState`].
+
+use super::{normalize, ContextStack, ProofChecker};
+use crate::{ast::*, benchmarking::OnlineBenchmarkResults, CarcaraResult};
+use indexmap::IndexSet;
+use std::borrow::Cow;
+
+/// The progress of a [`ProofChecker`] that checked part of a proof, as returned by
+/// [`ProofChecker::checkpoint`]. This can be passed to [`ProofChecker::resume`] to continue the
+/// check from where it stopped, possibly in a different process.
+///
+/// Checkpoints are only taken between root-level commands, where the context stack is always
+/// empty, so the state doesn't contain any terms. This means the checkpoint doesn't depend on the
+/// term pool, and a new pool can be used when resuming, as long as the same proof is parsed into it
+/// again. If the `serde` feature is enabled, the state can be serialized and saved to disk.
+///
+/// Warnings collected before the checkpoint are not included in the state.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use carcara::{checker::*, parser};
+/// # let (problem_text, proof_text) = ("", "");
+/// let (problem, proof, mut pool) = parser::parse_instance(
+///     problem_text.as_bytes(),
+///     proof_text.as_bytes(),
+///     parser::Config::new(),
+/// )?;
+/// let mut checker = ProofChecker::new(&mut pool, Config::new());
+/// checker.check_partial(&problem, &proof, 1000)?;
+/// let state = checker.checkpoint();
+///
+/// // Later, possibly in a different process
+/// let (problem, proof, mut pool) = parser::parse_instance(
+///     problem_text.as_bytes(),
+///     proof_text.as_bytes(),
+///     parser::Config::new(),
+/// )?;
+/// let mut checker = ProofChecker::new(&mut pool, Config::new());
+/// let is_holey = checker.resume(state, &problem, &proof)?;
+/// # Ok::<(), carcara::Error>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CheckpointState {
+    /// The index of the next root-level command to be checked.
+    pub next_command: usize,
+
+    /// Whether any of the checked steps concluded the empty clause.
+    pub reached_empty_clause: bool,
+
+    /// Whether any of the checked steps was a hole.
+    pub is_holey: bool,
+
+    /// The id and rule of each step that was skipped so far. See
+    /// [`ProofChecker::skipped_steps`].
+    pub skipped_steps: Vec<(String, String)>,
+}
+
+impl ProofChecker<'_> {
+    /// Checks at most `num_commands` root-level commands of the proof, along with their subproofs,
+    /// starting from the first command that wasn't checked yet. After this, [`checkpoint`] can be
+    /// used to save the progress of the check. Returns `true` if the whole proof has been checked.
+    ///
+    /// Note that this doesn't check whether the proof reaches the empty clause, since that is only
+    /// known after the last command is checked. For that, the check must be finished with
+    /// [`resume`].
+    ///
+    /// [`checkpoint`]: ProofChecker::checkpoint
+    /// [`resume`]: ProofChecker::resume
+    pub fn check_partial(
+        &mut self,
+        problem: &Problem,
+        proof: &Proof,
+        num_commands: usize,
+    ) -> CarcaraResult<bool> {
+        let (premises, proof) = self.prepare_for_partial_check(problem, proof);
+        let start = self.next_command.min(proof.commands.len());
+        let end = start.saturating_add(num_commands).min(proof.commands.len());
+
+        // Since premises can only refer to previous commands, we can check a prefix of the proof
+        // without checking the commands after it
+        let mut iter = ProofIter::starting_at(&proof.commands[..end], start);
+        self.check_commands::<OnlineBenchmarkResults>(&premises, &mut iter, &mut None)?;
+        self.next_command = end;
+        Ok(end == proof.commands.len())
+    }
+
+    /// Returns the progress of the check so far. See [`CheckpointState`].
+    pub fn checkpoint(&self) -> CheckpointState {
+        CheckpointState {
+            next_command: self.next_command,
+            reached_empty_clause: self.reached_empty_clause,
+            is_holey: self.is_holey,
+            skipped_steps: self.skipped_steps.clone(),
+        }
+    }
+
+    /// Restores the progress saved in a checkpoint, and checks the rest of the proof. The result is
+    /// the same that [`ProofChecker::check`] would return if the whole proof was checked at once.
+    pub fn resume(
+        &mut self,
+        state: CheckpointState,
+        problem: &Problem,
+        proof: &Proof,
+    ) -> CarcaraResult<bool> {
+        self.context = ContextStack::new();
+        self.next_command = state.next_command;
+        self.reached_empty_clause = state.reached_empty_clause;
+        self.is_holey = state.is_holey;
+        self.skipped_steps = state.skipped_steps;
+
+        self.check_partial(problem, proof, usize::MAX)?;
+        self.finish_check(proof)
+    }
+
+    fn prepare_for_partial_check<'a>(
+        &mut self,
+        problem: &'a Problem,
+        proof: &'a Proof,
+    ) -> (Cow<'a, IndexSet<Rc<Term>>>, Cow<'a, Proof>) {
+        if self.config.expand_distinct {
+            let (premises, proof) =
+                normalize::expand_distinct_in_proof(self.pool, &problem.premises, proof);
+            (Cow::Owned(premises), Cow::Owned(proof))
+        } else {
+            (Cow::Borrowed(&problem.premises), Cow::Borrowed(proof))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{checker::Config, parser};
+
+    const PROBLEM: &str = "
+        (declare-const p Bool)
+        (declare-const q Bool)
+        (assert (or p q))
+        (assert (not p))
+        (assert (not q))
+    ";
+    const PROOF: &str = "
+        (assume h1 (or p q))
+        (assume h2 (not p))
+        (assume h3 (not q))
+        (step t1 (cl p q) :rule or :premises (h1))
+        (anchor :step t2)
+        (assume t2.a0 p)
+        (step t2.t1 (cl p) :rule hole)
+        (step t2 (cl (not p) p) :rule subproof :discharge (t2.a0))
+        (step t3 (cl q) :rule resolution :premises (t1 h2))
+        (step t4 (cl) :rule resolution :premises (t3 h3))
+    ";
+
+    fn parse(proof: &str) -> (Problem, Proof, PrimitivePool) {
+        parser::parse_instance(PROBLEM.as_bytes(), proof.as_bytes(), parser::Config::new()).unwrap()
+    }
+
+    #[test]
+    fn test_checkpoint_and_resume() {
+        let (problem, proof, mut pool) = parse(PROOF);
+        let expected = ProofChecker::new(&mut pool, Config::new())
+            .check(&problem, &proof)
+            .unwrap();
+
+        // We stop right after the subproof, and resume with a new pool and checker
+        let mut checker = ProofChecker::new(&mut pool, Config::new());
+        assert!(!checker.check_partial(&problem, &proof, 3).unwrap());
+        assert!(!checker.check_partial(&problem, &proof, 2).unwrap());
+        let state = checker.checkpoint();
+        assert_eq!(state.next_command, 5);
+        assert!(state.is_holey);
+        assert!(!state.reached_empty_clause);
+
+        let (problem, proof, mut pool) = parse(PROOF);
+        let mut checker = ProofChecker::new(&mut pool, Config::new());
+        assert_eq!(expected, checker.resume(state, &problem, &proof).unwrap());
+        assert_eq!(checker.checkpoint().next_command, proof.commands.len());
+
+        // Errors after the checkpoint are still found when resuming
+        let invalid = PROOF.replace("(t3 h3)", "(t3 h2)");
+        let (problem, proof, mut pool) = parse(&invalid);
+        let mut checker = ProofChecker::new(&mut pool, Config::new());
+        checker.check_partial(&problem, &proof, 4).unwrap();
+        let state = checker.checkpoint();
+        assert!(checker.resume(state, &problem, &proof).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_checkpoint_serialize() {
+        let (problem, proof, mut pool) = parse(PROOF);
+        let mut checker = ProofChecker::new(&mut pool, Config::new());
+        checker.check_partial(&problem, &proof, 4).unwrap();
+        let state = checker.checkpoint();
+        let bytes = bincode::serialize(&state).unwrap();
+        assert_eq!(state, bincode::deserialize(&bytes).unwrap());
+    }
+}
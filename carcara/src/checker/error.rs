@@ -1,12 +1,43 @@
 use crate::{
     ast::*,
     checker::rules::linear_arithmetic::LinearComb,
+    parser::SortError,
     utils::{Range, TypeName},
 };
 use rug::{Integer, Rational};
 use std::fmt;
 use thiserror::Error;
 
+/// Returns the plural form of `noun`, unless `range` is exactly one.
+fn plural(range: &Range, noun: &str) -> String {
+    if range.is_exactly(1) {
+        noun.to_owned()
+    } else {
+        format!("{}s", noun)
+    }
+}
+
+/// A problem in a proof that is not severe enough to make it invalid, but that may indicate
+/// redundancy or a bug in the solver that produced it. Warnings are only collected if
+/// `Config::collect_warnings` is enabled.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum Warning {
+    #[error("the conclusion of step '{step}' contains the literal 'true'")]
+    ClauseContainsTrue { step: String },
+
+    #[error("the conclusion of step '{step}' contains both '{literal}' and its negation")]
+    ComplementaryLiterals { step: String, literal: Rc<Term> },
+
+    #[error(
+        "step '{step}' uses the rule '{rule}', which is an alias; consider using '{canonical}'"
+    )]
+    RuleAlias {
+        step: String,
+        rule: String,
+        canonical: &'static str,
+    },
+}
+
 #[derive(Debug, Error)]
 pub enum CheckerError {
     #[error("unspecified error")]
@@ -18,6 +49,9 @@ pub enum CheckerError {
     #[error(transparent)]
     Substitution(#[from] SubstitutionError),
 
+    #[error(transparent)]
+    Sort(#[from] SortError),
+
     #[error("could not match term to any of the original problem premises: {0}")]
     Assume(Rc<Term>),
 
@@ -34,6 +68,9 @@ pub enum CheckerError {
     #[error(transparent)]
     Quant(#[from] QuantifierError),
 
+    #[error(transparent)]
+    BfunElim(#[from] BfunElimError),
+
     #[error(transparent)]
     LinearArithmetic(#[from] LinearArithmeticError),
 
@@ -50,6 +87,28 @@ pub enum CheckerError {
         target: Rc<Term>,
     },
 
+    #[error(
+        "no `{rule}` pattern applies to term with top-level operator '{operator}' (tried: {})",
+        patterns.join(", ")
+    )]
+    NoSimplificationPatternApplies {
+        rule: &'static str,
+        operator: Operator,
+        patterns: &'static [&'static str],
+    },
+
+    #[error(
+        "the `{rule}` case '{case}' applies to '{original}', so expected result to be \
+        '{expected}', got '{got}'"
+    )]
+    SimplificationCaseMismatch {
+        rule: &'static str,
+        case: &'static str,
+        original: Rc<Term>,
+        expected: Rc<Term>,
+        got: Rc<Term>,
+    },
+
     #[error("encountered cycle when simplifying term: '{0}'")]
     CycleInSimplification(Rc<Term>),
 
@@ -59,9 +118,21 @@ pub enum CheckerError {
     #[error("term '{0}' is not a connective")]
     TermIsNotConnective(Rc<Term>),
 
+    #[error("connective '{0}' in term '{1}' has no definition")]
+    NoConnectiveDefinition(String, Rc<Term>),
+
     #[error("term '{0}' does not have the correct form for `ite_intro`")]
     IsNotValidIteIntro(Rc<Term>),
 
+    #[error("'ite' term '{0}' is not a subterm of '{1}'")]
+    IteIntroNotSubterm(Rc<Term>, Rc<Term>),
+
+    #[error("'ite' term '{0}' is introduced more than once")]
+    IteIntroDuplicate(Rc<Term>),
+
+    #[error("'ite' term '{0}' is never introduced")]
+    IteIntroMissing(Rc<Term>),
+
     #[error("broken transitivity chain: can't prove '(= {0} {1})'")]
     BrokenTransitivityChain(Rc<Term>, Rc<Term>),
 
@@ -74,12 +145,51 @@ pub enum CheckerError {
     #[error("term '{0}' is not a valid n-ary operation")]
     NotValidNaryTerm(Rc<Term>),
 
+    #[error("operator '{0}' is not supported by `nary_elim`")]
+    NaryElimUnsupportedOperator(Operator),
+
+    #[error("operator '{0}' is {1}, but it was expanded in the opposite direction")]
+    NaryElimWrongAssociativity(Operator, &'static str),
+
+    #[error("expected identity element of operator '{op}' to be '{expected}', got '{got}'")]
+    NaryElimWrongIdentity {
+        op: Operator,
+        expected: Rc<Term>,
+        got: Rc<Term>,
+    },
+
     #[error("cannot evaluate the fixed length of the term '{0}'")]
     LengthCannotBeEvaluated(Rc<Term>),
 
     #[error("No {0}-th child in term {1}")]
     NoIthChildInTerm(usize, Rc<Term>),
 
+    #[error(
+        "expected literal to be argument {asked} of a term with {available} {}{}",
+        plural(&(*.available).into(), "argument"),
+        match .found {
+            Some(i) => format!(", but it is argument {i}"),
+            None => String::new(),
+        }
+    )]
+    WrongLiteralIndex {
+        asked: usize,
+        available: usize,
+        found: Option<usize>,
+    },
+
+    #[error(
+        "expected '{}' and '{}' to be the sides of '{premise}'{}",
+        .got.0,
+        .got.1,
+        if *.allow_symmetry { ", in either order" } else { ", in the same order" }
+    )]
+    EquivSidesMismatch {
+        premise: Rc<Term>,
+        got: (Rc<Term>, Rc<Term>),
+        allow_symmetry: bool,
+    },
+
     #[error("cannot apply the re_unfold_pos rule to the regular expression term '{0}'")]
     CannotApplyReUnfoldPos(Rc<Term>),
 
@@ -94,16 +204,16 @@ pub enum CheckerError {
     CannotApplyReUnfoldPosComponentDifferentArgNum(Rc<Term>, Rc<Term>),
 
     // General errors
-    #[error("expected {0} premises, got {1}")]
+    #[error("expected {0} {}, got {1}", plural(.0, "premise"))]
     WrongNumberOfPremises(Range, usize),
 
-    #[error("expected {0} terms in clause, got {1}")]
+    #[error("expected {0} {} in clause, got {1}", plural(.0, "term"))]
     WrongLengthOfClause(Range, usize),
 
-    #[error("expected {0} arguments, got {1}")]
+    #[error("expected {0} {}, got {1}", plural(.0, "argument"))]
     WrongNumberOfArgs(Range, usize),
 
-    #[error("expected {1} terms in '{0}' term, got {2}")]
+    #[error("expected {1} {} in '{0}' term, got {2}", plural(.1, "term"))]
     WrongNumberOfTermsInOp(Operator, Range, usize),
 
     #[error("expected term '{1}' to appear in '{0}' term")]
@@ -184,6 +294,12 @@ pub enum CheckerError {
 
     #[error("unknown rule")]
     UnknownRule,
+
+    #[error("rule '{0}' is forbidden, since it is not checked")]
+    TrustForbidden(String),
+
+    #[error("step '{step}' references premise '{premise}', which is not defined before it")]
+    ForwardReference { step: String, premise: String },
 }
 
 /// Errors in which we expected two things to be equal but they weren't.
@@ -213,14 +329,20 @@ pub enum CongruenceError {
     #[error("too many premises")]
     TooManyPremises,
 
-    #[error("no premise to justify equality of arguments '{0}' and '{1}'")]
-    MissingPremise(Rc<Term>, Rc<Term>),
+    /// No premise justifies the equality of the arguments at the given position (starting from
+    /// zero).
+    #[error("no premise to justify equality of arguments '{1}' and '{2}' (at position {0})")]
+    MissingPremise(usize, Rc<Term>, Rc<Term>),
+
+    #[error("no premise to justify equality of applied functions '{0}' and '{1}'")]
+    FunctionsNotJustified(Rc<Term>, Rc<Term>),
 
     #[error(
-        "premise '(= {} {})' doesn't justify conclusion arguments '{}' and '{}'",
-        .premise.0, .premise.1, .args.0, .args.1
+        "premise '(= {} {})' doesn't justify conclusion arguments '{}' and '{}' (at position {})",
+        .premise.0, .premise.1, .args.0, .args.1, .index
     )]
     PremiseDoesntJustifyArgs {
+        index: usize,
         args: (Rc<Term>, Rc<Term>),
         premise: (Rc<Term>, Rc<Term>),
     },
@@ -263,6 +385,9 @@ pub enum QuantifierError {
         right: BindingList,
     },
 
+    #[error("binding '{0}' occurs in the quantifier body, so the quantifier is not vacuous")]
+    BindingIsUsed(String),
+
     #[error("unknown binding introduced in right-hand side: '{0}'")]
     CnfNewBindingIntroduced(String),
 
@@ -271,6 +396,58 @@ pub enum QuantifierError {
 
     #[error("result clause doesn't appear in CNF of original term: '{0}'")]
     ClauseDoesntAppearInCnf(Rc<Term>),
+
+    /// The result clause doesn't appear in the CNF of the original term, but shares some literals
+    /// with one of its clauses. `closest` is the clause in the CNF that shares the most literals
+    /// with the result clause.
+    #[error("result clause '{clause}' doesn't match the closest clause in CNF of original term: '{closest}'")]
+    CnfClauseMismatch { clause: Rc<Term>, closest: Rc<Term> },
+}
+
+/// Errors relevant to the `bfun_elim` rule.
+#[derive(Debug, Error)]
+pub enum BfunElimError {
+    #[error("expected expansion of '{0}' to have {1} cases, got {2}")]
+    WrongNumberOfCases(Rc<Term>, usize, usize),
+
+    #[error(
+        "case {index} of the expansion (where {}) should be '{expected}', got '{got}'",
+        DisplayAssignment(.assignment)
+    )]
+    WrongCase {
+        index: usize,
+        assignment: Vec<(String, bool)>,
+        expected: Rc<Term>,
+        got: Rc<Term>,
+    },
+
+    #[error("case {0} of the expansion is out of order, it should be case {1}")]
+    CaseOutOfOrder(usize, usize),
+
+    #[error("expected expansion over boolean argument '{arg}' to be '{expected}', got '{got}'")]
+    WrongArgumentExpansion {
+        arg: Rc<Term>,
+        expected: Rc<Term>,
+        got: Rc<Term>,
+    },
+}
+
+/// A wrapper struct that implements `fmt::Display` for assignments of values to variables.
+struct DisplayAssignment<'a, V, T>(&'a [(V, T)]);
+
+impl<V: fmt::Display, T: fmt::Display> fmt::Display for DisplayAssignment<'_, V, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "any assignment");
+        }
+        for (i, (var, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{} = {}", var, value)?;
+        }
+        Ok(())
+    }
 }
 
 /// Errors relevant to the linear arithmetic rules.
@@ -291,11 +468,21 @@ pub enum LinearArithmeticError {
     #[error("final disequality is not tautological: '{}'", DisplayLinearComb(.0, .1))]
     DisequalityIsNotTautology(Operator, LinearComb),
 
-    #[error("expected term '{0}' to be less than term '{1}'")]
-    ExpectedLessThan(Rc<Term>, Rc<Term>),
+    #[error("'{clause}' is not a tautology: it is falsified by {}", DisplayAssignment(.assignment))]
+    TautologyFalsified {
+        clause: Rc<Term>,
+        assignment: Vec<(Rc<Term>, Rational)>,
+    },
 
-    #[error("expected term '{0}' to be less than or equal to term '{1}'")]
-    ExpectedLessEq(Rc<Term>, Rc<Term>),
+    #[error(
+        "expected {} inequality to be '(<= {} {})', got '(<= {} {})'",
+        .branch, .expected.0, .expected.1, .got.0, .got.1
+    )]
+    DisequalityWrongInequality {
+        branch: &'static str,
+        expected: (Rc<Term>, Rc<Term>),
+        got: (Rc<Term>, Rc<Term>),
+    },
 }
 
 /// Errors relevant to all rules that end subproofs (not just the `subproof` rule).
@@ -322,14 +509,21 @@ pub enum SubproofError {
     #[error("binding '{0}' was not introduced in context")]
     BindingIsNotInContext(String),
 
+    #[error(
+        "the anchor renames '{var}' to '{value}', but '{value}' is not bound on the right side of \
+        the conclusion"
+    )]
+    BindRenamedVarNotBound { var: String, value: Rc<Term> },
+
     #[error("expected {0} bindings in 'let' term, got {1}")]
     WrongNumberOfLetBindings(usize, usize),
 
     #[error(
-        "premise '(= {} {})' doesn't justify substitution of '{}' for '{}'",
-        .premise.0, .premise.1, .substitution.0, .substitution.1
+        "premise '(= {} {})' doesn't justify substitution of '{}' for '{}' in binding '{}'",
+        .premise.0, .premise.1, .substitution.0, .substitution.1, .binding
     )]
     PremiseDoesntJustifyLet {
+        binding: String,
         substitution: (Rc<Term>, Rc<Term>),
         premise: (Rc<Term>, Rc<Term>),
     },
@@ -337,6 +531,12 @@ pub enum SubproofError {
     #[error("substitution '(:= {0} {1})' doesn't appear as a point in phi")]
     NoPointForSubstitution(String, Rc<Term>),
 
+    #[error(
+        "variable '{var}' is one-pointed with witness '{witness}', which doesn't match the \
+        substitution in the context"
+    )]
+    OnepointMismatch { var: String, witness: Rc<Term> },
+
     #[error("expected binding list in left-hand side to be '{0}'")]
     OnepointWrongLeftBindings(BindingList),
 
@@ -1,21 +1,38 @@
+mod attestation;
+mod checkpoint;
 pub mod error;
+mod normalize;
 mod parallel;
 mod rules;
+mod sorts;
+mod stepwise;
+mod streaming;
+#[cfg(test)]
+mod tests;
 
 use crate::{
     ast::*,
     benchmarking::{CollectResults, OnlineBenchmarkResults},
+    parser::SortError,
     CarcaraResult, Error,
 };
-use error::{CheckerError, SubproofError};
+pub use attestation::Attestation;
+pub use checkpoint::CheckpointState;
+use error::{CheckerError, SubproofError, Warning};
 use indexmap::IndexSet;
 pub use parallel::{scheduler::Scheduler, ParallelProofChecker};
-use rules::{Premise, Rule, RuleArgs, RuleResult};
+pub use rules::simplification::simplify;
+pub use rules::{Premise, Rule, RuleArgs, RuleResult};
+pub use sorts::sort_of;
 use std::{
-    collections::HashSet,
-    fmt,
+    collections::{HashMap, HashSet},
+    fmt, io,
+    sync::{Arc, Mutex},
     time::{Duration, Instant},
 };
+pub use stepwise::StepwiseChecker;
+pub(crate) use streaming::StreamingChecker;
+pub use streaming::StreamingSummary;
 
 #[derive(Clone)]
 pub struct CheckerStatistics<'s, CR: CollectResults + Send + Default> {
@@ -42,6 +59,39 @@ impl<CR: CollectResults + Send + Default> fmt::Debug for CheckerStatistics<'_, C
     }
 }
 
+/// A lightweight summary of a checking run, that is always available, even when not collecting
+/// statistics.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CheckingSummary {
+    /// The total time spent checking the proof.
+    pub duration: Duration,
+
+    /// The number of `step` commands in the proof, including the ones inside subproofs.
+    pub num_steps: usize,
+}
+
+/// An estimate of the memory used by a checker, as returned by [`ProofChecker::memory_estimate`].
+/// All sizes are approximate, and are given in bytes.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryReport {
+    /// The estimated size of the term pool, including its caches.
+    pub pool_bytes: usize,
+
+    /// The number of contexts currently in the context stack.
+    pub context_depth: usize,
+
+    /// The estimated size of the context stack, including the cumulative substitutions of all
+    /// contexts.
+    pub context_bytes: usize,
+}
+
+impl MemoryReport {
+    /// Returns the estimated total number of bytes used by the checker.
+    pub fn total(&self) -> usize {
+        self.pool_bytes + self.context_bytes
+    }
+}
+
 #[derive(Debug, Default, Clone)]
 pub struct Config {
     /// If `true`, the checker will assume that the proof is elaborated, and enforce extra
@@ -58,6 +108,124 @@ pub struct Config {
 
     /// A set of rule names that the checker will allow, considering them holes in the proof.
     pub allowed_rules: HashSet<String>,
+
+    /// If `true`, the checker will skip any steps with rules that it does not recognize, like
+    /// `ignore_unknown_rules`, but will also record the id and rule of every skipped step. After
+    /// checking, these can be retrieved with [`ProofChecker::skipped_steps`], so the caller can
+    /// review them and decide whether to trust the proof. This is useful to detect typos in rule
+    /// names, which would otherwise silently be considered holes.
+    ///
+    /// Note that this is currently only supported by the sequential checker.
+    pub record_skipped: bool,
+
+    /// If `true`, the checker will flag every step whose conclusion is trivially true, that is,
+    /// that contains the literal `true` or a pair of complementary literals. These steps are still
+    /// valid, so they don't cause the check to fail, but they usually indicate redundancy in the
+    /// proof. After checking, the warnings can be retrieved with [`ProofChecker::warnings`].
+    ///
+    /// Note that this is currently only supported by the sequential checker.
+    pub collect_warnings: bool,
+
+    /// If `true`, the checker will require that the last command of the proof is a step concluding
+    /// the empty clause. Normally, it is enough for any step in the proof to conclude it.
+    pub require_empty_clause: bool,
+
+    /// If `true`, the checker will reject any step that uses the `trust` or `trust_me` rules. These
+    /// rules are not checked, so a proof that uses them may be unsound even if the check passes.
+    /// This takes precedence over `ignore_unknown_rules`, `allowed_rules` and `rule_overrides`. See
+    /// also [`uses_trust`].
+    pub forbid_trust: bool,
+
+    /// If `true`, the checker will compare terms modulo trivial simplifications of `ite` terms,
+    /// that is, `(ite true a b)`, `(ite false b a)` and `(ite c a a)` are all considered equal to
+    /// `a`.
+    ///
+    /// Currently, if enabled, the following rules are affected: `ite_intro`, `ite1`, `ite2` and
+    /// `ite_simplify`.
+    pub normalize_ite: bool,
+
+    /// If `true`, every `distinct` term in the problem and in the proof is rewritten to its pairwise
+    /// form before checking, that is, `(distinct a b c)` becomes `(and (not (= a b)) (not (= a c))
+    /// (not (= b c)))`, and `(distinct a b)` becomes `(not (= a b))`. This allows rules to match a
+    /// `distinct` term against its expansion. Steps that use the `distinct_elim` rule are also
+    /// rewritten, and are checked against the pairwise form instead.
+    ///
+    /// Note that this is currently only supported by the sequential checker.
+    pub expand_distinct: bool,
+
+    /// If `true`, the checker will disable all lenient conveniences, and require every step to be
+    /// fully explicit. This is useful for reproducible verification, since the result will not
+    /// depend on the heuristics used to fill in the gaps in a proof.
+    ///
+    /// Currently, if enabled, the following behaviours are affected:
+    /// - `assume` and `refl`: implicit reordering of equalities is not allowed, as if `elaborated`
+    ///   was enabled
+    /// - `trans` and `eq_transitive`: premise equalities may not be implicitly flipped to form the
+    ///   transitive chain
+    /// - `cong`, `ho_cong`, `eq_congruent` and `eq_congruent_pred`: each premise equality must be in
+    ///   the same orientation as the arguments it justifies, and the two sides of an equality in the
+    ///   conclusion may not be implicitly flipped
+    /// - `eq_congruent` and `eq_congruent_pred`: there must be an equality for every argument, even
+    ///   if the arguments are syntactically equal
+    /// - `ite_intro`: every `ite` subterm of the root term must be introduced
+    /// - `prod_simplify`: the non-constant factors must be in the same order as in the original
+    ///   product
+    /// - `normalize_ite` is ignored, that is, terms are never compared modulo `ite` simplifications
+    pub strict: bool,
+
+    /// If `true`, the checker will sort check the conclusion of every step before checking its
+    /// rule, verifying that the arguments of operators and functions have the expected sorts, and
+    /// that every literal in the clause is of sort `Bool`. See [`sort_of`] for details. This is
+    /// mostly useful for proofs that are not parsed, since the parser already sort checks terms.
+    pub check_sorts: bool,
+
+    /// A map from rule names to implementations that replace the default ones. When checking a
+    /// step whose rule is in this map, the overriding implementation is used instead of the one
+    /// normally associated with that name. This can also be used to add support for rules that the
+    /// checker doesn't recognize.
+    pub rule_overrides: HashMap<String, Rule>,
+
+    /// A sink to which a trace of the checking is written. For every step whose rule is checked, a
+    /// line is written containing the step id, the rule name, the premises and their clauses, and
+    /// whether the step passed or failed, with the error message in the latter case. Each line is
+    /// an S-expression of the form:
+    ///
+    /// ```text
+    /// (step t2 :rule resolution :premises ((h1 (cl p)) (h2 (cl (not p)))) :result pass)
+    /// ```
+    ///
+    /// where the result is either `pass` or `(fail "<message>")`. Like the `on_step_begin` and
+    /// `on_step_end` callbacks, this only includes steps whose rules are actually checked. Errors
+    /// when writing to the sink are logged and otherwise ignored.
+    pub trace_sink: Option<Hook<Mutex<dyn io::Write + Send>>>,
+
+    /// A callback that is invoked right before the rule of each step is checked.
+    pub on_step_begin: Option<Hook<dyn Fn(&ProofStep) + Send + Sync>>,
+
+    /// A callback that is invoked right after the rule of each step is checked, receiving the
+    /// result of the check.
+    ///
+    /// Note that, like `on_step_begin`, this is only invoked for steps whose rules are actually
+    /// checked. Steps with unknown rules that are considered holes, and steps that fail before their
+    /// rule is applied (for example, because of an invalid premise reference), don't trigger either
+    /// callback.
+    pub on_step_end: Option<Hook<dyn Fn(&ProofStep, &Result<(), CheckerError>) + Send + Sync>>,
+}
+
+/// A callback that can be set in the checker [`Config`]. This is a thin wrapper around an `Arc`,
+/// so that the config can still be cloned and printed.
+pub struct Hook<F: ?Sized>(pub Arc<F>);
+
+impl<F: ?Sized> Clone for Hook<F> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<F: ?Sized> fmt::Debug for Hook<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Hook({:p})", Arc::as_ptr(&self.0) as *const ())
+    }
 }
 
 impl Config {
@@ -74,6 +242,147 @@ impl Config {
         self.ignore_unknown_rules = value;
         self
     }
+
+    pub fn record_skipped(mut self, value: bool) -> Self {
+        self.record_skipped = value;
+        self
+    }
+
+    pub fn collect_warnings(mut self, value: bool) -> Self {
+        self.collect_warnings = value;
+        self
+    }
+
+    pub fn require_empty_clause(mut self, value: bool) -> Self {
+        self.require_empty_clause = value;
+        self
+    }
+
+    pub fn forbid_trust(mut self, value: bool) -> Self {
+        self.forbid_trust = value;
+        self
+    }
+
+    pub fn check_sorts(mut self, value: bool) -> Self {
+        self.check_sorts = value;
+        self
+    }
+
+    pub fn normalize_ite(mut self, value: bool) -> Self {
+        self.normalize_ite = value;
+        self
+    }
+
+    pub fn expand_distinct(mut self, value: bool) -> Self {
+        self.expand_distinct = value;
+        self
+    }
+
+    pub fn strict(mut self, value: bool) -> Self {
+        self.strict = value;
+        self
+    }
+
+    /// Overrides the implementation of the rule with the given name. See
+    /// [`Config::rule_overrides`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use carcara::{ast::*, checker::{error::CheckerError, *}, parser};
+    /// // A `refl` rule that only accepts equalities whose sides are the same term
+    /// fn syntactic_refl(args: RuleArgs) -> RuleResult {
+    ///     match args.conclusion {
+    ///         [t] => match t.as_ref() {
+    ///             Term::Op(Operator::Equals, sides) if sides[0] == sides[1] => Ok(()),
+    ///             _ => Err(CheckerError::Explanation("not a syntactic equality".into())),
+    ///         },
+    ///         _ => Err(CheckerError::Unspecified),
+    ///     }
+    /// }
+    ///
+    /// let (problem, proof, mut pool) = parser::parse_instance(
+    ///     "(declare-const a Int) (declare-const b Int)".as_bytes(),
+    ///     "(step t1 (cl (= a a)) :rule refl) (step t2 (cl (= b a)) :rule refl)".as_bytes(),
+    ///     parser::Config::new(),
+    /// )?;
+    /// let config = Config::new().rule_override("refl", syntactic_refl);
+    /// let result = ProofChecker::new(&mut pool, config).check(&problem, &proof);
+    /// assert!(matches!(result, Err(carcara::Error::Checker { step, .. }) if step == "t2"));
+    /// # Ok::<(), carcara::Error>(())
+    /// ```
+    pub fn rule_override(mut self, rule_name: &str, rule: Rule) -> Self {
+        self.rule_overrides.insert(rule_name.to_owned(), rule);
+        self
+    }
+
+    pub fn on_step_begin(mut self, hook: impl Fn(&ProofStep) + Send + Sync + 'static) -> Self {
+        self.on_step_begin = Some(Hook(Arc::new(hook)));
+        self
+    }
+
+    pub fn on_step_end(
+        mut self,
+        hook: impl Fn(&ProofStep, &Result<(), CheckerError>) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_step_end = Some(Hook(Arc::new(hook)));
+        self
+    }
+
+    /// Sets the sink to which the checking trace is written. See [`Config::trace_sink`].
+    pub fn trace_sink(mut self, sink: impl io::Write + Send + 'static) -> Self {
+        self.trace_sink = Some(Hook(Arc::new(Mutex::new(sink))));
+        self
+    }
+
+    /// Applies a rule to a step, invoking the `on_step_begin` and `on_step_end` callbacks around
+    /// it, and writing it to the trace sink, if they are set.
+    fn run_rule(&self, rule: Rule, step: &ProofStep, rule_args: RuleArgs) -> RuleResult {
+        if let Some(Hook(hook)) = &self.on_step_begin {
+            hook(step);
+        }
+        let premises = self
+            .trace_sink
+            .as_ref()
+            .map(|_| trace_premises(rule_args.premises));
+        let result = rule(rule_args);
+        if let Some(Hook(hook)) = &self.on_step_end {
+            hook(step, &result);
+        }
+        if let (Some(Hook(sink)), Some(premises)) = (&self.trace_sink, premises) {
+            let outcome = match &result {
+                Ok(()) => "pass".to_owned(),
+                Err(e) => format!("(fail \"{}\")", e.to_string().replace('"', "\"\"")),
+            };
+            let mut sink = sink.lock().unwrap();
+            let written = writeln!(
+                sink,
+                "(step {} :rule {} :premises ({}) :result {})",
+                step.id, step.rule, premises, outcome
+            );
+            if let Err(e) = written {
+                log::error!("failed to write checking trace: {}", e);
+            }
+        }
+        result
+    }
+}
+
+/// Formats the premises of a step for the checking trace, as a list of pairs of premise ids and
+/// clauses.
+fn trace_premises(premises: &[Premise]) -> String {
+    let mut result = String::new();
+    for (i, premise) in premises.iter().enumerate() {
+        if i > 0 {
+            result.push(' ');
+        }
+        result.push_str(&format!("({} (cl", premise.id));
+        for term in premise.clause {
+            result.push_str(&format!(" {}", term));
+        }
+        result.push_str("))");
+    }
+    result
 }
 
 pub struct ProofChecker<'c> {
@@ -82,6 +391,9 @@ pub struct ProofChecker<'c> {
     context: ContextStack,
     reached_empty_clause: bool,
     is_holey: bool,
+    skipped_steps: Vec<(String, String)>,
+    warnings: Vec<Warning>,
+    next_command: usize,
 }
 
 impl<'c> ProofChecker<'c> {
@@ -92,12 +404,59 @@ impl<'c> ProofChecker<'c> {
             context: ContextStack::new(),
             reached_empty_clause: false,
             is_holey: false,
+            skipped_steps: Vec::new(),
+            warnings: Vec::new(),
+            next_command: 0,
+        }
+    }
+
+    /// Returns the id and rule of each step that was skipped because its rule is unknown, in the
+    /// order they appear in the proof. This is only populated if `Config::record_skipped` is
+    /// enabled.
+    pub fn skipped_steps(&self) -> &[(String, String)] {
+        &self.skipped_steps
+    }
+
+    /// Returns the warnings found while checking, in the order they appear in the proof. This is
+    /// only populated if `Config::collect_warnings` is enabled.
+    pub fn warnings(&self) -> &[Warning] {
+        &self.warnings
+    }
+
+    /// Returns an estimate of the memory currently used by the checker, that is, by its term pool
+    /// and context stack. This can be used to detect when checking a proof is likely to exhaust
+    /// the available memory.
+    pub fn memory_estimate(&self) -> MemoryReport {
+        MemoryReport {
+            pool_bytes: self.pool.memory_estimate(),
+            context_depth: self.context.len(),
+            context_bytes: self.context.memory_estimate(),
         }
     }
 
     pub fn check(&mut self, problem: &Problem, proof: &Proof) -> CarcaraResult<bool> {
         self.check_impl(
-            problem,
+            &problem.premises,
+            proof,
+            None::<&mut CheckerStatistics<OnlineBenchmarkResults>>,
+        )
+    }
+
+    /// Checks the proof like [`ProofChecker::check`], but validates the top-level `assume`
+    /// commands against the given assertions, instead of the premises of a parsed [`Problem`].
+    /// Like in a regular check, each `assume` must match one of the assertions, modulo the implicit
+    /// reordering of equalities.
+    ///
+    /// This is useful when the assertions are obtained from a separate source, for example, when
+    /// the problem is not parsed by Carcara.
+    pub fn check_with_problem(
+        &mut self,
+        proof: &Proof,
+        problem_assertions: &[Rc<Term>],
+    ) -> CarcaraResult<bool> {
+        let premises = problem_assertions.iter().cloned().collect();
+        self.check_impl(
+            &premises,
             proof,
             None::<&mut CheckerStatistics<OnlineBenchmarkResults>>,
         )
@@ -109,18 +468,215 @@ impl<'c> ProofChecker<'c> {
         proof: &Proof,
         stats: &mut CheckerStatistics<CR>,
     ) -> CarcaraResult<bool> {
-        self.check_impl(problem, proof, Some(stats))
+        self.check_impl(&problem.premises, proof, Some(stats))
     }
 
-    fn check_impl<CR: CollectResults + Send + Default>(
+    /// Checks the proof like [`ProofChecker::check`], and, if it is valid, returns an
+    /// [`Attestation`] that it was successfully checked. The attestation identifies the proof by a
+    /// hash of its canonical printed form, and the problem by a hash of its premises. It also
+    /// records the checker version and the options that affect which proofs are accepted.
+    pub fn check_and_attest(
         &mut self,
         problem: &Problem,
         proof: &Proof,
+    ) -> CarcaraResult<Attestation> {
+        let is_holey = self.check(problem, proof)?;
+        let printed = print_proof_to_string(self.pool, &problem.prelude, proof, false);
+        Ok(Attestation::new(
+            &problem.premises,
+            &printed,
+            &self.config,
+            is_holey,
+        ))
+    }
+
+    /// Checks the proof like [`ProofChecker::check`], but also returns a [`CheckingSummary`] with
+    /// the total checking time and the number of steps checked.
+    pub fn check_with_summary(
+        &mut self,
+        problem: &Problem,
+        proof: &Proof,
+    ) -> CarcaraResult<(bool, CheckingSummary)> {
+        let time = Instant::now();
+        let result = self.check(problem, proof)?;
+        let summary = CheckingSummary {
+            duration: time.elapsed(),
+            num_steps: proof.iter().filter(|c| c.is_step()).count(),
+        };
+        Ok((result, summary))
+    }
+
+    /// Checks only that every `assume` command in the root proof matches one of the given problem
+    /// premises, without checking any of the steps. Like in a full check, an assumption may match a
+    /// premise modulo reordering of equalities, unless `elaborated` or `strict` is enabled. The
+    /// `assume` commands inside subproofs are local assumptions, so they are not checked.
+    ///
+    /// This is much cheaper than a full check, so it can be used to quickly confirm that a proof
+    /// refers to the right problem.
+    pub fn check_assumptions(
+        &self,
+        proof: &Proof,
+        problem: &[Rc<Term>],
+    ) -> Result<(), CheckerError> {
+        let premises: IndexSet<_> = problem.iter().cloned().collect();
+        let allow_reordering = !self.config.elaborated && !self.config.strict;
+        for command in &proof.commands {
+            let ProofCommand::Assume { term, .. } = command else {
+                continue;
+            };
+            let found = premises.contains(term)
+                || allow_reordering
+                    && premises.iter().any(|p| {
+                        Polyeq::new()
+                            .mod_reordering(true)
+                            .mod_nary(true)
+                            .eq(term, p)
+                    });
+            if !found {
+                return Err(CheckerError::Assume(term.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks only the step with id `target`, along with all the commands it transitively
+    /// depends on. This includes its premises, the assumptions it discharges, and, for steps that
+    /// end a subproof, the previous command in the subproof. All other commands are skipped, and
+    /// only the contexts of the subproofs that contain a checked command are built.
+    ///
+    /// Since this is meant to quickly re-check a single failing step, `assume` commands are taken
+    /// as given, and are not checked against the problem premises. Likewise, the proof is not
+    /// required to reach the empty clause. Returns `true` if any of the checked steps is a hole.
+    pub fn check_only(&mut self, proof: &Proof, target: &str) -> CarcaraResult<bool> {
+        let needed =
+            premise_closure(proof, target).ok_or_else(|| Error::StepNotFound(target.to_owned()))?;
+
+        // For each open subproof, whether its context was pushed. This is needed because the end
+        // step of a subproof may be skipped, so we can't rely on it to pop the context
+        let mut open_contexts: Vec<bool> = Vec::new();
+        let mut iter = proof.iter();
+        let mut position = 0;
+        while let Some(command) = iter.next() {
+            let is_needed = needed[position];
+            position += 1;
+
+            let depth = command_depth(command, &iter);
+            while open_contexts.len() > depth {
+                if open_contexts.pop().unwrap() {
+                    self.context.pop();
+                }
+            }
+            match command {
+                ProofCommand::Step(step) if is_needed => {
+                    let previous_command = if iter.is_end_step() {
+                        // If the subproof has no commands besides this step, there is no previous
+                        // command
+                        let subproof = iter.current_subproof().unwrap();
+                        subproof
+                            .len()
+                            .checked_sub(2)
+                            .map(|index| Premise::new((iter.depth(), index), &subproof[index]))
+                    } else {
+                        None
+                    };
+                    self.check_step::<OnlineBenchmarkResults>(
+                        step,
+                        previous_command,
+                        &iter,
+                        &mut None,
+                    )
+                    .map_err(|e| Error::Checker {
+                        inner: e,
+                        rule: step.rule.clone(),
+                        step: step.id.clone(),
+                    })?;
+                }
+                ProofCommand::Subproof(s) => {
+                    if is_needed {
+                        self.context.push(&s.args);
+                    }
+                    open_contexts.push(is_needed);
+                }
+                _ => (),
+            }
+        }
+        for pushed in open_contexts {
+            if pushed {
+                self.context.pop();
+            }
+        }
+        Ok(self.is_holey)
+    }
+
+    /// Checks several proofs of the same problem, such as the ones parsed by
+    /// [`parser::parse_instance_multi`](crate::parser::parse_instance_multi), and returns the result
+    /// of each one. Each proof is checked independently, so an error in one of them does not
+    /// prevent the others from being checked. Skipped steps and warnings are accumulated across all
+    /// proofs.
+    pub fn check_many(&mut self, problem: &Problem, proofs: &[Proof]) -> Vec<CarcaraResult<bool>> {
+        proofs
+            .iter()
+            .map(|proof| {
+                self.context = ContextStack::new();
+                self.reached_empty_clause = false;
+                self.is_holey = false;
+                self.next_command = 0;
+                self.check(problem, proof)
+            })
+            .collect()
+    }
+
+    fn check_impl<CR: CollectResults + Send + Default>(
+        &mut self,
+        premises: &IndexSet<Rc<Term>>,
+        proof: &Proof,
         mut stats: Option<&mut CheckerStatistics<CR>>,
     ) -> CarcaraResult<bool> {
+        let expanded;
+        let (premises, proof) = if self.config.expand_distinct {
+            expanded = normalize::expand_distinct_in_proof(self.pool, premises, proof);
+            (&expanded.0, &expanded.1)
+        } else {
+            (premises, proof)
+        };
+
+        self.check_commands(premises, &mut proof.iter(), &mut stats)?;
+        self.finish_check(proof)
+    }
+
+    /// Returns the final result of the check, after all commands of the proof were checked.
+    fn finish_check(&self, proof: &Proof) -> CarcaraResult<bool> {
+        if self.config.require_empty_clause && !proof.concludes_empty_clause() {
+            return Err(Error::DoesNotReachEmptyClause);
+        }
+        if self.reached_empty_clause {
+            Ok(self.is_holey)
+        } else {
+            Err(Error::DoesNotReachEmptyClause)
+        }
+    }
+
+    /// Checks the last command in a slice of root-level commands, along with all of its subproof
+    /// commands, if it is a subproof. The previous commands are not checked, but may be referenced
+    /// as premises.
+    fn check_last_command(
+        &mut self,
+        premises: &IndexSet<Rc<Term>>,
+        commands: &[ProofCommand],
+    ) -> CarcaraResult<()> {
+        let mut iter = ProofIter::starting_at(commands, commands.len() - 1);
+        self.check_commands::<OnlineBenchmarkResults>(premises, &mut iter, &mut None)
+    }
+
+    /// Checks all the commands yielded by `iter`.
+    fn check_commands<CR: CollectResults + Send + Default>(
+        &mut self,
+        premises: &IndexSet<Rc<Term>>,
+        iter: &mut ProofIter,
+        stats: &mut Option<&mut CheckerStatistics<CR>>,
+    ) -> CarcaraResult<()> {
         // Similarly to the parser, to avoid stack overflows in proofs with many nested subproofs,
         // we check the subproofs iteratively, instead of recursively
-        let mut iter = proof.iter();
         while let Some(command) = iter.next() {
             match command {
                 ProofCommand::Step(step) => {
@@ -130,14 +686,14 @@ impl<'c> ProofChecker<'c> {
                     // previous command in the subproof
                     let previous_command = if is_end_of_subproof {
                         let subproof = iter.current_subproof().unwrap();
-                        let index = subproof.len() - 2;
                         subproof
-                            .get(index)
-                            .map(|command| Premise::new((iter.depth(), index), command))
+                            .len()
+                            .checked_sub(2)
+                            .map(|index| Premise::new((iter.depth(), index), &subproof[index]))
                     } else {
                         None
                     };
-                    self.check_step(step, previous_command, &iter, &mut stats)
+                    self.check_step(step, previous_command, iter, stats)
                         .map_err(|e| Error::Checker {
                             inner: e,
                             rule: step.rule.clone(),
@@ -154,6 +710,11 @@ impl<'c> ProofChecker<'c> {
                     if step.clause.is_empty() {
                         self.reached_empty_clause = true;
                     }
+
+                    if self.config.collect_warnings {
+                        self.warnings.extend(rule_alias_warning(step));
+                        self.warnings.extend(trivial_clause_warning(step));
+                    }
                 }
                 ProofCommand::Subproof(s) => {
                     let time = Instant::now();
@@ -161,7 +722,7 @@ impl<'c> ProofChecker<'c> {
 
                     self.context.push(&s.args);
 
-                    if let Some(stats) = &mut stats {
+                    if let Some(stats) = stats {
                         let rule_name = match s.commands.last() {
                             Some(ProofCommand::Step(step)) => format!("anchor({})", &step.rule),
                             _ => "anchor".to_owned(),
@@ -175,7 +736,7 @@ impl<'c> ProofChecker<'c> {
                     }
                 }
                 ProofCommand::Assume { id, term } => {
-                    if !self.check_assume(id, term, &problem.premises, &iter, &mut stats) {
+                    if !self.check_assume(id, term, premises, iter, stats) {
                         return Err(Error::Checker {
                             inner: CheckerError::Assume(term.clone()),
                             rule: "assume".into(),
@@ -185,11 +746,7 @@ impl<'c> ProofChecker<'c> {
                 }
             }
         }
-        if self.reached_empty_clause {
-            Ok(self.is_holey)
-        } else {
-            Err(Error::DoesNotReachEmptyClause)
-        }
+        Ok(())
     }
 
     fn check_assume<'i, CR: CollectResults + Send + Default>(
@@ -220,7 +777,7 @@ impl<'c> ProofChecker<'c> {
             return true;
         }
 
-        if self.config.elaborated {
+        if self.config.elaborated || self.config.strict {
             return false;
         }
 
@@ -276,12 +833,40 @@ impl<'c> ProofChecker<'c> {
         if !step.discharge.is_empty() && step.rule != "subproof" {
             return Err(CheckerError::Subproof(SubproofError::DischargeInWrongRule));
         }
+        check_premise_references(
+            step,
+            |p| iter.precedes_current(p),
+            |p| iter.try_get_premise(p),
+        )?;
+
+        // Local assumptions must be discharged by the step that closes their subproof. This is
+        // checked before the rule itself, so that it also applies to steps whose rule is skipped
+        if iter.is_end_step() {
+            let subproof = iter.current_subproof().unwrap();
+            Self::check_discharge(subproof, iter.depth(), &step.discharge)?;
+        }
 
-        let rule = match Self::get_rule(&step.rule, self.config.elaborated) {
+        if self.config.check_sorts {
+            for term in &step.clause {
+                let sort = sort_of(self.pool, term)?;
+                SortError::assert_eq(&Sort::Bool, &sort)?;
+            }
+        }
+
+        if self.config.forbid_trust && is_trust_rule(&step.rule) {
+            return Err(CheckerError::TrustForbidden(step.rule.clone()));
+        }
+
+        let rule = match Self::get_rule(&step.rule, &self.config) {
             Some(r) => r,
             None if self.config.ignore_unknown_rules
+                || self.config.record_skipped
                 || self.config.allowed_rules.contains(&step.rule) =>
             {
+                if self.config.record_skipped {
+                    self.skipped_steps
+                        .push((step.id.clone(), step.rule.clone()));
+                }
                 self.is_holey = true;
                 return Ok(());
             }
@@ -317,12 +902,7 @@ impl<'c> ProofChecker<'c> {
             polyeq_time: &mut polyeq_time,
         };
 
-        rule(rule_args)?;
-
-        if iter.is_end_step() {
-            let subproof = iter.current_subproof().unwrap();
-            Self::check_discharge(subproof, iter.depth(), &step.discharge)?;
-        }
+        self.config.run_rule(rule, step, rule_args)?;
 
         if let Some(s) = stats {
             let time = time.elapsed();
@@ -353,180 +933,403 @@ impl<'c> ProofChecker<'c> {
         }
     }
 
-    pub fn get_rule(rule_name: &str, elaborated: bool) -> Option<Rule> {
-        use rules::*;
-
-        Some(match rule_name {
-            "true" => tautology::r#true,
-            "false" => tautology::r#false,
-            "not_not" => tautology::not_not,
-            "and_pos" => tautology::and_pos,
-            "and_neg" => tautology::and_neg,
-            "or_pos" => tautology::or_pos,
-            "or_neg" => tautology::or_neg,
-            "xor_pos1" => tautology::xor_pos1,
-            "xor_pos2" => tautology::xor_pos2,
-            "xor_neg1" => tautology::xor_neg1,
-            "xor_neg2" => tautology::xor_neg2,
-            "implies_pos" => tautology::implies_pos,
-            "implies_neg1" => tautology::implies_neg1,
-            "implies_neg2" => tautology::implies_neg2,
-            "equiv_pos1" => tautology::equiv_pos1,
-            "equiv_pos2" => tautology::equiv_pos2,
-            "equiv_neg1" => tautology::equiv_neg1,
-            "equiv_neg2" => tautology::equiv_neg2,
-            "ite_pos1" => tautology::ite_pos1,
-            "ite_pos2" => tautology::ite_pos2,
-            "ite_neg1" => tautology::ite_neg1,
-            "ite_neg2" => tautology::ite_neg2,
-            "eq_reflexive" => reflexivity::eq_reflexive,
-            "eq_transitive" => transitivity::eq_transitive,
-            "eq_congruent" => congruence::eq_congruent,
-            "eq_congruent_pred" => congruence::eq_congruent_pred,
-            "distinct_elim" => clausification::distinct_elim,
-            "la_rw_eq" => linear_arithmetic::la_rw_eq,
-            "la_generic" => linear_arithmetic::la_generic,
-            "la_disequality" => linear_arithmetic::la_disequality,
-            "la_totality" => linear_arithmetic::la_totality,
-            "la_tautology" => linear_arithmetic::la_tautology,
-            "forall_inst" => quantifier::forall_inst,
-            "qnt_join" => quantifier::qnt_join,
-            "qnt_rm_unused" => quantifier::qnt_rm_unused,
-            "resolution" | "th_resolution" if elaborated => resolution::resolution_with_args,
-            "resolution" | "th_resolution" => resolution::resolution,
-            "refl" if elaborated => reflexivity::strict_refl,
-            "refl" => reflexivity::refl,
-            "trans" => transitivity::trans,
-            "cong" => congruence::cong,
-            "ho_cong" => congruence::ho_cong,
-            "and" => clausification::and,
-            "tautology" => resolution::tautology,
-            "not_or" => clausification::not_or,
-            "or" => clausification::or,
-            "not_and" => clausification::not_and,
-            "xor1" => clausification::xor1,
-            "xor2" => clausification::xor2,
-            "not_xor1" => clausification::not_xor1,
-            "not_xor2" => clausification::not_xor2,
-            "implies" => clausification::implies,
-            "not_implies1" => clausification::not_implies1,
-            "not_implies2" => clausification::not_implies2,
-            "equiv1" => tautology::equiv1,
-            "equiv2" => tautology::equiv2,
-            "not_equiv1" => tautology::not_equiv1,
-            "not_equiv2" => tautology::not_equiv2,
-            "ite1" => tautology::ite1,
-            "ite2" => tautology::ite2,
-            "not_ite1" => tautology::not_ite1,
-            "not_ite2" => tautology::not_ite2,
-            "ite_intro" => tautology::ite_intro,
-            "contraction" => resolution::contraction,
-            "connective_def" => tautology::connective_def,
-            "ite_simplify" => simplification::ite_simplify,
-            "eq_simplify" => simplification::eq_simplify,
-            "and_simplify" => simplification::and_simplify,
-            "or_simplify" => simplification::or_simplify,
-            "not_simplify" => simplification::not_simplify,
-            "implies_simplify" => simplification::implies_simplify,
-            "equiv_simplify" => simplification::equiv_simplify,
-            "bool_simplify" => simplification::bool_simplify,
-            "qnt_simplify" => simplification::qnt_simplify,
-            "div_simplify" => simplification::div_simplify,
-            "prod_simplify" => simplification::prod_simplify,
-            // Despite being separate rules in the specification, proofs generated by veriT don't
-            // differentiate between `unary_minus_simplify` and `minus_simplify`. To account for
-            // that, `simplification::minus_simplify` implements both rules in the same function.
-            "unary_minus_simplify" | "minus_simplify" => simplification::minus_simplify,
-            "sum_simplify" => simplification::sum_simplify,
-            "comp_simplify" => simplification::comp_simplify,
-            "nary_elim" => clausification::nary_elim,
-            "ac_simp" => simplification::ac_simp,
-            "bfun_elim" => clausification::bfun_elim,
-            "bind" => subproof::bind,
-            "qnt_cnf" => quantifier::qnt_cnf,
-            "subproof" => subproof::subproof,
-            "let" => subproof::r#let,
-            "onepoint" => subproof::onepoint,
-            "sko_ex" => subproof::sko_ex,
-            "sko_forall" => subproof::sko_forall,
-            "reordering" => extras::reordering,
-            "symm" => extras::symm,
-            "not_symm" => extras::not_symm,
-            "eq_symmetric" => extras::eq_symmetric,
-            "weakening" => extras::weakening,
-            "bind_let" => extras::bind_let,
-            "la_mult_pos" => extras::la_mult_pos,
-            "la_mult_neg" => extras::la_mult_neg,
-            "mod_simplify" => extras::mod_simplify,
-            "bitblast_extract" => bitvectors::extract,
-            "bitblast_bvadd" => bitvectors::add,
-            "bitblast_ult" => bitvectors::ult,
-
-            "concat_eq" => strings::concat_eq,
-            "concat_unify" => strings::concat_unify,
-            "concat_conflict" => strings::concat_conflict,
-            "concat_csplit_prefix" => strings::concat_csplit_prefix,
-            "concat_csplit_suffix" => strings::concat_csplit_suffix,
-            "concat_split_prefix" => strings::concat_split_prefix,
-            "concat_split_suffix" => strings::concat_split_suffix,
-            "concat_lprop_prefix" => strings::concat_lprop_prefix,
-            "concat_lprop_suffix" => strings::concat_lprop_suffix,
-            "concat_cprop_prefix" => strings::concat_cprop_prefix,
-            "concat_cprop_suffix" => strings::concat_cprop_suffix,
-
-            // pseudo-boolean bitblasting
-            "pbblast_bveq" => pb_blasting::pbblast_bveq,
-            "pbblast_bvult" => pb_blasting::pbblast_bvult,
-            "pbblast_bvugt" => pb_blasting::pbblast_bvugt,
-            "pbblast_bvuge" => pb_blasting::pbblast_bvuge,
-            "pbblast_bvule" => pb_blasting::pbblast_bvule,
-            "pbblast_bvslt" => pb_blasting::pbblast_bvslt,
-            "pbblast_bvsgt" => pb_blasting::pbblast_bvsgt,
-            "pbblast_bvsge" => pb_blasting::pbblast_bvsge,
-            "pbblast_bvsle" => pb_blasting::pbblast_bvsle,
-            "pbblast_pbbvar" => pb_blasting::pbblast_pbbvar,
-            "pbblast_pbbconst" => pb_blasting::pbblast_pbbconst,
-            "pbblast_bvxor" => pb_blasting::pbblast_bvxor,
-            "pbblast_bvand" => pb_blasting::pbblast_bvand,
-            "pbblast_bvand_ith_bit" => pb_blasting::pbblast_bvand_ith_bit,
-
-            // cutting planes rules
-            "cp_addition" => cutting_planes::cp_addition,
-            "cp_multiplication" => cutting_planes::cp_multiplication,
-            "cp_division" => cutting_planes::cp_division,
-            "cp_saturation" => cutting_planes::cp_saturation,
-            "cp_literal" => cutting_planes::cp_literal,
-            "cp_normalize" => cutting_planes::cp_normalize,
-
-            "string_decompose" => strings::string_decompose,
-            "string_length_pos" => strings::string_length_pos,
-            "string_length_non_empty" => strings::string_length_non_empty,
-
-            "re_inter" => strings::re_inter,
-            "re_kleene_star_unfold_pos" => strings::re_kleene_star_unfold_pos,
-            "re_concat_unfold_pos" => strings::re_concat_unfold_pos,
-            "re_unfold_neg" => strings::re_unfold_neg,
-            "re_unfold_neg_concat_fixed_prefix" => strings::re_unfold_neg_concat_fixed_prefix,
-            "re_unfold_neg_concat_fixed_suffix" => strings::re_unfold_neg_concat_fixed_suffix,
-            // Drup format rules
-            "drup" => |x| drup::drup(false, x),
-            // Drup format rules
-            "drat" => |x| drup::drup(true, x),
-
-            // Special rules that always check as valid, and are used to indicate holes in the
-            // proof.
-            "hole" => |_| Ok(()),
-            "lia_generic" => |_| {
-                log::warn!("encountered \"lia_generic\" rule, ignoring");
-                Ok(())
-            },
-
-            // The Alethe specification does not yet describe how this more strict version of the
-            // resolution rule will be called. Until that is decided and added to the specification,
-            // we define a new specialized rule that calls it
-            "strict_resolution" => resolution::strict_resolution,
-
-            _ => return None,
+    pub fn get_rule(rule_name: &str, config: &Config) -> Option<Rule> {
+        if let Some(rule) = config.rule_overrides.get(rule_name) {
+            return Some(*rule);
+        }
+        let Config { elaborated, strict, .. } = *config;
+        let normalize_ite = config.normalize_ite && !strict;
+        rule_for_name(
+            rule_name,
+            elaborated,
+            strict,
+            normalize_ite,
+            config.expand_distinct,
+        )
+    }
+
+    /// Returns the names of all rules that the checker recognizes. Rules with more than one name,
+    /// such as `resolution` and `th_resolution`, are listed once for each name.
+    pub fn supported_rules() -> Vec<&'static str> {
+        let mut seen = HashSet::new();
+        RULE_NAMES
+            .iter()
+            .copied()
+            .filter(|name| seen.insert(*name))
+            .collect()
+    }
+
+    /// Returns the version of Carcara.
+    pub fn version() -> &'static str {
+        env!("CARGO_PKG_VERSION")
+    }
+}
+
+/// Defines the function that maps each rule name to its implementation, together with a constant
+/// containing all rule names, so that the two are always in sync. Each arm may have a guard, which
+/// can refer to any of the boolean flags that are passed to the function.
+macro_rules! rule_table {
+    (
+        fn $fn_name:ident($name:ident, $($flag:ident),*);
+        const $const_name:ident;
+        $( $($rule:literal)|+ $(if $guard:expr)? => $func:expr, )*
+    ) => {
+        const $const_name: &[&str] = &[$($($rule,)+)*];
+
+        fn $fn_name($name: &str, $($flag: bool),*) -> Option<Rule> {
+            use rules::*;
+
+            Some(match $name {
+                $( $($rule)|+ $(if $guard)? => $func, )*
+                _ => return None,
+            })
+        }
+    };
+}
+
+rule_table! {
+    fn rule_for_name(rule_name, elaborated, strict, normalize_ite, expand_distinct);
+    const RULE_NAMES;
+
+    "true" => tautology::r#true,
+    "false" => tautology::r#false,
+    "not_not" => tautology::not_not,
+    "and_pos" => tautology::and_pos,
+    "and_neg" => tautology::and_neg,
+    "or_pos" => tautology::or_pos,
+    "or_neg" => tautology::or_neg,
+    "xor_pos1" => tautology::xor_pos1,
+    "xor_pos2" => tautology::xor_pos2,
+    "xor_neg1" => tautology::xor_neg1,
+    "xor_neg2" => tautology::xor_neg2,
+    "implies_pos" => tautology::implies_pos,
+    "implies_neg1" => tautology::implies_neg1,
+    "implies_neg2" => tautology::implies_neg2,
+    "equiv_pos1" => tautology::equiv_pos1,
+    "equiv_pos2" => tautology::equiv_pos2,
+    "equiv_neg1" => tautology::equiv_neg1,
+    "equiv_neg2" => tautology::equiv_neg2,
+    "ite_pos1" => tautology::ite_pos1,
+    "ite_pos2" => tautology::ite_pos2,
+    "ite_neg1" => tautology::ite_neg1,
+    "ite_neg2" => tautology::ite_neg2,
+    "eq_reflexive" => reflexivity::eq_reflexive,
+    "eq_transitive" if strict => transitivity::strict_eq_transitive,
+    "eq_transitive" => transitivity::eq_transitive,
+    "eq_congruent" if strict => congruence::strict_eq_congruent,
+    "eq_congruent" => congruence::eq_congruent,
+    "eq_congruent_pred" if strict => congruence::strict_eq_congruent_pred,
+    "eq_congruent_pred" => congruence::eq_congruent_pred,
+    "distinct_elim" if expand_distinct => clausification::expanded_distinct_elim,
+    "distinct_elim" => clausification::distinct_elim,
+    "la_rw_eq" => linear_arithmetic::la_rw_eq,
+    "la_generic" => linear_arithmetic::la_generic,
+    "la_disequality" => linear_arithmetic::la_disequality,
+    "la_totality" => linear_arithmetic::la_totality,
+    "la_tautology" => linear_arithmetic::la_tautology,
+    "forall_inst" => quantifier::forall_inst,
+    "qnt_join" => quantifier::qnt_join,
+    "qnt_rm_unused" => quantifier::qnt_rm_unused,
+    "resolution" | "th_resolution" if elaborated => resolution::resolution_with_args,
+    "resolution" | "th_resolution" => resolution::resolution,
+    "refl" if elaborated || strict => reflexivity::strict_refl,
+    "refl" => reflexivity::refl,
+    "trans" if strict => transitivity::strict_trans,
+    "trans" => transitivity::trans,
+    "cong" if strict => congruence::strict_cong,
+    "cong" => congruence::cong,
+    "ho_cong" if strict => congruence::strict_ho_cong,
+    "ho_cong" => congruence::ho_cong,
+    "and" => clausification::and,
+    "tautology" => resolution::tautology,
+    "not_or" => clausification::not_or,
+    "or" => clausification::or,
+    "not_and" => clausification::not_and,
+    "xor1" => clausification::xor1,
+    "xor2" => clausification::xor2,
+    "not_xor1" => clausification::not_xor1,
+    "not_xor2" => clausification::not_xor2,
+    "implies" => clausification::implies,
+    "not_implies1" => clausification::not_implies1,
+    "not_implies2" => clausification::not_implies2,
+    "equiv1" if strict => tautology::strict_equiv1,
+    "equiv1" => tautology::equiv1,
+    "equiv2" if strict => tautology::strict_equiv2,
+    "equiv2" => tautology::equiv2,
+    "not_equiv1" if strict => tautology::strict_not_equiv1,
+    "not_equiv1" => tautology::not_equiv1,
+    "not_equiv2" if strict => tautology::strict_not_equiv2,
+    "not_equiv2" => tautology::not_equiv2,
+    "ite1" if normalize_ite => tautology::ite1_mod_ite,
+    "ite1" => tautology::ite1,
+    "ite2" if normalize_ite => tautology::ite2_mod_ite,
+    "ite2" => tautology::ite2,
+    "not_ite1" => tautology::not_ite1,
+    "not_ite2" => tautology::not_ite2,
+    "ite_intro" if strict => tautology::strict_ite_intro,
+    "ite_intro" if normalize_ite => tautology::ite_intro_mod_ite,
+    "ite_intro" => tautology::ite_intro,
+    "contraction" => resolution::contraction,
+    "connective_def" => tautology::connective_def,
+    "ite_simplify" if normalize_ite => simplification::ite_simplify_mod_ite,
+    "ite_simplify" => simplification::ite_simplify,
+    "eq_simplify" => simplification::eq_simplify,
+    "and_simplify" => simplification::and_simplify,
+    "or_simplify" => simplification::or_simplify,
+    "not_simplify" => simplification::not_simplify,
+    "implies_simplify" => simplification::implies_simplify,
+    "equiv_simplify" => simplification::equiv_simplify,
+    "bool_simplify" => simplification::bool_simplify,
+    "qnt_simplify" => simplification::qnt_simplify,
+    "div_simplify" => simplification::div_simplify,
+    "prod_simplify" if strict => simplification::strict_prod_simplify,
+    "prod_simplify" => simplification::prod_simplify,
+    // Despite being separate rules in the specification, proofs generated by veriT don't
+    // differentiate between `unary_minus_simplify` and `minus_simplify`. To account for
+    // that, `simplification::minus_simplify` implements both rules in the same function.
+    "unary_minus_simplify" | "minus_simplify" => simplification::minus_simplify,
+    "sum_simplify" => simplification::sum_simplify,
+    "comp_simplify" => simplification::comp_simplify,
+    "nary_elim" => clausification::nary_elim,
+    "ac_simp" => simplification::ac_simp,
+    "bfun_elim" => clausification::bfun_elim,
+    "bind" => subproof::bind,
+    "qnt_cnf" => quantifier::qnt_cnf,
+    "subproof" => subproof::subproof,
+    "let" => subproof::r#let,
+    "onepoint" => subproof::onepoint,
+    "sko_ex" => subproof::sko_ex,
+    "sko_forall" => subproof::sko_forall,
+    "reordering" => extras::reordering,
+    "symm" => extras::symm,
+    "not_symm" => extras::not_symm,
+    "eq_symmetric" => extras::eq_symmetric,
+    "weakening" => extras::weakening,
+    "bind_let" => extras::bind_let,
+    "la_mult_pos" => extras::la_mult_pos,
+    "la_mult_neg" => extras::la_mult_neg,
+    "mod_simplify" => extras::mod_simplify,
+    "bitblast_extract" => bitvectors::extract,
+    "bitblast_bvadd" => bitvectors::add,
+    "bitblast_ult" => bitvectors::ult,
+
+    "concat_eq" => strings::concat_eq,
+    "concat_unify" => strings::concat_unify,
+    "concat_conflict" => strings::concat_conflict,
+    "concat_csplit_prefix" => strings::concat_csplit_prefix,
+    "concat_csplit_suffix" => strings::concat_csplit_suffix,
+    "concat_split_prefix" => strings::concat_split_prefix,
+    "concat_split_suffix" => strings::concat_split_suffix,
+    "concat_lprop_prefix" => strings::concat_lprop_prefix,
+    "concat_lprop_suffix" => strings::concat_lprop_suffix,
+    "concat_cprop_prefix" => strings::concat_cprop_prefix,
+    "concat_cprop_suffix" => strings::concat_cprop_suffix,
+
+    // pseudo-boolean bitblasting
+    "pbblast_bveq" => pb_blasting::pbblast_bveq,
+    "pbblast_bvult" => pb_blasting::pbblast_bvult,
+    "pbblast_bvugt" => pb_blasting::pbblast_bvugt,
+    "pbblast_bvuge" => pb_blasting::pbblast_bvuge,
+    "pbblast_bvule" => pb_blasting::pbblast_bvule,
+    "pbblast_bvslt" => pb_blasting::pbblast_bvslt,
+    "pbblast_bvsgt" => pb_blasting::pbblast_bvsgt,
+    "pbblast_bvsge" => pb_blasting::pbblast_bvsge,
+    "pbblast_bvsle" => pb_blasting::pbblast_bvsle,
+    "pbblast_pbbvar" => pb_blasting::pbblast_pbbvar,
+    "pbblast_pbbconst" => pb_blasting::pbblast_pbbconst,
+    "pbblast_bvxor" => pb_blasting::pbblast_bvxor,
+    "pbblast_bvand" => pb_blasting::pbblast_bvand,
+    "pbblast_bvand_ith_bit" => pb_blasting::pbblast_bvand_ith_bit,
+
+    // cutting planes rules
+    "cp_addition" => cutting_planes::cp_addition,
+    "cp_multiplication" => cutting_planes::cp_multiplication,
+    "cp_division" => cutting_planes::cp_division,
+    "cp_saturation" => cutting_planes::cp_saturation,
+    "cp_weakening" => cutting_planes::cp_weakening,
+    "cp_literal" => cutting_planes::cp_literal,
+    "cp_normalize" => cutting_planes::cp_normalize,
+
+    "string_decompose" => strings::string_decompose,
+    "string_length_pos" => strings::string_length_pos,
+    "string_length_non_empty" => strings::string_length_non_empty,
+
+    "re_inter" => strings::re_inter,
+    "re_kleene_star_unfold_pos" => strings::re_kleene_star_unfold_pos,
+    "re_concat_unfold_pos" => strings::re_concat_unfold_pos,
+    "re_unfold_neg" => strings::re_unfold_neg,
+    "re_unfold_neg_concat_fixed_prefix" => strings::re_unfold_neg_concat_fixed_prefix,
+    "re_unfold_neg_concat_fixed_suffix" => strings::re_unfold_neg_concat_fixed_suffix,
+    // Drup format rules
+    "drup" => |x| drup::drup(false, x),
+    // Drup format rules
+    "drat" => |x| drup::drup(true, x),
+
+    // Special rules that always check as valid, and are used to indicate holes in the
+    // proof.
+    "hole" => |_| Ok(()),
+    "lia_generic" => |_| {
+        log::warn!("encountered \"lia_generic\" rule, ignoring");
+        Ok(())
+    },
+
+    // The Alethe specification does not yet describe how this more strict version of the
+    // resolution rule will be called. Until that is decided and added to the specification,
+    // we define a new specialized rule that calls it
+    "strict_resolution" => resolution::strict_resolution,
+
+}
+
+/// Checks that all premises and discharged assumptions of a step refer to commands that were
+/// defined before it, in the same subproof or in an enclosing one. This prevents a step from
+/// depending on itself, either directly or through a cycle of premises.
+///
+/// The `precedes_current` and `try_get_premise` functions should be the methods of the same name
+/// in the iterator being used to traverse the proof.
+fn check_premise_references<'a>(
+    step: &ProofStep,
+    precedes_current: impl Fn((usize, usize)) -> bool,
+    try_get_premise: impl Fn((usize, usize)) -> Option<&'a ProofCommand>,
+) -> RuleResult {
+    for &p in step.premises.iter().chain(&step.discharge) {
+        if !precedes_current(p) {
+            let premise = match try_get_premise(p) {
+                Some(command) => command.id().to_owned(),
+                None => format!("{:?}", p),
+            };
+            return Err(CheckerError::ForwardReference { step: step.id.clone(), premise });
+        }
+    }
+    Ok(())
+}
+
+/// The rules that are accepted without any checking, and which are rejected if
+/// `Config::forbid_trust` is enabled.
+const TRUST_RULES: &[&str] = &["trust", "trust_me"];
+
+fn is_trust_rule(rule: &str) -> bool {
+    TRUST_RULES.contains(&rule)
+}
+
+/// Returns the ids of all steps in the proof, including those inside subproofs, that use the
+/// `trust` or `trust_me` rules, in the order they appear in the proof. These steps are never
+/// checked, so a proof that contains them may be unsound. See also [`Config::forbid_trust`].
+pub fn uses_trust(proof: &Proof) -> Vec<String> {
+    proof
+        .iter()
+        .filter_map(|command| match command {
+            ProofCommand::Step(s) if is_trust_rule(&s.rule) => Some(s.id.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Rule names that are accepted as aliases of other rules, together with the canonical name of each
+/// rule. Steps that use an alias are checked exactly like steps that use the canonical name.
+const RULE_ALIASES: &[(&str, &str)] = &[("th_resolution", "resolution")];
+
+/// Returns a warning if `step` uses a rule name that is an alias of another rule.
+fn rule_alias_warning(step: &ProofStep) -> Option<Warning> {
+    RULE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == step.rule)
+        .map(|(_, canonical)| Warning::RuleAlias {
+            step: step.id.clone(),
+            rule: step.rule.clone(),
+            canonical,
         })
+}
+
+/// Returns a warning if the conclusion of `step` is trivially true, that is, if it contains the
+/// literal `true` or a pair of complementary literals. Since a clause is a disjunction, any `or`
+/// terms in it are flattened, so a step concluding `(cl (or p (not p)))` is also flagged.
+fn trivial_clause_warning(step: &ProofStep) -> Option<Warning> {
+    let mut literals = IndexSet::new();
+    let mut stack: Vec<&Rc<Term>> = step.clause.iter().rev().collect();
+    while let Some(term) = stack.pop() {
+        if let Some(args) = match_term!((or ...) = term) {
+            stack.extend(args.iter().rev());
+        } else {
+            literals.insert(term);
+        }
+    }
+
+    let is_true =
+        |t: &Rc<Term>| t.is_bool_true() || t.remove_negation().is_some_and(|t| t.is_bool_false());
+    if literals.iter().any(|t| is_true(t)) {
+        return Some(Warning::ClauseContainsTrue { step: step.id.clone() });
+    }
+    literals.iter().find_map(|t| {
+        let atom = t.remove_negation()?;
+        literals
+            .contains(atom)
+            .then(|| Warning::ComplementaryLiterals {
+                step: step.id.clone(),
+                literal: atom.clone(),
+            })
+    })
+}
+
+/// Returns the nesting depth of a command that was just returned by `iter`. For subproof commands,
+/// this is the depth of the subproof command itself, not of the commands inside it.
+fn command_depth(command: &ProofCommand, iter: &ProofIter) -> usize {
+    match command {
+        ProofCommand::Subproof(_) => iter.depth() - 1,
+        _ => iter.depth(),
+    }
+}
+
+/// Computes the set of commands that the step with id `target` transitively depends on, including
+/// the step itself. The result is indexed by the position of each command in the order they are
+/// yielded by `ProofIter`. Returns `None` if no step or assumption has id `target`.
+fn premise_closure(proof: &Proof, target: &str) -> Option<Vec<bool>> {
+    let mut positions: HashMap<*const ProofCommand, usize> = HashMap::new();
+    let mut dependencies: Vec<Vec<usize>> = Vec::new();
+    let mut target_position = None;
+
+    // The positions of the subproof commands that enclose the current command
+    let mut enclosing: Vec<usize> = Vec::new();
+    let mut iter = proof.iter();
+    while let Some(command) = iter.next() {
+        let position = dependencies.len();
+        positions.insert(command, position);
+
+        enclosing.truncate(command_depth(command, &iter));
+        let mut deps: Vec<usize> = enclosing.last().copied().into_iter().collect();
+        if let ProofCommand::Step(step) = command {
+            // A reference to a subproof is a reference to the step that ends it
+            let lookup = |c: &ProofCommand| {
+                let c = match c {
+                    ProofCommand::Subproof(s) => s.commands.last().unwrap(),
+                    other => other,
+                };
+                positions.get(&(c as *const _)).copied()
+            };
+            let premises = step.premises.iter().chain(&step.discharge);
+            deps.extend(premises.filter_map(|&p| iter.try_get_premise(p).and_then(lookup)));
+            if iter.is_end_step() {
+                let subproof = iter.current_subproof().unwrap();
+                if let Some(i) = subproof.len().checked_sub(2) {
+                    deps.extend(lookup(&subproof[i]));
+                }
+            }
+        }
+        if target_position.is_none() && !command.is_subproof() && command.id() == target {
+            target_position = Some(position);
+        }
+        if command.is_subproof() {
+            enclosing.push(position);
+        }
+        dependencies.push(deps);
+    }
+
+    let mut needed = vec![false; dependencies.len()];
+    let mut stack = vec![target_position?];
+    while let Some(i) = stack.pop() {
+        if !needed[i] {
+            needed[i] = true;
+            stack.extend(&dependencies[i]);
+        }
     }
+    Some(needed)
 }
@@ -0,0 +1,126 @@
+//! Normalization passes that are applied to a proof before it is checked.
+
+use crate::ast::*;
+use indexmap::IndexSet;
+use std::collections::HashMap;
+
+/// Rewrites every `distinct` term in the problem premises and in the proof to its pairwise form.
+/// See [`Config::expand_distinct`](super::Config::expand_distinct).
+///
+/// The conclusions of steps that use the `distinct_elim` rule are also rewritten, so that they match
+/// the steps that use them as premises. These steps are then checked by a variant of the rule that
+/// expects the pairwise form (see [`get_rule`](super::ProofChecker::get_rule)).
+pub(super) fn expand_distinct_in_proof(
+    pool: &mut PrimitivePool,
+    premises: &IndexSet<Rc<Term>>,
+    proof: &Proof,
+) -> (IndexSet<Rc<Term>>, Proof) {
+    let mut expander = DistinctExpander { pool, cache: HashMap::new() };
+    let premises = premises.iter().map(|t| expander.expand(t)).collect();
+    let proof = Proof {
+        constant_definitions: proof
+            .constant_definitions
+            .iter()
+            .map(|(name, value)| (name.clone(), expander.expand(value)))
+            .collect(),
+        commands: expander.expand_commands(&proof.commands),
+        comments: proof.comments.clone(),
+    };
+    (premises, proof)
+}
+
+struct DistinctExpander<'a> {
+    pool: &'a mut PrimitivePool,
+    cache: HashMap<Rc<Term>, Rc<Term>>,
+}
+
+impl DistinctExpander<'_> {
+    fn expand_commands(&mut self, commands: &[ProofCommand]) -> Vec<ProofCommand> {
+        commands
+            .iter()
+            .map(|command| match command {
+                ProofCommand::Assume { id, term } => ProofCommand::Assume {
+                    id: id.clone(),
+                    term: self.expand(term),
+                },
+                ProofCommand::Step(step) => ProofCommand::Step(ProofStep {
+                    clause: self.expand_all(&step.clause),
+                    args: self.expand_all(&step.args),
+                    ..step.clone()
+                }),
+                ProofCommand::Subproof(s) => ProofCommand::Subproof(Subproof {
+                    commands: self.expand_commands(&s.commands),
+                    args: s
+                        .args
+                        .iter()
+                        .map(|arg| match arg {
+                            AnchorArg::Assign(var, value) => {
+                                AnchorArg::Assign(var.clone(), self.expand(value))
+                            }
+                            AnchorArg::Variable(_) => arg.clone(),
+                        })
+                        .collect(),
+                    context_id: s.context_id,
+                }),
+            })
+            .collect()
+    }
+
+    fn expand_all(&mut self, terms: &[Rc<Term>]) -> Vec<Rc<Term>> {
+        terms.iter().map(|t| self.expand(t)).collect()
+    }
+
+    fn expand(&mut self, term: &Rc<Term>) -> Rc<Term> {
+        if let Some(result) = self.cache.get(term) {
+            return result.clone();
+        }
+        let result = match term.as_ref() {
+            Term::Op(Operator::Distinct, args) => {
+                let args = self.expand_all(args);
+                let mut pairs = Vec::new();
+                for (i, a) in args.iter().enumerate() {
+                    for b in &args[i + 1..] {
+                        pairs.push(build_term!(self.pool, (not (= {a.clone()} {b.clone()}))));
+                    }
+                }
+                if pairs.len() == 1 {
+                    pairs.pop().unwrap()
+                } else {
+                    self.pool.add(Term::Op(Operator::And, pairs))
+                }
+            }
+            Term::Op(op, args) => {
+                let args = self.expand_all(args);
+                self.pool.add(Term::Op(*op, args))
+            }
+            Term::App(f, args) => {
+                let args = self.expand_all(args);
+                self.pool.add(Term::App(f.clone(), args))
+            }
+            Term::Binder(binder, bindings, inner) => {
+                let inner = self.expand(inner);
+                self.pool
+                    .add(Term::Binder(*binder, bindings.clone(), inner))
+            }
+            Term::Let(bindings, inner) => {
+                let bindings = bindings
+                    .iter()
+                    .map(|(name, value)| (name.clone(), self.expand(value)))
+                    .collect();
+                let inner = self.expand(inner);
+                self.pool.add(Term::Let(BindingList(bindings), inner))
+            }
+            Term::ParamOp { op, op_args, args } => {
+                let args = self.expand_all(args);
+                self.pool.add(Term::ParamOp {
+                    op: *op,
+                    op_args: op_args.clone(),
+                    args,
+                })
+            }
+            Term::Const(_) | Term::Var(..) | Term::Sort(_) => term.clone(),
+        };
+        self.cache.insert(term.clone(), result.clone());
+        result
+    }
+}
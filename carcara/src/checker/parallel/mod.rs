@@ -1,14 +1,17 @@
 pub mod scheduler;
 
 use super::{
+    check_premise_references,
     error::{CheckerError, SubproofError},
+    is_trust_rule,
     rules::{Premise, RuleArgs, RuleResult},
-    Config, ProofChecker,
+    sort_of, Config, ProofChecker,
 };
 use crate::benchmarking::{CollectResults, OnlineBenchmarkResults};
 use crate::checker::CheckerStatistics;
 use crate::{
     ast::{pool::advanced::*, *},
+    parser::SortError,
     CarcaraResult, Error,
 };
 use indexmap::IndexSet;
@@ -40,7 +43,8 @@ impl<'c> ParallelProofChecker<'c> {
     ) -> Self {
         ParallelProofChecker {
             pool,
-            config,
+            // `expand_distinct` is not supported by the parallel checker
+            config: Config { expand_distinct: false, ..config },
             prelude,
             context: ContextStack::from_usage(context_usage),
             reached_empty_clause: false,
@@ -126,6 +130,9 @@ impl<'c> ParallelProofChecker<'c> {
             // If an error happened
             err?;
 
+            if self.config.require_empty_clause && !proof.concludes_empty_clause() {
+                return Err(Error::DoesNotReachEmptyClause);
+            }
             if reached {
                 Ok(holey)
             } else {
@@ -224,6 +231,9 @@ impl<'c> ParallelProofChecker<'c> {
             // If an error happened
             err?;
 
+            if self.config.require_empty_clause && !proof.concludes_empty_clause() {
+                return Err(Error::DoesNotReachEmptyClause);
+            }
             if reached {
                 Ok(holey)
             } else {
@@ -266,10 +276,10 @@ impl<'c> ParallelProofChecker<'c> {
                     // previous command in the subproof
                     let previous_command = if iter.is_end_step() {
                         let subproof = iter.current_subproof().unwrap();
-                        let index = subproof.len() - 2;
                         subproof
-                            .get(index)
-                            .map(|command| Premise::new((iter.depth(), index), command))
+                            .len()
+                            .checked_sub(2)
+                            .map(|index| Premise::new((iter.depth(), index), &subproof[index]))
                     } else {
                         None
                     };
@@ -363,7 +373,7 @@ impl<'c> ParallelProofChecker<'c> {
             return true;
         }
 
-        if self.config.elaborated {
+        if self.config.elaborated || self.config.strict {
             return false;
         }
 
@@ -420,8 +430,31 @@ impl<'c> ParallelProofChecker<'c> {
         if !step.discharge.is_empty() && step.rule != "subproof" {
             return Err(CheckerError::Subproof(SubproofError::DischargeInWrongRule));
         }
+        check_premise_references(
+            step,
+            |p| iter.precedes_current(p),
+            |p| iter.try_get_premise(p),
+        )?;
+
+        // Local assumptions must be discharged by the step that closes their subproof. This is
+        // checked before the rule itself, so that it also applies to steps whose rule is skipped
+        if iter.is_end_step() {
+            let subproof = iter.current_subproof().unwrap();
+            ProofChecker::check_discharge(subproof, iter.depth(), &step.discharge)?;
+        }
+
+        if self.config.check_sorts {
+            for term in &step.clause {
+                let sort = sort_of(pool, term)?;
+                SortError::assert_eq(&Sort::Bool, &sort)?;
+            }
+        }
+
+        if self.config.forbid_trust && is_trust_rule(&step.rule) {
+            return Err(CheckerError::TrustForbidden(step.rule.clone()));
+        }
 
-        let rule = match ProofChecker::get_rule(&step.rule, self.config.elaborated) {
+        let rule = match ProofChecker::get_rule(&step.rule, &self.config) {
             Some(r) => r,
             None if self.config.ignore_unknown_rules => {
                 self.is_holey = true;
@@ -459,12 +492,7 @@ impl<'c> ParallelProofChecker<'c> {
             polyeq_time: &mut polyeq_time,
         };
 
-        rule(rule_args)?;
-
-        if iter.is_end_step() {
-            let subproof = iter.current_subproof().unwrap();
-            ProofChecker::check_discharge(subproof, iter.depth(), &step.discharge)?;
-        }
+        self.config.run_rule(rule, step, rule_args)?;
 
         if let Some(s) = stats {
             let time = time.elapsed();
@@ -208,6 +208,9 @@ impl Scheduler {
 /// Iterates through schedule steps
 pub struct ScheduleIter<'a> {
     proof_stack: Vec<&'a [ProofCommand]>,
+    /// The index of the last returned command in each subproof in the stack. For the enclosing
+    /// subproofs, this is the index of the subproof command that is currently open.
+    positions: Vec<usize>,
     steps: &'a Vec<(usize, usize)>,
     step_id: usize,
 }
@@ -216,6 +219,7 @@ impl<'a> ScheduleIter<'a> {
     pub fn new(proof_commands: &'a [ProofCommand], steps: &'a Vec<(usize, usize)>) -> Self {
         Self {
             proof_stack: vec![proof_commands],
+            positions: vec![0],
             steps,
             step_id: 0,
         }
@@ -252,6 +256,19 @@ impl<'a> ScheduleIter<'a> {
     pub fn get_premise(&self, (depth, index): (usize, usize)) -> &ProofCommand {
         &self.proof_stack[depth][index]
     }
+
+    /// Similar to `get_premise`, but returns `None` if the premise index does not refer to a valid
+    /// command.
+    pub fn try_get_premise(&self, (depth, index): (usize, usize)) -> Option<&ProofCommand> {
+        self.proof_stack.get(depth)?.get(index)
+    }
+
+    /// Returns `true` if a premise index of the form (depth, index in subproof) refers to a command
+    /// that precedes the most recently returned step, in the same subproof or in one of the
+    /// subproofs that enclose it.
+    pub fn precedes_current(&self, (depth, index): (usize, usize)) -> bool {
+        self.positions.get(depth).is_some_and(|&i| index < i)
+    }
 }
 
 impl<'a> Iterator for ScheduleIter<'a> {
@@ -266,6 +283,7 @@ impl<'a> Iterator for ScheduleIter<'a> {
         // If current step is an closing subproof step
         while let (_, usize::MAX) = self.steps[self.step_id] {
             self.proof_stack.pop();
+            self.positions.pop();
             self.step_id += 1;
             // If reached the last closing step of the whole proof
             if self.step_id == self.steps.len() {
@@ -277,9 +295,11 @@ impl<'a> Iterator for ScheduleIter<'a> {
 
         let top = self.proof_stack.last().unwrap();
         let command = &top[cur_step.1];
+        *self.positions.last_mut().unwrap() = cur_step.1;
         // Opens a new subproof
         if let ProofCommand::Subproof(subproof) = command {
             self.proof_stack.push(&subproof.commands);
+            self.positions.push(0);
         }
         Some(command)
     }
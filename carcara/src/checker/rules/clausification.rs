@@ -3,13 +3,73 @@ use super::{
     assert_operation_len, assert_polyeq_expected, get_premise_term, CheckerError, EqualityError,
     RuleArgs, RuleResult,
 };
-use crate::ast::*;
+use crate::{ast::*, checker::error::BfunElimError};
 use indexmap::IndexMap;
+use std::time::Duration;
 
 pub fn distinct_elim(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
 
     let (distinct_args, second_term) = match_term_err!((= (distinct ...) second) = &conclusion[0])?;
+    check_distinct_elim(pool, distinct_args, second_term)
+}
+
+/// The `distinct_elim` rule used when the `expand_distinct` option is enabled. In that case, the
+/// `distinct` term in the conclusion was already rewritten to its pairwise form, so the arguments
+/// of the original `distinct` term are recovered from that form.
+///
+/// Note that the conclusion is still a valid equality even if the left-hand side was not originally
+/// a `distinct` term, since it is equivalent to the `distinct` term whose arguments are recovered.
+pub fn expanded_distinct_elim(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
+    assert_clause_len(conclusion, 1)?;
+
+    let (first_term, second_term) = match_term_err!((= first second) = &conclusion[0])?;
+    let distinct_args = collapse_pairwise_disequalities(first_term).ok_or_else(|| {
+        CheckerError::TermOfWrongForm("(and (not (= a b)) ...)", first_term.clone())
+    })?;
+    check_distinct_elim(pool, &distinct_args, second_term)
+}
+
+/// Given the pairwise form of a `distinct` term, as produced by the `expand_distinct` option,
+/// returns the arguments of the `distinct` term. Returns `None` if the term is not of that form.
+fn collapse_pairwise_disequalities(term: &Rc<Term>) -> Option<Vec<Rc<Term>>> {
+    if let Some((a, b)) = match_term!((not (= a b)) = term) {
+        return Some(vec![a.clone(), b.clone()]);
+    }
+
+    // If there are `n` arguments, the first `n - 1` disequalities are between the first argument
+    // and each of the others
+    let pairs = match_term!((and ...) = term)?
+        .iter()
+        .map(|t| match_term!((not (= a b)) = t))
+        .collect::<Option<Vec<_>>>()?;
+    let n = (1..=pairs.len() + 1).find(|n| n * (n - 1) / 2 >= pairs.len())?;
+    if n < 3 || n * (n - 1) / 2 != pairs.len() {
+        return None;
+    }
+    let first = pairs[0].0;
+    let args: Vec<_> = std::iter::once(first)
+        .chain(pairs[..n - 1].iter().map(|(_, b)| *b))
+        .cloned()
+        .collect();
+
+    let mut k = 0;
+    for i in 0..n {
+        for j in (i + 1)..n {
+            if pairs[k] != (&args[i], &args[j]) {
+                return None;
+            }
+            k += 1;
+        }
+    }
+    Some(args)
+}
+
+fn check_distinct_elim(
+    pool: &mut dyn TermPool,
+    distinct_args: &[Rc<Term>],
+    second_term: &Rc<Term>,
+) -> RuleResult {
     match distinct_args {
         [] | [_] => unreachable!(),
         [a, b] => {
@@ -164,15 +224,23 @@ pub fn not_xor2(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
     assert_eq(phi_2, &conclusion[1])
 }
 
+// Following SMT-LIB, n-ary implications are right-associative, that is, `(=> phi_1 ... phi_n)` is
+// equivalent to `(=> phi_1 (=> ... (=> phi_n-1 phi_n)))`. The `implies`, `not_implies1` and
+// `not_implies2` rules handle them accordingly.
+
 pub fn implies(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
     assert_num_premises(premises, 1)?;
-    assert_clause_len(conclusion, 2)?;
 
     let premise_term = get_premise_term(&premises[0])?;
-    let (phi_1, phi_2) = match_term_err!((=> phi_1 phi_2) = premise_term)?;
+    let phis = match_term_err!((=> ...) = premise_term)?;
+    assert_clause_len(conclusion, phis.len())?;
 
-    assert_eq(phi_1, conclusion[0].remove_negation_err()?)?;
-    assert_eq(phi_2, &conclusion[1])
+    // The conclusion must be `(cl (not phi_1) ... (not phi_n-1) phi_n)`
+    let (antecedents, consequent) = phis.split_at(phis.len() - 1);
+    for (phi, t) in antecedents.iter().zip(conclusion) {
+        assert_eq(phi, t.remove_negation_err()?)?;
+    }
+    assert_eq(&consequent[0], conclusion.last().unwrap())
 }
 
 pub fn not_implies1(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
@@ -180,9 +248,16 @@ pub fn not_implies1(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResu
     assert_clause_len(conclusion, 1)?;
 
     let premise_term = get_premise_term(&premises[0])?;
-    let (phi_1, _) = match_term_err!((not (=> phi_1 phi_2)) = premise_term)?;
-
-    assert_eq(phi_1, &conclusion[0])
+    let phis = match_term_err!((not (=> ...)) = premise_term)?;
+
+    // If the implication is false, all of its antecedents must be true, so the conclusion may be
+    // any one of them
+    let antecedents = &phis[..phis.len() - 1];
+    if antecedents.contains(&conclusion[0]) {
+        Ok(())
+    } else {
+        assert_eq(&antecedents[0], &conclusion[0])
+    }
 }
 
 pub fn not_implies2(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
@@ -190,28 +265,50 @@ pub fn not_implies2(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResu
     assert_clause_len(conclusion, 1)?;
 
     let premise_term = get_premise_term(&premises[0])?;
-    let (_, phi_2) = match_term_err!((not (=> phi_1 phi_2)) = premise_term)?;
+    let phis = match_term_err!((not (=> ...)) = premise_term)?;
 
-    assert_eq(phi_2, conclusion[0].remove_negation_err()?)
+    assert_eq(phis.last().unwrap(), conclusion[0].remove_negation_err()?)
 }
 
-pub fn nary_elim(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
-    /// The three possible cases for n-ary operators: chainable, right associative and left
-    /// associative
-    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-    enum Case {
-        Chainable,
-        RightAssoc,
-        LeftAssoc,
-    }
+/// The ways in which an n-ary operator can be expanded by `nary_elim`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NaryCase {
+    Chainable,
+    RightAssoc,
+    LeftAssoc,
+}
 
+/// The identity element of an n-ary operator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NaryIdentity {
+    Zero,
+    One,
+    True,
+    False,
+}
+
+/// The operators supported by `nary_elim`, with the way each of them is expanded, and their
+/// identity element, if they have one. The expansion of an operator with an identity element may
+/// also end in that element. For example, `(+ a b c)` may be expanded into `(+ (+ a b) c)` or
+/// into `(+ (+ (+ 0 a) b) c)`.
+const NARY_ELIM_OPERATORS: &[(Operator, NaryCase, Option<NaryIdentity>)] = &[
+    (Operator::Equals, NaryCase::Chainable, None),
+    (Operator::Add, NaryCase::LeftAssoc, Some(NaryIdentity::Zero)),
+    (Operator::Sub, NaryCase::LeftAssoc, None),
+    (Operator::Mult, NaryCase::LeftAssoc, Some(NaryIdentity::One)),
+    (Operator::And, NaryCase::LeftAssoc, Some(NaryIdentity::True)),
+    (Operator::Or, NaryCase::LeftAssoc, Some(NaryIdentity::False)),
+    (Operator::Implies, NaryCase::RightAssoc, None),
+];
+
+pub fn nary_elim(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
     /// A function to expand terms that fall in the right or left associative cases. For example,
     /// the term `(=> p q r s)` will be expanded into the term `(=> p (=> q (=> r s)))`.
     fn expand_assoc(
         pool: &mut dyn TermPool,
         op: Operator,
         args: &[Rc<Term>],
-        case: Case,
+        case: NaryCase,
     ) -> Rc<Term> {
         let (head, tail) = match args {
             [] => unreachable!(),
@@ -219,7 +316,7 @@ pub fn nary_elim(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
 
             // The "head" term will be the first or last term in `args`, depending on if the
             // operator is right or left associative
-            [first, rest @ ..] if case == Case::RightAssoc => (first, rest),
+            [first, rest @ ..] if case == NaryCase::RightAssoc => (first, rest),
             [rest @ .., last] => (last, rest),
         };
 
@@ -227,39 +324,98 @@ pub fn nary_elim(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
         let nested = expand_assoc(pool, op, tail, case);
 
         let new_args = match case {
-            Case::RightAssoc => vec![head.clone(), nested],
-            Case::LeftAssoc => vec![nested, head.clone()],
-            Case::Chainable => unreachable!(),
+            NaryCase::RightAssoc => vec![head.clone(), nested],
+            NaryCase::LeftAssoc => vec![nested, head.clone()],
+            NaryCase::Chainable => unreachable!(),
         };
         pool.add(Term::Op(op, new_args))
     }
 
+    /// Returns the innermost term of an expansion of `n` arguments that ends in an extra term, that
+    /// is, the term that would be the identity element in a valid expansion.
+    fn innermost_term(
+        term: &Rc<Term>,
+        op: Operator,
+        case: NaryCase,
+        n: usize,
+    ) -> Option<&Rc<Term>> {
+        let mut current = term;
+        for _ in 0..n {
+            current = match current.as_ref() {
+                Term::Op(o, args) if *o == op && args.len() == 2 => match case {
+                    NaryCase::LeftAssoc => &args[0],
+                    _ => &args[1],
+                },
+                _ => return None,
+            };
+        }
+        Some(current)
+    }
+
     assert_clause_len(conclusion, 1)?;
 
     let (original, result) = match_term_err!((= o r) = &conclusion[0])?;
 
     let (op, args) = match original.as_ref() {
-        Term::Op(op, args) if args.len() >= 2 => (op, args),
+        Term::Op(op, args) if args.len() >= 2 => (*op, args),
         _ => return Err(CheckerError::NotValidNaryTerm(original.clone())),
     };
 
-    let case = match op {
-        Operator::Equals => Case::Chainable,
-        Operator::Add | Operator::Sub | Operator::Mult => Case::LeftAssoc,
-        Operator::Implies => Case::RightAssoc,
-        _ => return Err(CheckerError::NotValidNaryTerm(original.clone())),
+    let Some(&(_, case, identity)) = NARY_ELIM_OPERATORS.iter().find(|(o, ..)| *o == op) else {
+        return Err(CheckerError::NaryElimUnsupportedOperator(op));
     };
 
-    let expected = match case {
-        Case::Chainable => {
-            let and_args: Vec<_> = args
-                .windows(2)
-                .map(|args| pool.add(Term::Op(*op, args.to_vec())))
-                .collect();
-            pool.add(Term::Op(Operator::And, and_args))
-        }
-        assoc_case => expand_assoc(pool, *op, args, assoc_case),
+    if case == NaryCase::Chainable {
+        let and_args: Vec<_> = args
+            .windows(2)
+            .map(|args| pool.add(Term::Op(op, args.to_vec())))
+            .collect();
+        let expected = pool.add(Term::Op(Operator::And, and_args));
+        return assert_is_expected(result, expected);
+    }
+
+    let expected = expand_assoc(pool, op, args, case);
+    if *result == expected {
+        return Ok(());
+    }
+
+    let (opposite, name) = match case {
+        NaryCase::LeftAssoc => (NaryCase::RightAssoc, "left-associative"),
+        _ => (NaryCase::LeftAssoc, "right-associative"),
     };
+    if *result == expand_assoc(pool, op, args, opposite) {
+        return Err(CheckerError::NaryElimWrongAssociativity(op, name));
+    }
+
+    if let Some(identity) = identity {
+        if let Some(innermost) = innermost_term(result, op, case, args.len()) {
+            let mut new_args = args.clone();
+            match case {
+                NaryCase::LeftAssoc => new_args.insert(0, innermost.clone()),
+                _ => new_args.push(innermost.clone()),
+            }
+            if *result == expand_assoc(pool, op, &new_args, case) {
+                let is_real = pool.sort(&args[0]).as_sort() == Some(&Sort::Real);
+                let identity = pool.add(match identity {
+                    NaryIdentity::Zero if is_real => Term::new_real(0),
+                    NaryIdentity::Zero => Term::new_int(0),
+                    NaryIdentity::One if is_real => Term::new_real(1),
+                    NaryIdentity::One => Term::new_int(1),
+                    NaryIdentity::True => Term::new_bool(true),
+                    NaryIdentity::False => Term::new_bool(false),
+                });
+                rassert!(
+                    *innermost == identity,
+                    CheckerError::NaryElimWrongIdentity {
+                        op,
+                        expected: identity,
+                        got: innermost.clone(),
+                    }
+                );
+                return Ok(());
+            }
+        }
+    }
     assert_is_expected(result, expected)
 }
 
@@ -400,5 +556,115 @@ pub fn bfun_elim(
     let psi = get_premise_term(&premises[0])?;
 
     let expected = apply_bfun_elim(pool, psi, &mut IndexMap::new())?;
-    assert_polyeq_expected(&conclusion[0], expected, polyeq_time)
+    if polyeq(&conclusion[0], &expected, polyeq_time) {
+        return Ok(());
+    }
+    let error = match psi.as_ref() {
+        Term::Binder(..) => bfun_elim_first_step_error(psi, &conclusion[0], &expected, polyeq_time),
+        Term::App(..) => bfun_elim_second_step_error(&conclusion[0], &expected, polyeq_time),
+        _ => None,
+    };
+    match error {
+        Some(e) => Err(e.into()),
+        None => assert_polyeq_expected(&conclusion[0], expected, polyeq_time),
+    }
+}
+
+/// Tries to find which case of the expansion of a quantifier over boolean variables is wrong.
+///
+/// The expansion must contain one case for each of the `2^k` assignments to the `k` boolean
+/// variables, where the case with index `i` assigns to the `j`-th boolean variable the value of the
+/// `j`-th bit of `i`.
+fn bfun_elim_first_step_error(
+    original: &Rc<Term>,
+    got: &Rc<Term>,
+    expected: &Rc<Term>,
+    polyeq_time: &mut Duration,
+) -> Option<BfunElimError> {
+    let (binder, bindings, _) = original.as_quant()?;
+    let bool_vars: Vec<_> = bindings
+        .iter()
+        .filter(|(_, sort)| *sort.as_sort().unwrap() == Sort::Bool)
+        .map(|(name, _)| name.clone())
+        .collect();
+    if bool_vars.is_empty() {
+        return None;
+    }
+
+    // If the quantifier also had non-boolean variables, the expansion is still quantified over them
+    let (expected, got) = match (expected.as_quant(), got.as_quant()) {
+        (Some((_, _, expected)), Some((_, _, got))) => (expected, got),
+        (None, _) => (expected, got),
+        (Some(_), None) => return None,
+    };
+    let op = if binder == Binder::Forall {
+        Operator::And
+    } else {
+        Operator::Or
+    };
+    let expected_cases = match expected.as_ref() {
+        Term::Op(o, args) if *o == op => args,
+        _ => return None,
+    };
+    let got_cases = match got.as_ref() {
+        Term::Op(o, args) if *o == op => args.as_slice(),
+        _ => std::slice::from_ref(got),
+    };
+    if got_cases.len() != expected_cases.len() {
+        return Some(BfunElimError::WrongNumberOfCases(
+            original.clone(),
+            expected_cases.len(),
+            got_cases.len(),
+        ));
+    }
+
+    let (index, got) = got_cases
+        .iter()
+        .enumerate()
+        .find(|(i, got)| !polyeq(got, &expected_cases[*i], polyeq_time))?;
+    if let Some(j) = expected_cases
+        .iter()
+        .position(|expected| polyeq(got, expected, polyeq_time))
+    {
+        return Some(BfunElimError::CaseOutOfOrder(index, j));
+    }
+    let assignment = bool_vars
+        .into_iter()
+        .enumerate()
+        .map(|(j, var)| (var, index & (1 << j) != 0))
+        .collect();
+    Some(BfunElimError::WrongCase {
+        index,
+        assignment,
+        expected: expected_cases[index].clone(),
+        got: got.clone(),
+    })
+}
+
+/// Tries to find which boolean argument of a function application was expanded incorrectly into
+/// an `ite` term.
+fn bfun_elim_second_step_error(
+    got: &Rc<Term>,
+    expected: &Rc<Term>,
+    polyeq_time: &mut Duration,
+) -> Option<BfunElimError> {
+    let (arg, expected_then, expected_else) = match_term!((ite c t e) = expected)?;
+    let error = || BfunElimError::WrongArgumentExpansion {
+        arg: arg.clone(),
+        expected: expected.clone(),
+        got: got.clone(),
+    };
+    let Some((got_arg, got_then, got_else)) = match_term!((ite c t e) = got) else {
+        return Some(error());
+    };
+    if !polyeq(got_arg, arg, polyeq_time) {
+        return Some(error());
+    }
+    for (got, expected) in [(got_then, expected_then), (got_else, expected_else)] {
+        if !polyeq(got, expected, polyeq_time) {
+            return bfun_elim_second_step_error(got, expected, polyeq_time)
+                .or_else(|| Some(error()));
+        }
+    }
+    None
 }
@@ -1,9 +1,18 @@
 use super::{
-    assert_clause_len, assert_num_premises, get_premise_term, CheckerError, RuleArgs, RuleResult,
+    assert_clause_len, assert_num_premises, get_premise_term, CheckerError, Premise, RuleArgs,
+    RuleResult,
 };
 use crate::{ast::*, checker::error::CongruenceError};
 
 pub fn eq_congruent(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
+    generic_eq_congruent(conclusion, true)
+}
+
+pub fn strict_eq_congruent(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
+    generic_eq_congruent(conclusion, false)
+}
+
+fn generic_eq_congruent(conclusion: &[Rc<Term>], allow_symmetry: bool) -> RuleResult {
     assert_clause_len(conclusion, 2..)?;
 
     let premises = conclusion[..conclusion.len() - 1]
@@ -11,10 +20,18 @@ pub fn eq_congruent(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
         .map(Rc::remove_negation_err);
     let conclusion = match_term_err!((= f g) = conclusion.last().unwrap())?;
 
-    generic_congruent_rule(premises, conclusion)
+    generic_congruent_rule(premises, conclusion, allow_symmetry)
 }
 
 pub fn eq_congruent_pred(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
+    generic_eq_congruent_pred(conclusion, true)
+}
+
+pub fn strict_eq_congruent_pred(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
+    generic_eq_congruent_pred(conclusion, false)
+}
+
+fn generic_eq_congruent_pred(conclusion: &[Rc<Term>], allow_symmetry: bool) -> RuleResult {
     assert_clause_len(conclusion, 3..)?;
 
     let premises = conclusion[..conclusion.len() - 2]
@@ -30,13 +47,25 @@ pub fn eq_congruent_pred(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
         None => (p, q.remove_negation_err()?),
     };
 
-    generic_congruent_rule(premises, conclusion)
+    generic_congruent_rule(premises, conclusion, allow_symmetry)
 }
 
 /// A function to check congruence. Useful for the `eq_congruent` and `eq_congruent_pred`
 /// rules. `premises` should be an iterator over the argument equalities, and `conclusion`
-/// should be the two function applications.
-fn generic_congruent_rule<'a, T>(premises: T, conclusion: (&Rc<Term>, &Rc<Term>)) -> RuleResult
+/// should be the two function applications. If `allow_symmetry` is `false`, each equality must
+/// be in the same orientation as the arguments it justifies.
+///
+/// Each argument position is checked independently, in order. Positions whose arguments are
+/// syntactically equal may be skipped, that is, they don't need an equality, unless
+/// `allow_symmetry` is `false`, in which case there must be exactly one equality for each position.
+/// Since terms of different sorts are never equal, an equality can only justify a position whose
+/// arguments have the same sorts as its sides. If a position is not justified, the error reports
+/// the first such position.
+fn generic_congruent_rule<'a, T>(
+    premises: T,
+    conclusion: (&Rc<Term>, &Rc<Term>),
+    allow_symmetry: bool,
+) -> RuleResult
 where
     T: Iterator<Item = Result<&'a Rc<Term>, CheckerError>>,
 {
@@ -63,43 +92,72 @@ where
         f_args.len() == g_args.len(),
         CongruenceError::DifferentNumberOfArguments(f_args.len(), g_args.len())
     );
-    {
-        // We check the number of premises in two steps, because the error is different if there
-        // too many or too few premises
+    rassert!(
+        premises.len() <= f_args.len(),
+        CongruenceError::TooManyPremises
+    );
+    if !allow_symmetry {
         let n = premises.len();
-        rassert!(n <= f_args.len(), CongruenceError::TooManyPremises);
         rassert!(
             n == f_args.len(),
-            CongruenceError::MissingPremise(f_args[n].clone(), g_args[n].clone())
+            CongruenceError::MissingPremise(n, f_args[n].clone(), g_args[n].clone())
         );
     }
 
-    for (i, (t, u)) in premises.into_iter().enumerate() {
-        let (f, g) = (&f_args[i], &g_args[i]);
-        rassert!(
-            (f, g) == (t, u) || (f, g) == (u, t),
-            CongruenceError::PremiseDoesntJustifyArgs {
-                args: (f.clone(), g.clone()),
-                premise: (t.clone(), u.clone())
+    let justifies = |(t, u): (&Rc<Term>, &Rc<Term>), f: &Rc<Term>, g: &Rc<Term>| {
+        (f, g) == (t, u) || allow_symmetry && (f, g) == (u, t)
+    };
+    let mut remaining = premises.as_slice();
+    for (i, (f, g)) in f_args.iter().zip(g_args).enumerate() {
+        match remaining.first() {
+            Some(&premise) if justifies(premise, f, g) => remaining = &remaining[1..],
+            _ if f == g && allow_symmetry => (),
+
+            // If the next premise justifies one of the following positions, we consider that the
+            // premise for this position is missing
+            Some(&premise)
+                if !f_args[i + 1..]
+                    .iter()
+                    .zip(&g_args[i + 1..])
+                    .any(|(f, g)| justifies(premise, f, g)) =>
+            {
+                return Err(CongruenceError::PremiseDoesntJustifyArgs {
+                    index: i,
+                    args: (f.clone(), g.clone()),
+                    premise: (premise.0.clone(), premise.1.clone()),
+                }
+                .into());
             }
-        );
+            _ => return Err(CongruenceError::MissingPremise(i, f.clone(), g.clone()).into()),
+        }
     }
+
+    // At the end, all premises must have been used
+    rassert!(remaining.is_empty(), CongruenceError::TooManyPremises);
     Ok(())
 }
 
 /// Since the semantics of the `cong` rule is slightly different from that of `eq_congruent` and
-/// `eq_congruent_pred`, we cannot just use the `generic_congruent_rule` function
-fn check_cong<'a, I>(premises: &[(&'a Rc<Term>, &'a Rc<Term>)], f_args: I, g_args: I) -> RuleResult
+/// `eq_congruent_pred`, we cannot just use the `generic_congruent_rule` function. The positions
+/// reported in errors are the indices of the arguments in `f_args` and `g_args`. If
+/// `allow_symmetry` is `false`, each premise must be in the same orientation as the arguments it
+/// justifies
+fn check_cong<'a, I>(
+    premises: &[(&'a Rc<Term>, &'a Rc<Term>)],
+    f_args: I,
+    g_args: I,
+    allow_symmetry: bool,
+) -> RuleResult
 where
     I: IntoIterator<Item = &'a Rc<Term>>,
 {
     let mut premises = premises.iter().peekable();
-    for (f_arg, g_arg) in f_args.into_iter().zip(g_args) {
+    for (index, (f_arg, g_arg)) in f_args.into_iter().zip(g_args).enumerate() {
         let expected = (f_arg.as_ref(), g_arg.as_ref());
         match premises.peek() {
             // If the next premise can justify that the arguments are equal, we consume it. We
             // prefer consuming the premise even if the arguments are directly equal
-            Some((t, u)) if expected == (t, u) || expected == (u, t) => {
+            Some((t, u)) if expected == (t, u) || allow_symmetry && expected == (u, t) => {
                 premises.next();
             }
 
@@ -110,10 +168,12 @@ where
             // If the arguments are not directly equal, we needed a premise that can justify
             // their equality, so now we return an error
             None => {
-                return Err(CongruenceError::MissingPremise(f_arg.clone(), g_arg.clone()).into());
+                let (f_arg, g_arg) = (f_arg.clone(), g_arg.clone());
+                return Err(CongruenceError::MissingPremise(index, f_arg, g_arg).into());
             }
             Some((t, u)) => {
                 return Err(CongruenceError::PremiseDoesntJustifyArgs {
+                    index,
                     args: (f_arg.clone(), g_arg.clone()),
                     premise: ((*t).clone(), (*u).clone()),
                 }
@@ -131,6 +191,14 @@ where
 }
 
 pub fn cong(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+    generic_cong(conclusion, premises, true)
+}
+
+pub fn strict_cong(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+    generic_cong(conclusion, premises, false)
+}
+
+fn generic_cong(conclusion: &[Rc<Term>], premises: &[Premise], allow_symmetry: bool) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
     assert_num_premises(premises, 1..)?;
 
@@ -145,7 +213,7 @@ pub fn cong(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
         // equalities of two terms, the order of their arguments may be flipped. Because of that,
         // we have to treat this special case separately
         (Term::Op(Operator::Equals, f_args), Term::Op(Operator::Equals, g_args))
-            if f_args.len() == 2 && g_args.len() == 2 =>
+            if allow_symmetry && f_args.len() == 2 && g_args.len() == 2 =>
         {
             // We have to test all four possibilities: neither f nor g are flipped, only f is
             // flipped, only g is flipped, or both f and g are flipped
@@ -155,11 +223,11 @@ pub fn cong(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
             // We store the result of the first possibility (when neither arguments are flipped),
             // because, if the checking fails in the end, we use it to get more sensible error
             // messages
-            let original_result = check_cong(&premises, f_args, g_args);
+            let original_result = check_cong(&premises, f_args, g_args, true);
             let any_valid = original_result.is_ok()
-                || check_cong(&premises, f_args_flipped, g_args.as_slice()).is_ok()
-                || check_cong(&premises, f_args.as_slice(), g_args_flipped).is_ok()
-                || check_cong(&premises, f_args_flipped, g_args_flipped).is_ok();
+                || check_cong(&premises, f_args_flipped, g_args.as_slice(), true).is_ok()
+                || check_cong(&premises, f_args.as_slice(), g_args_flipped, true).is_ok()
+                || check_cong(&premises, f_args_flipped, g_args_flipped, true).is_ok();
             return if any_valid { Ok(()) } else { original_result };
         }
 
@@ -203,12 +271,22 @@ pub fn cong(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
         f_args.len() == g_args.len(),
         CongruenceError::DifferentNumberOfArguments(f_args.len(), g_args.len())
     );
-    check_cong(&premises, f_args, g_args)
+    check_cong(&premises, f_args, g_args, allow_symmetry)
 }
 
 pub fn ho_cong(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
-    use std::iter::once;
+    generic_ho_cong(conclusion, premises, true)
+}
+
+pub fn strict_ho_cong(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+    generic_ho_cong(conclusion, premises, false)
+}
 
+fn generic_ho_cong(
+    conclusion: &[Rc<Term>],
+    premises: &[Premise],
+    allow_symmetry: bool,
+) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
     assert_num_premises(premises, 1..)?;
 
@@ -218,17 +296,28 @@ pub fn ho_cong(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
         .collect::<Result<_, _>>()?;
 
     let (f, g) = match_term_err!((= f g) = &conclusion[0])?;
-    let (f_args, g_args) = match (f.as_ref(), g.as_ref()) {
+    let ((f, f_args), (g, g_args)) = match (f.as_ref(), g.as_ref()) {
         (Term::App(f, f_args), Term::App(g, g_args)) => {
             rassert!(
                 f_args.len() == g_args.len(),
                 CongruenceError::DifferentNumberOfArguments(f_args.len(), g_args.len())
             );
-            Ok((once(f).chain(f_args.iter()), once(g).chain(g_args.iter())))
+            Ok(((f, f_args), (g, g_args)))
         }
         (Term::App(..), _) => Err(CongruenceError::NotApplicationOrOperation(g.clone())),
         _ => Err(CongruenceError::NotApplicationOrOperation(f.clone())),
     }?;
 
-    check_cong(&premises, f_args, g_args)
+    // The applied functions are justified like any pair of arguments, but they are checked
+    // separately so that the positions reported in errors refer only to the arguments
+    let mut premises = premises.as_slice();
+    match premises.first() {
+        Some(&(t, u)) if (f, g) == (t, u) || allow_symmetry && (f, g) == (u, t) => {
+            premises = &premises[1..];
+        }
+        _ if f == g => (),
+        _ => return Err(CongruenceError::FunctionsNotJustified(f.clone(), g.clone()).into()),
+    }
+
+    check_cong(premises, f_args, g_args, allow_symmetry)
 }
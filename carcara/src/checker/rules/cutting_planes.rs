@@ -4,8 +4,8 @@ use super::{
 use crate::ast::{Constant, Operator};
 use crate::checker::error::{CheckerError, EqualityError};
 use crate::checker::Rc;
-use rug::Integer;
-use std::collections::HashMap;
+use rug::{ops::DivRounding, Integer};
+use std::collections::{BTreeSet, HashMap};
 
 type PbHash = HashMap<String, Integer>;
 
@@ -55,7 +55,10 @@ fn get_pb_hashmap(pbsum: &Rc<Term>) -> Result<PbHash, CheckerError> {
 
 fn unwrap_pseudoboolean_inequality(clause: &Rc<Term>) -> Result<(PbHash, Integer), CheckerError> {
     let (pbsum, constant) = match_term_err!((>= pbsum constant) = clause)?;
-    let constant = constant.as_integer_err()?;
+    // The constant may be negative, in which case it is written using the unary `-` operator
+    let constant = constant
+        .as_signed_integer()
+        .ok_or_else(|| CheckerError::ExpectedAnyInteger(constant.clone()))?;
     let pbsum = get_pb_hashmap(pbsum)?;
     Ok((pbsum, constant))
 }
@@ -128,35 +131,45 @@ fn reduce_pbsum(pbsum: &PbHash) -> (PbHash, Integer) {
     (res, slack)
 }
 
-/// Checks that every key in ``pbsum_a`` is present in ``pbsum_b``
+/// Returns the literals of a pseudo-boolean sum that have a nonzero coefficient, sorted by name.
+fn nonzero_literals(pbsum: &PbHash) -> BTreeSet<&str> {
+    pbsum
+        .iter()
+        .filter(|(_, coeff)| **coeff != 0)
+        .map(|(literal, _)| literal.as_str())
+        .collect()
+}
+
+/// Checks that every literal with a nonzero coefficient in ``pbsum_a`` is also present in
+/// ``pbsum_b``
 /// ha ⊆ hb
 fn assert_pbsum_subset_keys(pbsum_a: &PbHash, pbsum_b: &PbHash) -> Result<(), CheckerError> {
-    for key in pbsum_a.keys() {
-        let val = pbsum_a.get(key).unwrap();
-
-        // Zero coefficient is ignored.
-        if val == &Integer::from(0) {
-            continue;
-        }
-
+    for key in nonzero_literals(pbsum_a) {
         if pbsum_b.get(key).is_none() {
             return Err(CheckerError::Explanation(format!(
                 "Key {} of {:?} not found in {:?}",
-                key, pbsum_b, pbsum_a
+                key, pbsum_a, pbsum_b
             )));
         }
     }
     Ok(())
 }
 
-fn assert_pbsum_same_keys(pbsum_a: &PbHash, pbsum_b: &PbHash) -> Result<(), CheckerError> {
-    // All keys in A are in B
-    assert_pbsum_subset_keys(pbsum_a, pbsum_b)?;
-
-    // All keys in B are in A
-    assert_pbsum_subset_keys(pbsum_b, pbsum_a)?;
-
-    Ok(())
+/// Checks that ``expected`` and ``got`` have exactly the same literals with nonzero coefficients.
+/// Literals with a zero coefficient may be present in either one or omitted.
+fn assert_pbsum_same_keys(expected: &PbHash, got: &PbHash) -> Result<(), CheckerError> {
+    let expected = nonzero_literals(expected);
+    let got = nonzero_literals(got);
+    if expected == got {
+        return Ok(());
+    }
+    let missing: Vec<_> = expected.difference(&got).copied().collect();
+    let unexpected: Vec<_> = got.difference(&expected).copied().collect();
+    Err(CheckerError::Explanation(format!(
+        "Literals of the conclusion differ from the expected ones: missing [{}], unexpected [{}]",
+        missing.join(", "),
+        unexpected.join(", ")
+    )))
 }
 
 pub fn cp_addition(RuleArgs { premises, args, conclusion, .. }: RuleArgs) -> RuleResult {
@@ -255,16 +268,20 @@ pub fn cp_multiplication(RuleArgs { premises, args, conclusion, .. }: RuleArgs)
         CheckerError::ExpectedInteger(scalar.clone() * constant_p, conclusion.clone())
     );
 
-    // Verify premise and conclusion share same keys
-    assert_pbsum_same_keys(&pbsum_p, &pbsum_c)?;
+    // Verify the scaled premise and the conclusion have the same literals. Literals whose scaled
+    // coefficient is zero may be dropped
+    let expected: PbHash = pbsum_p
+        .iter()
+        .map(|(literal, coeff_p)| (literal.clone(), Integer::from(&scalar * coeff_p)))
+        .collect();
+    assert_pbsum_same_keys(&expected, &pbsum_c)?;
 
     // Verify pseudo-boolean sums match
-    for (literal, coeff_p) in pbsum_p {
+    for (literal, expected) in expected {
         if let Some(coeff_c) = pbsum_c.get(&literal) {
-            let expected = &scalar * coeff_p;
             rassert!(
                 &expected == coeff_c,
-                CheckerError::ExpectedInteger(expected.clone(), conclusion.clone())
+                CheckerError::ExpectedInteger(expected, conclusion.clone())
             );
         }
     }
@@ -273,6 +290,7 @@ pub fn cp_multiplication(RuleArgs { premises, args, conclusion, .. }: RuleArgs)
 
 pub fn cp_division(RuleArgs { premises, args, conclusion, .. }: RuleArgs) -> RuleResult {
     assert_num_premises(premises, 1)?;
+    assert_clause_len(premises[0].clause, 1)?;
     let clause = &premises[0].clause[0];
 
     // Check there is exactly one arg
@@ -298,22 +316,27 @@ pub fn cp_division(RuleArgs { premises, args, conclusion, .. }: RuleArgs) -> Rul
     // Unwrap the conclusion inequality
     let (pbsum_c, constant_c) = unwrap_pseudoboolean_inequality(conclusion)?;
 
-    // Verify constants match ceil(c/d) == (c+d-1)/d
+    // Verify constants match ceil(c/d). Since the constant may be negative, we can't compute this
+    // as (c+d-1)/d, which only gives the ceiling for non-negative values
+    let expected_constant = constant_p.div_ceil(&divisor);
     rassert!(
-        (constant_p.clone() + divisor.clone() - 1) / divisor.clone() == constant_c,
-        CheckerError::ExpectedInteger(constant_p / divisor.clone(), conclusion.clone())
+        expected_constant == constant_c,
+        CheckerError::ExpectedInteger(expected_constant, conclusion.clone())
     );
 
-    // Verify premise and conclusion share same keys
-    assert_pbsum_same_keys(&pbsum_p, &pbsum_c)?;
+    // Verify the divided premise and the conclusion have the same literals
+    let expected: PbHash = pbsum_p
+        .into_iter()
+        .map(|(literal, coeff_p)| (literal, coeff_p.div_ceil(&divisor)))
+        .collect();
+    assert_pbsum_same_keys(&expected, &pbsum_c)?;
 
     // Verify pseudo-boolean sums match
-    for (literal, coeff_p) in pbsum_p {
+    for (literal, expected) in expected {
         if let Some(coeff_c) = pbsum_c.get(&literal) {
-            let expected: Integer = (coeff_p + &divisor - 1) / &divisor;
             rassert!(
                 &expected == coeff_c,
-                CheckerError::ExpectedInteger(expected.clone(), conclusion.clone())
+                CheckerError::ExpectedInteger(expected, conclusion.clone())
             );
         }
     }
@@ -324,6 +347,7 @@ pub fn cp_division(RuleArgs { premises, args, conclusion, .. }: RuleArgs) -> Rul
 pub fn cp_saturation(RuleArgs { premises, args, conclusion, .. }: RuleArgs) -> RuleResult {
     assert_num_premises(premises, 1)?;
     assert_num_args(args, 0)?;
+    assert_clause_len(premises[0].clause, 1)?;
     let clause = &premises[0].clause[0];
 
     // Check there is exactly one conclusion
@@ -342,16 +366,19 @@ pub fn cp_saturation(RuleArgs { premises, args, conclusion, .. }: RuleArgs) -> R
         CheckerError::ExpectedInteger(constant_p.clone(), conclusion.clone())
     );
 
-    // Verify premise and conclusion share same keys
-    assert_pbsum_same_keys(&pbsum_p, &pbsum_c)?;
+    // Verify the saturated premise and the conclusion have the same literals
+    let expected: PbHash = pbsum_p
+        .into_iter()
+        .map(|(literal, coeff_p)| (literal, Ord::min(&constant_p, &coeff_p).clone()))
+        .collect();
+    assert_pbsum_same_keys(&expected, &pbsum_c)?;
 
     // Verify saturation of variables match
-    for (literal, coeff_p) in pbsum_p {
+    for (literal, expected) in expected {
         if let Some(coeff_c) = pbsum_c.get(&literal) {
-            let expected = Ord::min(&constant_p, &coeff_p);
             rassert!(
-                expected == coeff_c,
-                CheckerError::ExpectedInteger(expected.clone(), conclusion.clone())
+                &expected == coeff_c,
+                CheckerError::ExpectedInteger(expected, conclusion.clone())
             );
         }
     }
@@ -359,8 +386,66 @@ pub fn cp_saturation(RuleArgs { premises, args, conclusion, .. }: RuleArgs) -> R
     Ok(())
 }
 
+/// Weakens a constraint by removing one of its literals. Since every literal is at most 1, the
+/// constant is reduced by the coefficient of the removed literal.
+pub fn cp_weakening(RuleArgs { premises, args, conclusion, .. }: RuleArgs) -> RuleResult {
+    assert_num_premises(premises, 1)?;
+    assert_clause_len(premises[0].clause, 1)?;
+    let clause = &premises[0].clause[0];
+
+    // Check there are no args
+    assert_num_args(args, 0)?;
+
+    // Check there is exactly one conclusion
+    assert_clause_len(conclusion, 1)?;
+    let conclusion = &conclusion[0];
+
+    // Unwrap the premise inequality
+    let (pbsum_p, constant_p) = unwrap_pseudoboolean_inequality(clause)?;
+
+    // Unwrap the conclusion inequality
+    let (pbsum_c, constant_c) = unwrap_pseudoboolean_inequality(conclusion)?;
+
+    // Find the literal that was removed. A literal that is kept with a zero coefficient counts as
+    // removed
+    let literals_c = nonzero_literals(&pbsum_c);
+    let removed: Vec<_> = pbsum_p
+        .iter()
+        .filter(|&(literal, coeff)| *coeff != 0 && !literals_c.contains(literal.as_str()))
+        .collect();
+    let [(_, removed_coeff)] = removed[..] else {
+        return Err(CheckerError::Explanation(format!(
+            "Expected exactly one literal to be removed, got {}",
+            removed.len()
+        )));
+    };
+
+    // Verify constants match
+    let expected = constant_p - removed_coeff;
+    rassert!(
+        expected == constant_c,
+        CheckerError::ExpectedInteger(expected, conclusion.clone())
+    );
+
+    // Verify the remaining literals are the same as in the premise
+    assert_pbsum_subset_keys(&pbsum_c, &pbsum_p)?;
+    for (literal, coeff_c) in &pbsum_c {
+        if *coeff_c == 0 {
+            continue;
+        }
+        let coeff_p = &pbsum_p[literal];
+        rassert!(
+            coeff_p == coeff_c,
+            CheckerError::ExpectedInteger(coeff_p.clone(), conclusion.clone())
+        );
+    }
+
+    Ok(())
+}
+
 pub fn cp_literal(RuleArgs { pool, args, conclusion, .. }: RuleArgs) -> RuleResult {
     assert_num_args(args, 1)?;
+    assert_clause_len(conclusion, 1)?;
     // TODO: Set args type to FF 2
 
     if let Some(((c, (_, l)), _)) = match_term!((>= (* c (- 1 l)) 0) = &conclusion[0]) {
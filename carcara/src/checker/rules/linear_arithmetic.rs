@@ -342,13 +342,26 @@ pub fn la_generic(RuleArgs { conclusion, args, .. }: RuleArgs) -> RuleResult {
 pub fn la_disequality(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
 
-    let ((t1_1, t2_1), (t1_2, t2_2), (t2_3, t1_3)) = match_term_err!(
-        (or (= t1 t2) (not (<= t1 t2)) (not (<= t2 t1))) = &conclusion[0]
+    let ((t1, t2), first, second) = match_term_err!(
+        (or (= t1 t2) (not (<= a b)) (not (<= c d))) = &conclusion[0]
     )?;
-    assert_eq(t1_1, t1_2)?;
-    assert_eq(t1_2, t1_3)?;
-    assert_eq(t2_1, t2_2)?;
-    assert_eq(t2_2, t2_3)
+
+    // The two inequalities must go in opposite directions, so that together with the equality they
+    // cover all possible cases
+    let check_inequality =
+        |branch, expected: (&Rc<Term>, &Rc<Term>), got: (&Rc<Term>, &Rc<Term>)| {
+            rassert!(
+                expected == got,
+                LinearArithmeticError::DisequalityWrongInequality {
+                    branch,
+                    expected: (expected.0.clone(), expected.1.clone()),
+                    got: (got.0.clone(), got.1.clone()),
+                }
+            );
+            Ok(())
+        };
+    check_inequality("first", (t1, t2), first)?;
+    check_inequality("second", (t2, t1), second)
 }
 
 pub fn la_totality(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
@@ -361,20 +374,41 @@ pub fn la_totality(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
     assert_eq(t2_1, t2_2)
 }
 
-fn assert_less_than(a: &Rc<Term>, b: &Rc<Term>) -> RuleResult {
-    rassert!(
-        a.as_signed_number_err()? < b.as_signed_number_err()?,
-        LinearArithmeticError::ExpectedLessThan(a.clone(), b.clone())
-    );
-    Ok(())
+/// Returns an error stating that `clause` is not a tautology, since it is falsified by the given
+/// assignment.
+fn tautology_falsified(clause: &Rc<Term>, assignment: Vec<(Rc<Term>, Rational)>) -> CheckerError {
+    LinearArithmeticError::TautologyFalsified { clause: clause.clone(), assignment }.into()
 }
 
-fn assert_less_eq(a: &Rc<Term>, b: &Rc<Term>) -> RuleResult {
-    rassert!(
-        a.as_signed_number_err()? <= b.as_signed_number_err()?,
-        LinearArithmeticError::ExpectedLessEq(a.clone(), b.clone())
-    );
-    Ok(())
+/// Finds an assignment that satisfies the disequality `vars op constant`, where `op` is `>`, `>=`
+/// or `=`. This is done by setting every variable to zero, except for the first one with a non-zero
+/// coefficient, which is chosen so that the disequality holds. Returns `None` if there is no such
+/// assignment, that is, if the disequality is contradictory.
+fn satisfying_assignment(
+    op: Operator,
+    LinearComb(vars, constant): &LinearComb,
+) -> Option<Vec<(Rc<Term>, Rational)>> {
+    let mut assignment: Vec<_> = vars.keys().map(|v| (v.clone(), Rational::new())).collect();
+    match vars.values().position(|coeff| *coeff != 0) {
+        Some(i) => {
+            // If the operator is `>`, we must add one to the constant to make the disequality hold
+            let target = if op == Operator::GreaterThan {
+                Rational::from(constant + 1)
+            } else {
+                constant.clone()
+            };
+            assignment[i].1 = target / &vars[i];
+            Some(assignment)
+        }
+        None => {
+            let holds = match op {
+                Operator::GreaterThan => *constant < 0,
+                Operator::GreaterEq => *constant <= 0,
+                _ => *constant == 0,
+            };
+            holds.then_some(assignment)
+        }
+    }
 }
 
 pub fn la_tautology(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
@@ -388,13 +422,23 @@ pub fn la_tautology(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
         ) {
             // First case
             assert_eq(s_1, s_2)?;
-            assert_less_eq(d_1, d_2)
+            let (d_1, d_2) = (d_1.as_signed_number_err()?, d_2.as_signed_number_err()?);
+            rassert!(
+                d_1 <= d_2,
+                tautology_falsified(&conclusion[0], vec![(s_1.clone(), d_1)])
+            );
+            Ok(())
         } else if let (Some((s_1, d_1)), Some((s_2, d_2))) = (
             match_term!((<= s d1) = first),
             match_term!((not (<= s d2)) = second),
         ) {
             // Second case
             assert_eq(s_1, s_2)?;
+            let d_2_value = d_2.as_signed_number_err()?;
+            rassert!(
+                d_1.as_signed_number_err()? >= d_2_value,
+                tautology_falsified(&conclusion[0], vec![(s_1.clone(), d_2_value)])
+            );
             assert_eq(d_1, d_2)
         } else if let (Some((s_1, d_1)), Some((s_2, d_2))) = (
             match_term!((not (>= s d1)) = first),
@@ -402,13 +446,23 @@ pub fn la_tautology(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
         ) {
             // Third case
             assert_eq(s_1, s_2)?;
-            assert_less_eq(d_2, d_1)
+            let (d_1, d_2) = (d_1.as_signed_number_err()?, d_2.as_signed_number_err()?);
+            rassert!(
+                d_2 <= d_1,
+                tautology_falsified(&conclusion[0], vec![(s_1.clone(), d_1)])
+            );
+            Ok(())
         } else if let (Some((s_1, d_1)), Some((s_2, d_2))) = (
             match_term!((>= s d1) = first),
             match_term!((not (>= s d2)) = second),
         ) {
             // Fourth case
             assert_eq(s_1, s_2)?;
+            let d_2_value = d_2.as_signed_number_err()?;
+            rassert!(
+                d_1.as_signed_number_err()? <= d_2_value,
+                tautology_falsified(&conclusion[0], vec![(s_1.clone(), d_2_value)])
+            );
             assert_eq(d_1, d_2)
         } else if let (Some((s_1, d_1)), Some((s_2, d_2))) = (
             match_term!((not (<= s d1)) = first),
@@ -416,7 +470,12 @@ pub fn la_tautology(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
         ) {
             // Fifth case
             assert_eq(s_1, s_2)?;
-            assert_less_than(d_1, d_2)
+            let (d_1, d_2) = (d_1.as_signed_number_err()?, d_2.as_signed_number_err()?);
+            rassert!(
+                d_1 < d_2,
+                tautology_falsified(&conclusion[0], vec![(s_1.clone(), d_1)])
+            );
+            Ok(())
         } else {
             Err(LinearArithmeticError::NotValidTautologyCase(conclusion[0].clone()).into())
         }
@@ -439,13 +498,17 @@ pub fn la_tautology(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
             op = Operator::GreaterEq;
         }
 
-        // The final disequality should be tautological
+        // The final disequality should be contradictory, since it is the negation of the
+        // conclusion. If it isn't, we report an assignment that satisfies it, and therefore
+        // falsifies the conclusion
         let is_disequality_true = disequality.0.is_empty()
             && (disequality.1 > 0 || op == Operator::GreaterThan && disequality.1 == 0);
-        rassert!(
-            is_disequality_true,
-            LinearArithmeticError::DisequalityIsNotTautology(op, disequality),
-        );
+        if !is_disequality_true {
+            return Err(match satisfying_assignment(op, &disequality) {
+                Some(assignment) => tautology_falsified(&conclusion[0], assignment),
+                None => LinearArithmeticError::DisequalityIsNotTautology(op, disequality).into(),
+            });
+        }
         Ok(())
     }
 }
@@ -12,20 +12,33 @@ pub type RuleResult = Result<(), CheckerError>;
 
 pub type Rule = fn(RuleArgs) -> RuleResult;
 
+/// The arguments passed to a rule when checking a step.
 pub struct RuleArgs<'a> {
-    pub(super) conclusion: &'a [Rc<Term>],
-    pub(super) premises: &'a [Premise<'a>],
-    pub(super) args: &'a [Rc<Term>],
-    pub(super) pool: &'a mut dyn TermPool,
-    pub(super) context: &'a mut ContextStack,
+    /// The conclusion clause of the step.
+    pub conclusion: &'a [Rc<Term>],
 
-    // For rules that end a subproof, we need to pass the previous command in the subproof that it
-    // is closing, because it may be implicitly referenced, and it is not given as premises. If a
-    // rule is not ending a subproof, this should be `None`.
-    pub(super) previous_command: Option<Premise<'a>>,
-    pub(super) discharge: &'a [&'a ProofCommand],
+    /// The premises of the step.
+    pub premises: &'a [Premise<'a>],
 
-    pub(super) polyeq_time: &'a mut Duration,
+    /// The arguments of the step.
+    pub args: &'a [Rc<Term>],
+
+    /// The term pool, which must be used to construct any new terms.
+    pub pool: &'a mut dyn TermPool,
+
+    /// The context stack of the subproofs that contain the step.
+    pub context: &'a mut ContextStack,
+
+    /// For rules that end a subproof, the previous command in the subproof that it is closing,
+    /// since it may be implicitly referenced, and it is not given as premises. If the rule is not
+    /// ending a subproof, this is `None`.
+    pub previous_command: Option<Premise<'a>>,
+
+    /// The local assumptions discharged by the step.
+    pub discharge: &'a [&'a ProofCommand],
+
+    /// The total time spent in polyequality comparisons, which rules should add to.
+    pub polyeq_time: &'a mut Duration,
 }
 
 #[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
@@ -69,6 +82,17 @@ macro_rules! rassert {
     };
 }
 
+/// Removes pairs of leading negations from a literal, so literals that differ only by double
+/// negations are normalized to the same term. For example, both `p` and `(not (not p))` are
+/// normalized to `p`, and `(not (not (not p)))` is normalized to `(not p)`.
+fn normalize_literal(term: &Rc<Term>) -> &Rc<Term> {
+    let mut term = term;
+    while let Some(inner) = match_term!((not (not t)) = term) {
+        term = inner;
+    }
+    term
+}
+
 fn assert_num_premises<T: Into<Range>>(premises: &[Premise], range: T) -> RuleResult {
     let range = range.into();
     if !range.contains(premises.len()) {
@@ -130,6 +154,17 @@ fn assert_polyeq(a: &Rc<Term>, b: &Rc<Term>, time: &mut Duration) -> Result<(),
     Ok(())
 }
 
+fn assert_polyeq_mod_ite(
+    a: &Rc<Term>,
+    b: &Rc<Term>,
+    time: &mut Duration,
+) -> Result<(), CheckerError> {
+    if !polyeq_mod_ite(a, b, time) {
+        return Err(EqualityError::ExpectedEqual(a.clone(), b.clone()).into());
+    }
+    Ok(())
+}
+
 fn assert_polyeq_expected(got: &Rc<Term>, expected: Rc<Term>, time: &mut Duration) -> RuleResult {
     if !polyeq(got, &expected, time) {
         return Err(EqualityError::ExpectedToBe { expected, got: got.clone() }.into());
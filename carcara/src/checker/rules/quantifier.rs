@@ -27,11 +27,10 @@ pub fn forall_inst(
             Ok((var.clone(), value.clone()))
         })
         .collect::<Result<_, CheckerError>>()?;
-    let mut substitution = Substitution::new(pool, substitution)?;
 
     // Equalities may be reordered, and the application of the substitution might rename bound
     // variables, so we need to compare for alpha-equivalence here
-    let expected = substitution.apply(pool, original);
+    let expected = substitute(pool, original, &substitution)?;
     assert_alpha_equiv_expected(substituted, expected, polyeq_time)
 }
 
@@ -276,10 +275,10 @@ pub fn qnt_cnf(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
         ));
     }
 
-    let selected_clause = clauses
-        .iter()
-        .find(|&clause| clause == phi_prime)
-        .ok_or_else(|| QuantifierError::ClauseDoesntAppearInCnf(phi_prime.clone()))?;
+    let selected_clause = match clauses.iter().find(|&clause| clause == phi_prime) {
+        Some(c) => c,
+        None => return Err(cnf_clause_mismatch(&clauses, phi_prime).into()),
+    };
 
     // Cloning here may be unnecessary
     let free_vars = pool.free_vars(selected_clause);
@@ -298,8 +297,72 @@ pub fn qnt_cnf(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
     Ok(())
 }
 
+/// Builds the error for when the result clause of a `qnt_cnf` step doesn't appear in the CNF of
+/// the original term. To make the error more useful, this finds the clause in the CNF that shares
+/// the most literals with the result clause, if any.
+fn cnf_clause_mismatch(clauses: &[Rc<Term>], result: &Rc<Term>) -> QuantifierError {
+    fn literals(clause: &Rc<Term>) -> &[Rc<Term>] {
+        match clause.as_ref() {
+            Term::Op(Operator::Or, args) => args,
+            _ => std::slice::from_ref(clause),
+        }
+    }
+
+    let result_literals: IndexSet<_> = literals(result).iter().collect();
+    let closest = clauses
+        .iter()
+        .map(|c| {
+            let shared = literals(c)
+                .iter()
+                .filter(|l| result_literals.contains(l))
+                .count();
+            (shared, c)
+        })
+        .filter(|&(shared, _)| shared > 0)
+        .max_by_key(|&(shared, _)| shared);
+
+    match closest {
+        Some((_, closest)) => QuantifierError::CnfClauseMismatch {
+            clause: result.clone(),
+            closest: closest.clone(),
+        },
+        None => QuantifierError::ClauseDoesntAppearInCnf(result.clone()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    #[test]
+    fn cnf_clause_mismatch() {
+        use super::*;
+        use crate::parser::tests::*;
+
+        let mut pool = crate::ast::pool::PrimitivePool::new();
+        let definitions = "
+            (declare-fun p () Bool)
+            (declare-fun q () Bool)
+            (declare-fun r () Bool)
+        ";
+        let [c1, c2, result, unrelated] = parse_terms(
+            &mut pool,
+            definitions,
+            ["(or p q)", "(or (not p) r)", "(or (not p) q r)", "(not r)"],
+        );
+        let clauses = [c1, c2.clone()];
+
+        match super::cnf_clause_mismatch(&clauses, &result) {
+            QuantifierError::CnfClauseMismatch { clause, closest } => {
+                assert_eq!(clause, result);
+                assert_eq!(closest, c2);
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        assert!(matches!(
+            super::cnf_clause_mismatch(&clauses, &unrelated),
+            QuantifierError::ClauseDoesntAppearInCnf(t) if t == unrelated
+        ));
+    }
+
     #[test]
     fn conjunctive_normal_form() {
         use super::*;
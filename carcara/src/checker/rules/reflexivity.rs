@@ -1,9 +1,15 @@
 use super::{assert_clause_len, assert_eq, CheckerError, RuleArgs, RuleResult};
 use crate::ast::*;
 
-pub fn eq_reflexive(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
+pub fn eq_reflexive(RuleArgs { conclusion, pool, context, .. }: RuleArgs) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
     let (a, b) = match_term_err!((= a b) = &conclusion[0])?;
+
+    // Inside a subproof, the two terms may only be equal after applying the context substitutions
+    if a != b && !context.is_empty() {
+        let (a, b) = (context.apply(pool, a), context.apply(pool, b));
+        return assert_eq(&a, &b);
+    }
     assert_eq(a, b)
 }
 
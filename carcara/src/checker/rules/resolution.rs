@@ -1,10 +1,14 @@
 use super::{
     assert_clause_len, assert_eq, assert_is_bool_constant, assert_num_args, assert_num_premises,
-    CheckerError, Premise, RuleArgs, RuleResult,
+    normalize_literal, CheckerError, Premise, RuleArgs, RuleResult,
 };
 use crate::{ast::*, resolution::*};
 use indexmap::IndexSet;
 
+/// Checks a `resolution` step. Like in every other rule, the literals of the premise and conclusion
+/// clauses are never flattened, that is, a literal such as `(or p q)` is an atom, unrelated to the
+/// literals `p` and `q`, and can only be resolved against its negation `(not (or p q))`. To break a
+/// disjunction into a clause, the `or` rule must be used first.
 pub fn resolution(rule_args: RuleArgs) -> RuleResult {
     if !rule_args.args.is_empty() {
         // If the rule was given arguments, we redirect to the variant of "resolution" that takes
@@ -88,7 +92,16 @@ pub fn resolution_with_args(
 ) -> RuleResult {
     let resolution_result = apply_generic_resolution::<IndexSet<_>>(premises, args, pool)?;
 
-    let conclusion: IndexSet<_> = conclusion.iter().map(Rc::remove_all_negations).collect();
+    // The pivots must match exactly, but the remaining literals are compared modulo double
+    // negations, so a `(not (not p))` left in the resolvent may appear as `p` in the conclusion
+    let resolution_result: IndexSet<_> = resolution_result
+        .into_iter()
+        .map(|(n, t)| (n % 2, t))
+        .collect();
+    let conclusion: IndexSet<_> = conclusion
+        .iter()
+        .map(|t| normalize_literal(t).remove_all_negations())
+        .collect();
 
     if let Some(extra) = conclusion.difference(&resolution_result).next() {
         let extra = literal_to_term(pool, *extra);
@@ -208,6 +221,9 @@ pub fn tautology(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult
     assert_is_bool_constant(&conclusion[0], true)?;
 
     let premise = premises[0].clause;
+
+    // Each literal is stored with all of its leading negations removed, tagged with its polarity.
+    // This way, a complementary pair can be found with a single pass over the clause
     let mut seen = IndexSet::with_capacity(premise.len());
     let with_negations_removed = premise.iter().map(Rc::remove_all_negations_with_polarity);
     for (polarity, term) in with_negations_removed {
@@ -219,11 +235,13 @@ pub fn tautology(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult
     Err(ResolutionError::TautologyFailed.into())
 }
 
+/// Checks a `contraction` step. As in `resolution`, a literal that is a disjunction is treated as an
+/// atom, so only syntactically equal literals (modulo double negations) are merged.
 pub fn contraction(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
     assert_num_premises(premises, 1)?;
 
-    let premise_set: IndexSet<_> = premises[0].clause.iter().collect();
-    let conclusion_set: IndexSet<_> = conclusion.iter().collect();
+    let premise_set: IndexSet<_> = premises[0].clause.iter().map(normalize_literal).collect();
+    let conclusion_set: IndexSet<_> = conclusion.iter().map(normalize_literal).collect();
     if let Some(&t) = premise_set.difference(&conclusion_set).next() {
         Err(CheckerError::ContractionMissingTerm(t.clone()))
     } else if let Some(&t) = conclusion_set.difference(&premise_set).next() {
@@ -2,7 +2,7 @@ use super::{
     assert_clause_len, assert_eq, assert_is_bool_constant, CheckerError, EqualityError, RuleArgs,
     RuleResult,
 };
-use crate::{ast::*, utils::DedupIterator};
+use crate::{ast::*, checker::error::QuantifierError, utils::DedupIterator};
 use indexmap::{IndexMap, IndexSet};
 use rug::Rational;
 
@@ -42,35 +42,51 @@ fn generic_simplify_rule(
     conclusion: &[Rc<Term>],
     pool: &mut dyn TermPool,
     simplify_function: fn(&Term, &mut dyn TermPool) -> Option<Rc<Term>>,
+) -> RuleResult {
+    generic_simplify_rule_modulo(conclusion, pool, simplify_function, &mut |a, b| a == b)
+}
+
+/// Similar to `generic_simplify_rule`, but compares the simplified terms with the expected results
+/// using the given equality function, instead of syntactic equality.
+fn generic_simplify_rule_modulo(
+    conclusion: &[Rc<Term>],
+    pool: &mut dyn TermPool,
+    simplify_function: fn(&Term, &mut dyn TermPool) -> Option<Rc<Term>>,
+    eq: &mut dyn FnMut(&Rc<Term>, &Rc<Term>) -> bool,
 ) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
 
-    let mut simplify_until_fixed_point =
-        |term: &Rc<Term>, goal: &Rc<Term>| -> Result<Rc<Term>, CheckerError> {
-            let mut current = term.clone();
-            let mut seen = IndexSet::new();
-            loop {
-                if !seen.insert(current.clone()) {
-                    return Err(CheckerError::CycleInSimplification(current));
-                }
-                match simplify_function(&current, pool) {
-                    Some(next) => {
-                        if next == *goal {
-                            return Ok(next);
-                        }
-                        current = next;
+    let mut simplify_until_fixed_point = |term: &Rc<Term>,
+                                          goal: &Rc<Term>,
+                                          eq: &mut dyn FnMut(&Rc<Term>, &Rc<Term>) -> bool|
+     -> Result<Rc<Term>, CheckerError> {
+        let mut current = term.clone();
+        let mut seen = IndexSet::new();
+        loop {
+            if !seen.insert(current.clone()) {
+                return Err(CheckerError::CycleInSimplification(current));
+            }
+            match simplify_function(&current, pool) {
+                Some(next) => {
+                    if eq(&next, goal) {
+                        return Ok(next);
                     }
-                    None => return Ok(current),
+                    current = next;
                 }
+                None => return Ok(current),
             }
-        };
+        }
+    };
 
     let (left, right) = match_term_err!((= phi psi) = &conclusion[0])?;
 
     // Since equalities can be implicitly flipped, we have to check both possibilities. We store the
     // result of the first simplification to use in the error if both of them fail.
-    let result = simplify_until_fixed_point(left, right)?;
-    let got = result == *right || simplify_until_fixed_point(right, left)? == *left;
+    let result = simplify_until_fixed_point(left, right, eq)?;
+    let got = eq(&result, right) || {
+        let flipped = simplify_until_fixed_point(right, left, eq)?;
+        eq(&flipped, left)
+    };
     rassert!(
         got,
         CheckerError::SimplificationFailed {
@@ -82,78 +98,162 @@ fn generic_simplify_rule(
     Ok(())
 }
 
+/// The transformations applied by [`simplify`]. At each step, the first one that applies to the
+/// term is used.
+const SIMPLIFICATIONS: &[fn(&Term, &mut dyn TermPool) -> Option<Rc<Term>>] = &[
+    simplify_not_term,
+    simplify_and_or_term,
+    simplify_implies_term,
+    simplify_equiv_term,
+    simplify_eq_term,
+    simplify_ite_term,
+    simplify_bool_term,
+    simplify_comp_term,
+];
+
+/// Simplifies a term using the transformations of the simplification rules (`not_simplify`,
+/// `and_simplify`, `or_simplify`, `implies_simplify`, `equiv_simplify`, `eq_simplify`,
+/// `ite_simplify`, `bool_simplify` and `comp_simplify`). The term is simplified bottom-up, and the
+/// transformations are applied repeatedly until none of them applies anymore. Subterms under
+/// binders and `let` terms are not simplified.
+///
+/// For example, `(and true (or p p))` is simplified to `p`.
+pub fn simplify(term: &Rc<Term>, pool: &mut dyn TermPool) -> Rc<Term> {
+    simplify_rec(term, pool, &mut IndexMap::new())
+}
+
+fn simplify_rec(
+    term: &Rc<Term>,
+    pool: &mut dyn TermPool,
+    cache: &mut IndexMap<Rc<Term>, Rc<Term>>,
+) -> Rc<Term> {
+    if let Some(result) = cache.get(term) {
+        return result.clone();
+    }
+
+    let mut current = simplify_subterms(term, pool, cache);
+    let mut seen = IndexSet::new();
+    // If the transformations ever cycle, we stop at the first repeated term
+    while seen.insert(current.clone()) {
+        match SIMPLIFICATIONS.iter().find_map(|f| f(&current, pool)) {
+            // The transformations may introduce new subterms that can also be simplified
+            Some(next) => current = simplify_subterms(&next, pool, cache),
+            None => break,
+        }
+    }
+    cache.insert(term.clone(), current.clone());
+    current
+}
+
+fn simplify_subterms(
+    term: &Rc<Term>,
+    pool: &mut dyn TermPool,
+    cache: &mut IndexMap<Rc<Term>, Rc<Term>>,
+) -> Rc<Term> {
+    let mut simplify_all = |args: &[Rc<Term>]| -> Vec<_> {
+        args.iter().map(|a| simplify_rec(a, pool, cache)).collect()
+    };
+    let new_term = match term.as_ref() {
+        Term::App(f, args) => Term::App(f.clone(), simplify_all(args)),
+        Term::Op(op, args) => Term::Op(*op, simplify_all(args)),
+        Term::ParamOp { op, op_args, args } => Term::ParamOp {
+            op: *op,
+            op_args: op_args.clone(),
+            args: simplify_all(args),
+        },
+        _ => return term.clone(),
+    };
+    pool.add(new_term)
+}
+
 pub fn ite_simplify(args: RuleArgs) -> RuleResult {
-    generic_simplify_rule(args.conclusion, args.pool, |term, pool| {
-        simplify!(term {
-            // ite true t_1 t_2 => t_1
-            (ite true t_1 t_2): (_, t_1, _) => t_1.clone(),
-
-            // ite false t_1 t_2 => t_2
-            (ite false t_1 t_2): (_, _, t_2) => t_2.clone(),
-
-            // ite phi t t => t
-            (ite phi t t): (_, t_1, t_2) if t_1 == t_2 => t_1.clone(),
-
-            // ite psi true false => psi
-            (ite psi true false): (psi, _, _) => psi.clone(),
-
-            // ite psi false true => ¬psi
-            (ite psi false true): (psi, _, _) => build_term!(pool, (not {psi.clone()})),
-
-            // ite ¬phi t_1 t_2 => ite phi t_2 t_1
-            (ite (not phi) t_1 t_2): (phi, t_1, t_2) => {
-                build_term!(pool, (ite {phi.clone()} {t_2.clone()} {t_1.clone()}))
-            },
-
-            // ite phi (ite phi t_1 t_2) t_3 => ite phi t_1 t_3
-            (ite phi (ite phi t_1 t_2) t_3): (phi_1, (phi_2, t_1, _), t_3) if phi_1 == phi_2 => {
-                build_term!(pool, (ite {phi_1.clone()} {t_1.clone()} {t_3.clone()}))
-            },
-
-            // ite phi t_1 (ite phi t_2 t_3) => ite phi t_1 t_3
-            (ite phi t_1 (ite phi t_2 t_3)): (phi_1, t_1, (phi_2, _, t_3)) if phi_1 == phi_2 => {
-                build_term!(pool, (ite {phi_1.clone()} {t_1.clone()} {t_3.clone()}))
-            },
-
-            // ite psi true phi => psi v phi
-            (ite psi true phi): (psi, _, phi) => {
-                build_term!(pool, (or {psi.clone()} {phi.clone()}))
-            },
-
-            // ite psi phi false => psi ^ phi
-            (ite psi phi false): (psi, phi, _) => {
-                build_term!(pool, (and {psi.clone()} {phi.clone()}))
-            },
-
-            // ite psi false phi => ¬psi ^ phi
-            (ite psi false phi): (psi, _, phi) => {
-                build_term!(pool, (and (not {psi.clone()}) {phi.clone()}))
-            },
-
-            // ite psi phi true => ¬psi v phi
-            (ite psi phi true): (psi, phi, _) => {
-                build_term!(pool, (or (not {psi.clone()}) {phi.clone()}))
-            },
-        })
+    generic_simplify_rule(args.conclusion, args.pool, simplify_ite_term)
+}
+
+/// Similar to `ite_simplify`, but compares the simplification result with the expected term modulo
+/// trivial simplifications of `ite` subterms. See [`polyeq_mod_ite`].
+pub fn ite_simplify_mod_ite(args: RuleArgs) -> RuleResult {
+    let time = args.polyeq_time;
+    generic_simplify_rule_modulo(
+        args.conclusion,
+        args.pool,
+        simplify_ite_term,
+        &mut |a, b| polyeq_mod_ite(a, b, time),
+    )
+}
+
+fn simplify_ite_term(term: &Term, pool: &mut dyn TermPool) -> Option<Rc<Term>> {
+    simplify!(term {
+        // ite true t_1 t_2 => t_1
+        (ite true t_1 t_2): (_, t_1, _) => t_1.clone(),
+
+        // ite false t_1 t_2 => t_2
+        (ite false t_1 t_2): (_, _, t_2) => t_2.clone(),
+
+        // ite phi t t => t
+        (ite phi t t): (_, t_1, t_2) if t_1 == t_2 => t_1.clone(),
+
+        // ite psi true false => psi
+        (ite psi true false): (psi, _, _) => psi.clone(),
+
+        // ite psi false true => ¬psi
+        (ite psi false true): (psi, _, _) => build_term!(pool, (not {psi.clone()})),
+
+        // ite ¬phi t_1 t_2 => ite phi t_2 t_1
+        (ite (not phi) t_1 t_2): (phi, t_1, t_2) => {
+            build_term!(pool, (ite {phi.clone()} {t_2.clone()} {t_1.clone()}))
+        },
+
+        // ite phi (ite phi t_1 t_2) t_3 => ite phi t_1 t_3
+        (ite phi (ite phi t_1 t_2) t_3): (phi_1, (phi_2, t_1, _), t_3) if phi_1 == phi_2 => {
+            build_term!(pool, (ite {phi_1.clone()} {t_1.clone()} {t_3.clone()}))
+        },
+
+        // ite phi t_1 (ite phi t_2 t_3) => ite phi t_1 t_3
+        (ite phi t_1 (ite phi t_2 t_3)): (phi_1, t_1, (phi_2, _, t_3)) if phi_1 == phi_2 => {
+            build_term!(pool, (ite {phi_1.clone()} {t_1.clone()} {t_3.clone()}))
+        },
+
+        // ite psi true phi => psi v phi
+        (ite psi true phi): (psi, _, phi) => {
+            build_term!(pool, (or {psi.clone()} {phi.clone()}))
+        },
+
+        // ite psi phi false => psi ^ phi
+        (ite psi phi false): (psi, phi, _) => {
+            build_term!(pool, (and {psi.clone()} {phi.clone()}))
+        },
+
+        // ite psi false phi => ¬psi ^ phi
+        (ite psi false phi): (psi, _, phi) => {
+            build_term!(pool, (and (not {psi.clone()}) {phi.clone()}))
+        },
+
+        // ite psi phi true => ¬psi v phi
+        (ite psi phi true): (psi, phi, _) => {
+            build_term!(pool, (or (not {psi.clone()}) {phi.clone()}))
+        },
     })
 }
 
 pub fn eq_simplify(args: RuleArgs) -> RuleResult {
-    generic_simplify_rule(args.conclusion, args.pool, |term, pool| {
-        simplify!(term {
-            // t = t => true
-            (= t t): (t1, t2) if t1 == t2 => pool.bool_true(),
-
-            // t_1 = t_2 => false, if t_1 and t_2 are different numerical constants
-            (= t t): (t1, t2) if {
-                let t1 = t1.as_signed_number();
-                let t2 = t2.as_signed_number();
-                t1.is_some() && t2.is_some() && t1 != t2
-            } => pool.bool_false(),
-
-            // ¬(t = t) => false, if t is a numerical constant
-            (not (= t t)): (t1, t2) if t1 == t2 && t1.is_signed_number() => pool.bool_false(),
-        })
+    generic_simplify_rule(args.conclusion, args.pool, simplify_eq_term)
+}
+
+fn simplify_eq_term(term: &Term, pool: &mut dyn TermPool) -> Option<Rc<Term>> {
+    simplify!(term {
+        // t = t => true
+        (= t t): (t1, t2) if t1 == t2 => pool.bool_true(),
+
+        // t_1 = t_2 => false, if t_1 and t_2 are different numerical constants
+        (= t t): (t1, t2) if {
+            let t1 = t1.as_signed_number();
+            let t2 = t2.as_signed_number();
+            t1.is_some() && t2.is_some() && t1 != t2
+        } => pool.bool_false(),
+
+        // ¬(t = t) => false, if t is a numerical constant
+        (not (= t t)): (t1, t2) if t1 == t2 && t1.is_signed_number() => pool.bool_false(),
     })
 }
 
@@ -220,24 +320,15 @@ fn generic_and_or_simplify(
     }
 
     // Finally, we check to see if the result was short-circuited
-    let seen: IndexSet<(bool, &Rc<Term>)> = phis
-        .iter()
-        .map(Rc::remove_all_negations_with_polarity)
-        .collect();
-    for term in &phis {
-        // If the term is the "short-circuit term", or is the negation of a term previously
-        // encountered, the result is short-circuited
-        let (polarity, inner) = term.remove_all_negations_with_polarity();
-        if seen.contains(&(!polarity, inner)) || term.is_bool_constant(short_circuit_term) {
-            return if result_args.len() == 1 {
-                assert_is_bool_constant(&result_args[0], short_circuit_term)
-            } else {
-                Err(CheckerError::ExpectedBoolConstant(
-                    short_circuit_term,
-                    result_term.clone(),
-                ))
-            };
-        }
+    if is_short_circuited(&phis, short_circuit_term) {
+        return if result_args.len() == 1 {
+            assert_is_bool_constant(&result_args[0], short_circuit_term)
+        } else {
+            Err(CheckerError::ExpectedBoolConstant(
+                short_circuit_term,
+                result_term.clone(),
+            ))
+        };
     }
 
     if phis.is_empty() {
@@ -259,6 +350,47 @@ fn generic_and_or_simplify(
     }
 }
 
+/// Returns `true` if a conjunction or disjunction with the given arguments is short-circuited, that
+/// is, if one of the arguments is the "short-circuit term", or is the negation of another argument.
+fn is_short_circuited(phis: &[Rc<Term>], short_circuit_term: bool) -> bool {
+    let seen: IndexSet<(bool, &Rc<Term>)> = phis
+        .iter()
+        .map(Rc::remove_all_negations_with_polarity)
+        .collect();
+    phis.iter().any(|term| {
+        let (polarity, inner) = term.remove_all_negations_with_polarity();
+        seen.contains(&(!polarity, inner)) || term.is_bool_constant(short_circuit_term)
+    })
+}
+
+/// Applies all the transformations of the `and_simplify` and `or_simplify` rules to a conjunction
+/// or disjunction at once. This is only used by [`simplify`], since the rules themselves also
+/// accept the intermediate results.
+fn simplify_and_or_term(term: &Term, pool: &mut dyn TermPool) -> Option<Rc<Term>> {
+    let (op, args) = match term {
+        Term::Op(op @ (Operator::And | Operator::Or), args) => (*op, args),
+        _ => return None,
+    };
+    let skip_term = op == Operator::And;
+
+    let mut phis = args.clone();
+    phis.retain(|t| !t.is_bool_constant(skip_term));
+    let mut seen = IndexSet::with_capacity(phis.len());
+    phis.retain(|t| seen.insert(t.clone()));
+
+    Some(if is_short_circuited(&phis, !skip_term) {
+        pool.bool_constant(!skip_term)
+    } else if phis.is_empty() {
+        pool.bool_constant(skip_term)
+    } else if phis.len() == 1 {
+        phis.pop().unwrap()
+    } else if phis.len() < args.len() {
+        pool.add(Term::Op(op, phis))
+    } else {
+        return None;
+    })
+}
+
 pub fn and_simplify(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
     generic_and_or_simplify(pool, conclusion, Operator::And)
 }
@@ -268,138 +400,233 @@ pub fn or_simplify(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
 }
 
 pub fn not_simplify(args: RuleArgs) -> RuleResult {
-    generic_simplify_rule(args.conclusion, args.pool, |term, pool| {
-        simplify!(term {
-            // ¬(¬phi) => phi
-            (not (not phi)): phi => phi.clone(),
+    generic_simplify_rule(args.conclusion, args.pool, simplify_not_term)
+}
 
-            // ¬false => true
-            (not false): _ => pool.bool_true(),
+fn simplify_not_term(term: &Term, pool: &mut dyn TermPool) -> Option<Rc<Term>> {
+    simplify!(term {
+        // ¬(¬phi) => phi
+        (not (not phi)): phi => phi.clone(),
 
-            // ¬true => false
-            (not true): _ => pool.bool_false(),
-        })
+        // ¬false => true
+        (not false): _ => pool.bool_true(),
+
+        // ¬true => false
+        (not true): _ => pool.bool_false(),
     })
 }
 
 pub fn implies_simplify(args: RuleArgs) -> RuleResult {
-    generic_simplify_rule(args.conclusion, args.pool, |term, pool| {
-        simplify!(term {
-            // ¬phi_1 -> ¬phi_2 => phi_2 -> phi_1
-            (=> (not phi_1) (not phi_2)): (phi_1, phi_2) => {
-                build_term!(pool, (=> {phi_2.clone()} {phi_1.clone()}))
-            },
-
-            // false -> phi => true
-            (=> false phi): _ => pool.bool_true(),
-
-            // phi -> true => true
-            (=> phi true): _ => pool.bool_true(),
-
-            // true -> phi => phi
-            (=> true phi): (_, phi) => phi.clone(),
-
-            // phi -> false => ¬phi
-            (=> phi false): (phi, _) => build_term!(pool, (not {phi.clone()})),
-
-            // phi -> phi => true
-            (=> phi phi): (phi_1, phi_2) if phi_1 == phi_2 => pool.bool_true(),
-
-            // ¬phi -> phi => phi
-            // phi -> ¬phi => ¬phi
-            (=> phi_1 phi_2): (phi_1, phi_2) if {
-                phi_1.remove_negation() == Some(phi_2) || phi_2.remove_negation() == Some(phi_1)
-            } => phi_2.clone(),
-
-            // (phi_1 -> phi_2) -> phi_2 => phi_1 v phi_2
-            (=> (=> phi_1 phi_2) phi_3): ((phi_1, phi_2), phi_3) if phi_2 == phi_3 => {
-                build_term!(pool, (or {phi_1.clone()} {phi_2.clone()}))
-            },
-        })
+    let result = generic_simplify_rule(args.conclusion, args.pool, simplify_implies_term);
+
+    // If the original term has a constant antecedent or consequent, we report which of these cases
+    // applies to it, instead of the generic simplification error
+    if let Err(CheckerError::SimplificationFailed { original, result: expected, target }) = &result
+    {
+        if let Some(case) = implies_constant_case(original) {
+            return Err(CheckerError::SimplificationCaseMismatch {
+                rule: "implies_simplify",
+                case,
+                original: original.clone(),
+                expected: expected.clone(),
+                got: target.clone(),
+            });
+        }
+    }
+    result
+}
+
+fn simplify_implies_term(term: &Term, pool: &mut dyn TermPool) -> Option<Rc<Term>> {
+    simplify!(term {
+        // ¬phi_1 -> ¬phi_2 => phi_2 -> phi_1
+        (=> (not phi_1) (not phi_2)): (phi_1, phi_2) => {
+            build_term!(pool, (=> {phi_2.clone()} {phi_1.clone()}))
+        },
+
+        // false -> phi => true
+        (=> false phi): _ => pool.bool_true(),
+
+        // phi -> true => true
+        (=> phi true): _ => pool.bool_true(),
+
+        // true -> phi => phi
+        (=> true phi): (_, phi) => phi.clone(),
+
+        // phi -> false => ¬phi
+        (=> phi false): (phi, _) => build_term!(pool, (not {phi.clone()})),
+
+        // phi -> phi => true
+        (=> phi phi): (phi_1, phi_2) if phi_1 == phi_2 => pool.bool_true(),
+
+        // ¬phi -> phi => phi
+        // phi -> ¬phi => ¬phi
+        (=> phi_1 phi_2): (phi_1, phi_2) if {
+            phi_1.remove_negation() == Some(phi_2) || phi_2.remove_negation() == Some(phi_1)
+        } => phi_2.clone(),
+
+        // (phi_1 -> phi_2) -> phi_2 => phi_1 v phi_2
+        (=> (=> phi_1 phi_2) phi_3): ((phi_1, phi_2), phi_3) if phi_2 == phi_3 => {
+            build_term!(pool, (or {phi_1.clone()} {phi_2.clone()}))
+        },
+    })
+}
+
+/// If the term is an implication with a constant antecedent or consequent, returns the
+/// `implies_simplify` case that applies to it.
+fn implies_constant_case(term: &Rc<Term>) -> Option<&'static str> {
+    let (phi_1, phi_2) = match_term!((=> phi_1 phi_2) = term)?;
+    Some(if phi_1.is_bool_false() {
+        "(=> false phi) => true"
+    } else if phi_2.is_bool_true() {
+        "(=> phi true) => true"
+    } else if phi_1.is_bool_true() {
+        "(=> true phi) => phi"
+    } else if phi_2.is_bool_false() {
+        "(=> phi false) => (not phi)"
+    } else {
+        return None;
     })
 }
 
 pub fn equiv_simplify(args: RuleArgs) -> RuleResult {
-    generic_simplify_rule(args.conclusion, args.pool, |term, pool| {
-        simplify!(term {
-            // ¬phi_1 = ¬phi_2 => phi_1 = phi_2
-            (= (not phi_1) (not phi_2)): (phi_1, phi_2) => {
-                build_term!(pool, (= {phi_1.clone()} {phi_2.clone()}))
-            },
+    generic_simplify_rule(args.conclusion, args.pool, simplify_equiv_term)
+}
 
-            // phi = phi => true
-            (= phi_1 phi_2): (phi_1, phi_2) if phi_1 == phi_2 => pool.bool_true(),
+fn simplify_equiv_term(term: &Term, pool: &mut dyn TermPool) -> Option<Rc<Term>> {
+    let result = simplify!(term {
+        // ¬phi_1 = ¬phi_2 => phi_1 = phi_2
+        (= (not phi_1) (not phi_2)): (phi_1, phi_2) => {
+            build_term!(pool, (= {phi_1.clone()} {phi_2.clone()}))
+        },
 
-            // phi = ¬phi => false
-            (= phi_1 (not phi_2)): (phi_1, phi_2) if phi_1 == phi_2 => pool.bool_false(),
+        // phi = phi => true
+        (= phi_1 phi_2): (phi_1, phi_2) if phi_1 == phi_2 => pool.bool_true(),
 
-            // ¬phi = phi => false
-            (= (not phi_1) phi_2): (phi_1, phi_2) if phi_1 == phi_2 => pool.bool_false(),
+        // (a = b) = (b = a) => true
+        (= (= a b) (= c d)): ((a, b), (c, d)) if a == d && b == c => pool.bool_true(),
 
-            // true = phi => phi
-            (= true phi_1): (_, phi_1) => phi_1.clone(),
+        // phi = ¬phi => false
+        (= phi_1 (not phi_2)): (phi_1, phi_2) if phi_1 == phi_2 => pool.bool_false(),
 
-            // phi = true => phi
-            (= phi_1 true): (phi_1, _) => phi_1.clone(),
+        // ¬phi = phi => false
+        (= (not phi_1) phi_2): (phi_1, phi_2) if phi_1 == phi_2 => pool.bool_false(),
 
-            // false = phi => ¬phi
-            (= false phi_1): (_, phi_1) => build_term!(pool, (not {phi_1.clone()})),
+        // true = phi => phi
+        (= true phi_1): (_, phi_1) => phi_1.clone(),
 
-            // phi = false => ¬phi
-            (= phi_1 false): (phi_1, _) => build_term!(pool, (not {phi_1.clone()})),
-        })
-    })
+        // phi = true => phi
+        (= phi_1 true): (phi_1, _) => phi_1.clone(),
+
+        // false = phi => ¬phi
+        (= false phi_1): (_, phi_1) => build_term!(pool, (not {phi_1.clone()})),
+
+        // phi = false => ¬phi
+        (= phi_1 false): (phi_1, _) => build_term!(pool, (not {phi_1.clone()})),
+    });
+    if result.is_some() {
+        return result;
+    }
+
+    // If no pattern applies to the term itself, we try to simplify the equivalences nested in it
+    let (phi_1, phi_2) = match_term!((= phi_1 phi_2) = term)?;
+    let mut simplify_nested = |phi: &Rc<Term>| match phi.as_ref() {
+        Term::Op(Operator::Equals, _) => simplify_equiv_term(phi, pool),
+        _ => None,
+    };
+    let (new_phi_1, new_phi_2) = (simplify_nested(phi_1), simplify_nested(phi_2));
+    if new_phi_1.is_none() && new_phi_2.is_none() {
+        return None;
+    }
+    let new_phi_1 = new_phi_1.unwrap_or_else(|| phi_1.clone());
+    let new_phi_2 = new_phi_2.unwrap_or_else(|| phi_2.clone());
+    Some(build_term!(pool, (= {new_phi_1} {new_phi_2})))
 }
 
+/// The patterns tried by `bool_simplify`, in order. This is only used to report failures.
+const BOOL_SIMPLIFY_PATTERNS: &[&str] = &[
+    "(not (=> phi_1 phi_2))",
+    "(not (or phi_1 phi_2))",
+    "(not (and phi_1 phi_2))",
+    "(=> phi_1 (=> phi_2 phi_3))",
+    "(=> (=> phi_1 phi_2) phi_2)",
+    "(and phi_1 (=> phi_1 phi_2))",
+    "(and (=> phi_1 phi_2) phi_1)",
+];
+
 pub fn bool_simplify(args: RuleArgs) -> RuleResult {
-    generic_simplify_rule(args.conclusion, args.pool, |term, pool| {
-        simplify!(term {
-            // ¬(phi_1 -> phi_2) => (phi_1 ^ ¬phi_2)
-            (not (=> phi_1 phi_2)): (phi_1, phi_2) => {
-                build_term!(pool, (and {phi_1.clone()} (not {phi_2.clone()})))
-            },
-
-            // ¬(phi_1 v phi_2) => (¬phi_1 ^ ¬phi_2)
-            (not (or phi_1 phi_2)): (phi_1, phi_2) => {
-                build_term!(pool, (and (not {phi_1.clone()}) (not {phi_2.clone()})))
-            },
-
-            // ¬(phi_1 ^ phi_2) => (¬phi_1 v ¬phi_2)
-            (not (and phi_1 phi_2)): (phi_1, phi_2) => {
-                build_term!(pool, (or (not {phi_1.clone()}) (not {phi_2.clone()})))
-            },
-
-            // (phi_1 -> (phi_2 -> phi_3)) => ((phi_1 ^ phi_2) -> phi_3)
-            (=> phi_1 (=> phi_2 phi_3)): (phi_1, (phi_2, phi_3)) => {
-                build_term!(pool, (=> (and {phi_1.clone()} {phi_2.clone()}) {phi_3.clone()}))
-            },
-
-            // ((phi_1 -> phi_2) -> phi_2) => (phi_1 v phi_2)
-            (=> (=> phi_1 phi_2) phi_3): ((phi_1, phi_2), phi_3) if phi_2 == phi_3 => {
-                build_term!(pool, (or {phi_1.clone()} {phi_2.clone()}))
-            },
-
-            // (phi_1 ^ (phi_1 -> phi_2)) => (phi_1 ^ phi_2)
-            (and phi_1 (=> phi_2 phi_3)): (phi_1, (phi_2, phi_3)) if phi_1 == phi_2 => {
-                build_term!(pool, (and {phi_1.clone()} {phi_3.clone()}))
-            },
-
-            // ((phi_1 -> phi_2) ^ phi_1) => (phi_1 ^ phi_2)
-            (and (=> phi_1 phi_2) phi_3): ((phi_1, phi_2), phi_3) if phi_1 == phi_3 => {
-                build_term!(pool, (and {phi_1.clone()} {phi_2.clone()}))
-            },
-        })
+    let result = generic_simplify_rule(args.conclusion, args.pool, simplify_bool_term);
+
+    // If not even the first simplification step could be applied, we report that no pattern
+    // matched the original term, instead of the generic simplification error
+    if let Err(CheckerError::SimplificationFailed { original, result: simplified, .. }) = &result {
+        if let (true, Term::Op(operator, _)) = (original == simplified, original.as_ref()) {
+            return Err(CheckerError::NoSimplificationPatternApplies {
+                rule: "bool_simplify",
+                operator: *operator,
+                patterns: BOOL_SIMPLIFY_PATTERNS,
+            });
+        }
+    }
+    result
+}
+
+fn simplify_bool_term(term: &Term, pool: &mut dyn TermPool) -> Option<Rc<Term>> {
+    simplify!(term {
+        // ¬(phi_1 -> phi_2) => (phi_1 ^ ¬phi_2)
+        (not (=> phi_1 phi_2)): (phi_1, phi_2) => {
+            build_term!(pool, (and {phi_1.clone()} (not {phi_2.clone()})))
+        },
+
+        // ¬(phi_1 v phi_2) => (¬phi_1 ^ ¬phi_2)
+        (not (or phi_1 phi_2)): (phi_1, phi_2) => {
+            build_term!(pool, (and (not {phi_1.clone()}) (not {phi_2.clone()})))
+        },
+
+        // ¬(phi_1 ^ phi_2) => (¬phi_1 v ¬phi_2)
+        (not (and phi_1 phi_2)): (phi_1, phi_2) => {
+            build_term!(pool, (or (not {phi_1.clone()}) (not {phi_2.clone()})))
+        },
+
+        // (phi_1 -> (phi_2 -> phi_3)) => ((phi_1 ^ phi_2) -> phi_3)
+        (=> phi_1 (=> phi_2 phi_3)): (phi_1, (phi_2, phi_3)) => {
+            build_term!(pool, (=> (and {phi_1.clone()} {phi_2.clone()}) {phi_3.clone()}))
+        },
+
+        // ((phi_1 -> phi_2) -> phi_2) => (phi_1 v phi_2)
+        (=> (=> phi_1 phi_2) phi_3): ((phi_1, phi_2), phi_3) if phi_2 == phi_3 => {
+            build_term!(pool, (or {phi_1.clone()} {phi_2.clone()}))
+        },
+
+        // (phi_1 ^ (phi_1 -> phi_2)) => (phi_1 ^ phi_2)
+        (and phi_1 (=> phi_2 phi_3)): (phi_1, (phi_2, phi_3)) if phi_1 == phi_2 => {
+            build_term!(pool, (and {phi_1.clone()} {phi_3.clone()}))
+        },
+
+        // ((phi_1 -> phi_2) ^ phi_1) => (phi_1 ^ phi_2)
+        (and (=> phi_1 phi_2) phi_3): ((phi_1, phi_2), phi_3) if phi_1 == phi_3 => {
+            build_term!(pool, (and {phi_1.clone()} {phi_2.clone()}))
+        },
     })
 }
 
-pub fn qnt_simplify(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
+pub fn qnt_simplify(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
     let (left, right) = match_term_err!((= l r) = &conclusion[0])?;
-    let (_, _, inner) = left.as_quant_err()?;
-    rassert!(
-        inner.is_bool_false() || inner.is_bool_true(),
-        CheckerError::ExpectedAnyBoolConstant(inner.clone())
-    );
+    let (_, bindings, inner) = left.as_quant_err()?;
+
+    // Besides quantifiers over a boolean constant, we also accept vacuous quantifiers, that is,
+    // quantifiers in which none of the bound variables occur in the body. This includes
+    // quantifiers with an empty binding list. Unlike in `qnt_rm_unused`, the whole quantifier must
+    // be removed
+    if !inner.is_bool_false() && !inner.is_bool_true() {
+        let free_vars = pool.free_vars(inner);
+        if let Some((name, _)) = bindings
+            .iter()
+            .find(|&var| free_vars.contains(&pool.add(var.clone().into())))
+        {
+            return Err(QuantifierError::BindingIsUsed(name.clone()).into());
+        }
+    }
     assert_eq(right, inner)?;
     Ok(())
 }
@@ -423,6 +650,12 @@ pub fn div_simplify(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
         None => (match_term_err!((/ n d) = left)?, false),
     };
 
+    // Division by zero is uninterpreted in SMT-LIB, so we must not fold any term of the form
+    // `(div t 0)` or `(/ t 0.0)`, not even `(div 0 0)`
+    if denom.as_signed_number().is_some_and(|d| d.is_zero()) {
+        return Err(CheckerError::DivOrModByZero);
+    }
+
     if numer == denom {
         rassert!(
             right.as_signed_number_err()? == 1,
@@ -433,11 +666,11 @@ pub fn div_simplify(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
         assert_eq(right, numer)
     } else {
         let denom = denom.as_signed_number_err()?;
-        if denom.is_zero() {
-            return Err(CheckerError::DivOrModByZero);
-        }
         let numer = numer.as_signed_number_err()?;
         let expected = if is_int_div {
+            // SMT-LIB defines integer division such that the remainder is always non-negative,
+            // which corresponds to Euclidean division. Note that this is different from rounding
+            // towards negative infinity when the divisor is negative, e.g. `(div 7 (- 2))` is -3
             assert!(numer.is_integer() && denom.is_integer()); // This is guaranteed by the Alethe typing rules
             let [numer, denom] = [numer, denom].map(|n| n.into_numer_denom().0);
             Rational::from(numer.div_rem_euc(denom).0)
@@ -453,12 +686,15 @@ pub fn div_simplify(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
 }
 
 /// Used for both the `sum_simplify` and `prod_simplify` rules, depending on `rule_kind`.
-/// `rule_kind` has to be either `Operator::Add` or `Operator::Mult`.
+/// `rule_kind` has to be either `Operator::Add` or `Operator::Mult`. If `sort_args` is `true`, the
+/// non-constant arguments of `u` may be in any order, since both they and the expected arguments
+/// are sorted according to the [`StructuralOrder`] before being compared.
 fn generic_sum_prod_simplify_rule(
     pool: &mut dyn TermPool,
     ts: &Rc<Term>,
     u: &Rc<Term>,
     rule_kind: Operator,
+    sort_args: bool,
 ) -> RuleResult {
     let identity_value = match rule_kind {
         Operator::Add => Rational::new(),
@@ -538,8 +774,18 @@ fn generic_sum_prod_simplify_rule(
         return Ok(());
     }
 
+    let args_match = if sort_args {
+        let mut got = u_args.to_vec();
+        let mut expected: Vec<_> = result.into_iter().cloned().collect();
+        StructuralOrder.sort(&mut got);
+        StructuralOrder.sort(&mut expected);
+        got == expected
+    } else {
+        u_args.iter().eq(result)
+    };
+
     // Finally, we verify that the constant and the remaining arguments are what we expect
-    rassert!(u_constant == constant_total && u_args.iter().eq(result), {
+    rassert!(u_constant == constant_total && args_match, {
         let expected = {
             let mut expected_args = vec![pool.add(Term::new_real(constant_total))];
             expected_args.extend(u_args.iter().cloned());
@@ -551,15 +797,29 @@ fn generic_sum_prod_simplify_rule(
 }
 
 pub fn prod_simplify(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
+    generic_prod_simplify(conclusion, pool, true)
+}
+
+/// Similar to `prod_simplify`, but requires the non-constant factors in the result to be in the
+/// same order as in the original product.
+pub fn strict_prod_simplify(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
+    generic_prod_simplify(conclusion, pool, false)
+}
+
+fn generic_prod_simplify(
+    conclusion: &[Rc<Term>],
+    pool: &mut dyn TermPool,
+    sort_args: bool,
+) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
     let (first, second) = match_term_err!((= first second) = &conclusion[0])?;
 
     // Since the equality may be flipped, we need to test both possibilities. We first test the
     // "reversed" one to make the error messages more reasonable in case both fail
-    if generic_sum_prod_simplify_rule(pool, second, first, Operator::Mult).is_ok() {
+    if generic_sum_prod_simplify_rule(pool, second, first, Operator::Mult, sort_args).is_ok() {
         return Ok(());
     }
-    generic_sum_prod_simplify_rule(pool, first, second, Operator::Mult)
+    generic_sum_prod_simplify_rule(pool, first, second, Operator::Mult, sort_args)
 }
 
 pub fn minus_simplify(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
@@ -630,52 +890,83 @@ pub fn sum_simplify(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
 
     // Since the equality may be flipped, we need to test both possibilities. We first test the
     // "reversed" one to make the error messages more reasonable in case both fail
-    if generic_sum_prod_simplify_rule(pool, second, first, Operator::Add).is_ok() {
+    if generic_sum_prod_simplify_rule(pool, second, first, Operator::Add, false).is_ok() {
         return Ok(());
     }
-    generic_sum_prod_simplify_rule(pool, first, second, Operator::Add)
+    generic_sum_prod_simplify_rule(pool, first, second, Operator::Add, false)
 }
 
 pub fn comp_simplify(args: RuleArgs) -> RuleResult {
-    generic_simplify_rule(args.conclusion, args.pool, |term, pool| {
-        simplify!(term {
-            (< t_1 t_2): (t_1, t_2) => {
-                if let (Some(t_1), Some(t_2)) =
-                    (t_1.as_fraction(), t_2.as_fraction())
-                {
-                    // t_1 < t_2 => phi, where t_1 and t_2 are numerical constants
-                    pool.bool_constant(t_1 < t_2)
-                } else if t_1 == t_2 {
-                    // t < t => false
-                    pool.bool_false()
-                } else {
-                    // t_1 < t_2 => ¬(t_2 <= t_1)
-                    build_term!(pool, (not (<= {t_2.clone()} {t_1.clone()})))
-                }
-            },
-            (<= t_1 t_2): (t_1, t_2) => {
-                if let (Some(t_1), Some(t_2)) =
-                    (t_1.as_fraction(), t_2.as_fraction())
-                {
-                    // t_1 <= t_2 => phi, where t_1 and t_2 are numerical constants
-                    pool.bool_constant(t_1 <= t_2)
-                } else if t_1 == t_2 {
-                    // t <= t => true
-                    pool.bool_true()
-                } else {
-                    return None
-                }
-            },
+    let result = generic_simplify_rule(args.conclusion, args.pool, simplify_comp_term);
+    if result.is_err()
+        && generic_simplify_rule(args.conclusion, args.pool, simplify_int_comp_term).is_ok()
+    {
+        return Ok(());
+    }
+    result
+}
+
+fn simplify_comp_term(term: &Term, pool: &mut dyn TermPool) -> Option<Rc<Term>> {
+    simplify!(term {
+        (< t_1 t_2): (t_1, t_2) => {
+            if let (Some(t_1), Some(t_2)) =
+                (t_1.as_fraction(), t_2.as_fraction())
+            {
+                // t_1 < t_2 => phi, where t_1 and t_2 are numerical constants
+                pool.bool_constant(t_1 < t_2)
+            } else if t_1 == t_2 {
+                // t < t => false
+                pool.bool_false()
+            } else {
+                // t_1 < t_2 => ¬(t_2 <= t_1)
+                build_term!(pool, (not (<= {t_2.clone()} {t_1.clone()})))
+            }
+        },
+        (<= t_1 t_2): (t_1, t_2) => {
+            if let (Some(t_1), Some(t_2)) =
+                (t_1.as_fraction(), t_2.as_fraction())
+            {
+                // t_1 <= t_2 => phi, where t_1 and t_2 are numerical constants
+                pool.bool_constant(t_1 <= t_2)
+            } else if t_1 == t_2 {
+                // t <= t => true
+                pool.bool_true()
+            } else {
+                return None
+            }
+        },
 
-            // t_1 >= t_2 => t_2 <= t_1
-            (>= t_1 t_2): (t_1, t_2) => build_term!(pool, (<= {t_2.clone()} {t_1.clone()})),
+        // t_1 >= t_2 => t_2 <= t_1
+        (>= t_1 t_2): (t_1, t_2) => build_term!(pool, (<= {t_2.clone()} {t_1.clone()})),
 
-            // t_1 > t_2 => ¬(t_1 <= t_2)
-            (> t_1 t_2): (t_1, t_2) => build_term!(pool, (not (<= {t_1.clone()} {t_2.clone()}))),
-        })
+        // t_1 > t_2 => ¬(t_1 <= t_2)
+        (> t_1 t_2): (t_1, t_2) => build_term!(pool, (not (<= {t_1.clone()} {t_2.clone()}))),
     })
 }
 
+/// Similar to `simplify_comp_term`, but also applies simplifications that are only valid when the
+/// operands are integers. Since these simplifications conflict with the general ones, this is only
+/// tried if checking the step with `simplify_comp_term` fails.
+fn simplify_int_comp_term(term: &Term, pool: &mut dyn TermPool) -> Option<Rc<Term>> {
+    let is_one = |t: &Rc<Term>| t.as_integer().is_some_and(|i| i == 1);
+    let tightened = simplify!(term {
+        // t_1 < t_2 + 1 => t_1 <= t_2, where t_1 and t_2 are integers
+        (< t_1 (+ t_2 one)): (t_1, (t_2, one)) if is_one(one) => (t_1, t_2),
+        (< t_1 (+ one t_2)): (t_1, (one, t_2)) if is_one(one) => (t_1, t_2),
+    });
+    // With Int/Real subtyping, only one of the operands may be an integer, so both must be checked
+    let is_int = |pool: &mut dyn TermPool, t: &Rc<Term>| pool.sort(t).as_sort() == Some(&Sort::Int);
+    match tightened {
+        Some((t_1, t_2)) if is_int(pool, t_1) && is_int(pool, t_2) => {
+            Some(build_term!(pool, (<= {t_1.clone()} {t_2.clone()})))
+        }
+        _ => simplify_comp_term(term, pool),
+    }
+}
+
+/// Flattens nested applications of `and` and `or`. The operands are kept in the order in which they
+/// appear in the original term, which is the canonical order expected in the conclusion of an
+/// `ac_simp` step. Since both operators are idempotent, consecutive duplicate operands are removed.
 fn apply_ac_simp(
     pool: &mut dyn TermPool,
     cache: &mut IndexMap<Rc<Term>, Rc<Term>>,
@@ -736,3 +1027,68 @@ pub fn ac_simp(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
         &apply_ac_simp(pool, &mut IndexMap::new(), original),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn simplify() {
+        use crate::parser::tests::*;
+
+        let definitions = "
+            (declare-fun p () Bool)
+            (declare-fun q () Bool)
+            (declare-fun a () Real)
+            (declare-fun b () Real)
+        ";
+        let cases = [
+            ("(and true (or p p))", "p"),
+            ("(not (not (or false p)))", "p"),
+            ("(=> (and p (not p)) q)", "true"),
+            ("(ite (= a a) (> a b) q)", "(not (<= a b))"),
+            ("(= (< 1.0 2.0) p)", "p"),
+            (
+                "(forall ((x Real)) (and true (= x x)))",
+                "(forall ((x Real)) (and true (= x x)))",
+            ),
+            ("(or p q)", "(or p q)"),
+        ];
+        for (term, expected) in cases {
+            let mut pool = crate::ast::pool::PrimitivePool::new();
+            let [term, expected] = parse_terms(&mut pool, definitions, [term, expected]);
+            assert_eq!(expected, super::simplify(&term, &mut pool));
+        }
+    }
+
+    #[test]
+    fn comp_simplify_int_real_subtyping() {
+        use crate::{checker::*, parser};
+
+        // With Int/Real subtyping, a real can be compared against an integer constant, but the
+        // integer tightening still must not be applied to it
+        let problem = "
+            (declare-fun a () Int)
+            (declare-fun b () Int)
+            (declare-fun x () Real)
+            (declare-fun y () Real)
+        ";
+        let cases = [
+            ("(= (< a (+ b 1)) (<= a b))", true),
+            ("(= (< x (+ y 1)) (<= x y))", false),
+            ("(= (< x (+ 1 y)) (<= x y))", false),
+            ("(= (< x (+ y 1)) (not (<= (+ y 1) x)))", true),
+        ];
+        for (conclusion, expected) in cases {
+            let proof = format!(
+                "(step t1 (cl {conclusion}) :rule comp_simplify) (step t2 (cl) :rule hole)"
+            );
+            let config = parser::Config {
+                allow_int_real_subtyping: true,
+                ..parser::Config::new()
+            };
+            let (problem, proof, mut pool) =
+                parser::parse_instance(problem.as_bytes(), proof.as_bytes(), config).unwrap();
+            let result = ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof);
+            assert_eq!(expected, result.is_ok(), "{conclusion}: {result:?}");
+        }
+    }
+}
@@ -93,8 +93,8 @@ pub fn bind(
     let context = context.last().unwrap();
     let context = context.as_ref().unwrap();
 
-    let (xs, ys): (IndexSet<_>, IndexSet<_>) = {
-        let (mut xs, mut ys) = (IndexSet::new(), IndexSet::new());
+    let (xs, ys): (IndexMap<_, _>, IndexSet<_>) = {
+        let (mut xs, mut ys) = (IndexMap::new(), IndexSet::new());
         for arg in &context.args {
             match arg {
                 AnchorArg::Variable((name, _)) if !xs.is_empty() => {
@@ -103,8 +103,8 @@ pub fn bind(
                 AnchorArg::Variable(var) => {
                     ys.insert(pool.add(var.clone().into()));
                 }
-                AnchorArg::Assign(var, _) => {
-                    xs.insert(pool.add(var.clone().into()));
+                AnchorArg::Assign(var, value) => {
+                    xs.insert(pool.add(var.clone().into()), value.clone());
                 }
             }
         }
@@ -118,11 +118,11 @@ pub fn bind(
 
     let (l_bindings, r_bindings): (IndexSet<_>, IndexSet<_>) = (
         l_bindings.difference(&r_bindings).cloned().collect(),
-        r_bindings.difference(&r_bindings).cloned().collect(),
+        r_bindings.difference(&l_bindings).cloned().collect(),
     );
 
     // `l_bindings` should be a subset of `xs` and `r_bindigns` should be a subset of `ys`
-    if let Some(x) = l_bindings.iter().find(|&x| !xs.contains(x)) {
+    if let Some(x) = l_bindings.iter().find(|&x| !xs.contains_key(x)) {
         let x = x.as_var().unwrap().to_owned();
         return Err(SubproofError::BindingIsNotInContext(x).into());
     }
@@ -130,6 +130,19 @@ pub fn bind(
         let y = y.as_var().unwrap().to_owned();
         return Err(SubproofError::BindingIsNotInContext(y).into());
     }
+
+    // Each variable bound on the left side is renamed by the anchor, so the variable it is renamed
+    // to must be bound on the right side
+    for x in &l_bindings {
+        let value = &xs[x];
+        if !r_bindings.contains(value) {
+            return Err(SubproofError::BindRenamedVarNotBound {
+                var: x.as_var().unwrap().to_owned(),
+                value: value.clone(),
+            }
+            .into());
+        }
+    }
     Ok(())
 }
 
@@ -185,18 +198,19 @@ pub fn r#let(
             let s = mappings
                 .get(&x_term)
                 .ok_or_else(|| SubproofError::BindingIsNotInContext(x.clone()))?;
-            Ok((s, t))
+            Ok((x, s, t))
         })
         .collect::<Result<_, CheckerError>>()?;
-    pairs.retain(|(s, t)| s != t); // The pairs where s == t don't need a premise to justify them
+    pairs.retain(|(_, s, t)| s != t); // The pairs where s == t don't need a premise to justify them
 
     assert_num_premises(premises, pairs.len())?;
 
-    for (premise, (s, t)) in premises.iter().zip(pairs) {
+    for (premise, (x, s, t)) in premises.iter().zip(pairs) {
         let (a, b) = match_term_err!((= a b) = get_premise_term(premise)?)?;
         rassert!(
             (a, b) == (s, t) || (a, b) == (t, s),
             SubproofError::PremiseDoesntJustifyLet {
+                binding: x.clone(),
                 substitution: (s.clone(), t.clone()),
                 premise: (a.clone(), b.clone()),
             }
@@ -308,6 +322,15 @@ pub fn onepoint(
 
     // For each substitution (:= x t) in the context, the equality (= x t) must appear in phi
     if let Some((k, v)) = mappings.find(|&(k, v)| !points.contains(&(k.clone(), v.clone()))) {
+        // If the variable is one-pointed in phi, but with a different witness term, we report the
+        // witness that was found, since that is most likely the source of the error
+        if let Some((_, witness)) = points.iter().find(|(x, _)| x == k) {
+            return Err(SubproofError::OnepointMismatch {
+                var: k.clone(),
+                witness: witness.clone(),
+            }
+            .into());
+        }
         return Err(SubproofError::NoPointForSubstitution(k.clone(), v.clone()).into());
     }
 
@@ -356,6 +379,13 @@ pub fn onepoint(
     Ok(())
 }
 
+/// Checks the `sko_ex` and `sko_forall` rules.
+///
+/// In Alethe, Skolem terms are not fresh function symbols, but `choice` terms that are completely
+/// determined by the quantified formula. Because of that, these rules don't need to check that the
+/// Skolem term is fresh. Instead, we check that each Skolem term is exactly the expected `choice`
+/// term, which means an arbitrary term (for example, one that already appears in the formula) can
+/// never be used as a Skolem term.
 fn generic_skolemization_rule(
     rule_type: Binder,
     RuleArgs {
@@ -1,8 +1,11 @@
 use super::{
-    assert_clause_len, assert_eq, assert_num_args, assert_num_premises, assert_polyeq,
-    get_premise_term, CheckerError, RuleArgs, RuleResult,
+    assert_clause_len, assert_eq, assert_is_expected, assert_num_args, assert_num_premises,
+    assert_polyeq_mod_ite, get_premise_term, CheckerError, EqualityError, Premise, RuleArgs,
+    RuleResult,
 };
 use crate::{ast::*, checker::rules::assert_operation_len};
+use indexmap::IndexSet;
+use std::time::Duration;
 
 pub fn r#true(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
@@ -37,12 +40,7 @@ pub fn and_pos(RuleArgs { conclusion, args, .. }: RuleArgs) -> RuleResult {
 
     let and_contents = match_term_err!((not (and ...)) = &conclusion[0])?;
     let i = args[0].as_usize_err()?;
-
-    if i >= and_contents.len() {
-        return Err(CheckerError::NoIthChildInTerm(i, conclusion[0].clone()));
-    }
-
-    assert_eq(&conclusion[1], &and_contents[i])
+    assert_literal_index(and_contents, i, &conclusion[1])
 }
 
 pub fn and_neg(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
@@ -64,8 +62,8 @@ pub fn or_pos(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
     let or_contents = match_term_err!((not (or ...)) = &conclusion[0])?;
     assert_operation_len(Operator::Or, or_contents, conclusion.len() - 1)?;
 
-    for (t, u) in or_contents.iter().zip(&conclusion[1..]) {
-        assert_eq(t, u)?;
+    for (i, u) in conclusion[1..].iter().enumerate() {
+        assert_literal_index(or_contents, i, u)?;
     }
     Ok(())
 }
@@ -77,12 +75,25 @@ pub fn or_neg(RuleArgs { conclusion, args, .. }: RuleArgs) -> RuleResult {
     let or_contents = match_term_err!((or ...) = &conclusion[0])?;
     let other = conclusion[1].remove_negation_err()?;
     let i = args[0].as_usize_err()?;
+    assert_literal_index(or_contents, i, other)
+}
 
-    if i >= or_contents.len() {
-        return Err(CheckerError::NoIthChildInTerm(i, conclusion[0].clone()));
+/// Checks that `literal` is the argument at index `asked` of an `and` or `or` term. If it isn't,
+/// and the index is out of range or the literal is a different argument of the term, this returns
+/// a `CheckerError::WrongLiteralIndex` error.
+fn assert_literal_index(contents: &[Rc<Term>], asked: usize, literal: &Rc<Term>) -> RuleResult {
+    if contents.get(asked) == Some(literal) {
+        return Ok(());
+    }
+    let found = contents.iter().position(|t| t == literal);
+    match contents.get(asked) {
+        Some(expected) if found.is_none() => assert_eq(literal, expected),
+        _ => Err(CheckerError::WrongLiteralIndex {
+            asked,
+            available: contents.len(),
+            found,
+        }),
     }
-
-    assert_eq(other, &or_contents[i])
 }
 
 pub fn xor_pos1(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
@@ -189,39 +200,96 @@ pub fn ite_neg2(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
 }
 
 pub fn equiv1(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
-    assert_num_premises(premises, 1)?;
-    assert_clause_len(conclusion, 2)?;
-    let premise_term = get_premise_term(&premises[0])?;
-    let (phi_1, phi_2) = match_term_err!((= phi_1 phi_2) = premise_term)?;
-    assert_eq(phi_1, conclusion[0].remove_negation_err()?)?;
-    assert_eq(phi_2, &conclusion[1])
+    generic_equiv(conclusion, premises, (true, false), true)
+}
+
+pub fn strict_equiv1(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+    generic_equiv(conclusion, premises, (true, false), false)
 }
 
 pub fn equiv2(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
-    assert_num_premises(premises, 1)?;
-    assert_clause_len(conclusion, 2)?;
-    let premise_term = get_premise_term(&premises[0])?;
-    let (phi_1, phi_2) = match_term_err!((= phi_1 phi_2) = premise_term)?;
-    assert_eq(phi_1, &conclusion[0])?;
-    assert_eq(phi_2, conclusion[1].remove_negation_err()?)
+    generic_equiv(conclusion, premises, (false, true), true)
+}
+
+pub fn strict_equiv2(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+    generic_equiv(conclusion, premises, (false, true), false)
 }
 
 pub fn not_equiv1(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
-    assert_num_premises(premises, 1)?;
-    assert_clause_len(conclusion, 2)?;
-    let premise_term = get_premise_term(&premises[0])?;
-    let (phi_1, phi_2) = match_term_err!((not (= phi_1 phi_2)) = premise_term)?;
-    assert_eq(phi_1, &conclusion[0])?;
-    assert_eq(phi_2, &conclusion[1])
+    generic_not_equiv(conclusion, premises, (false, false), true)
+}
+
+pub fn strict_not_equiv1(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+    generic_not_equiv(conclusion, premises, (false, false), false)
 }
 
 pub fn not_equiv2(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+    generic_not_equiv(conclusion, premises, (true, true), true)
+}
+
+pub fn strict_not_equiv2(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+    generic_not_equiv(conclusion, premises, (true, true), false)
+}
+
+/// Checks a step of the `equiv1` or `equiv2` rules, whose premise is an equivalence `(= phi_1
+/// phi_2)`. The two literals of the conclusion must be `phi_1` and `phi_2`, each negated according to
+/// `negated`. If `allow_symmetry` is `true`, the premise may also be in the opposite orientation.
+fn generic_equiv(
+    conclusion: &[Rc<Term>],
+    premises: &[Premise],
+    negated: (bool, bool),
+    allow_symmetry: bool,
+) -> RuleResult {
     assert_num_premises(premises, 1)?;
     assert_clause_len(conclusion, 2)?;
     let premise_term = get_premise_term(&premises[0])?;
-    let (phi_1, phi_2) = match_term_err!((not (= phi_1 phi_2)) = premise_term)?;
-    assert_eq(phi_1, conclusion[0].remove_negation_err()?)?;
-    assert_eq(phi_2, conclusion[1].remove_negation_err()?)
+    let sides = match_term_err!((= phi_1 phi_2) = premise_term)?;
+    assert_equiv_sides(premise_term, sides, conclusion, negated, allow_symmetry)
+}
+
+/// Similar to [`generic_equiv`], but for the `not_equiv1` and `not_equiv2` rules, whose premise is a
+/// negated equivalence `(not (= phi_1 phi_2))`.
+fn generic_not_equiv(
+    conclusion: &[Rc<Term>],
+    premises: &[Premise],
+    negated: (bool, bool),
+    allow_symmetry: bool,
+) -> RuleResult {
+    assert_num_premises(premises, 1)?;
+    assert_clause_len(conclusion, 2)?;
+    let premise_term = get_premise_term(&premises[0])?;
+    let sides = match_term_err!((not (= phi_1 phi_2)) = premise_term)?;
+    assert_equiv_sides(premise_term, sides, conclusion, negated, allow_symmetry)
+}
+
+fn assert_equiv_sides(
+    premise: &Rc<Term>,
+    sides: (&Rc<Term>, &Rc<Term>),
+    conclusion: &[Rc<Term>],
+    negated: (bool, bool),
+    allow_symmetry: bool,
+) -> RuleResult {
+    let literal = |term: &Rc<Term>, negated: bool| -> Result<Rc<Term>, CheckerError> {
+        if negated {
+            Ok(term.remove_negation_err()?.clone())
+        } else {
+            Ok(term.clone())
+        }
+    };
+    let got = (
+        literal(&conclusion[0], negated.0)?,
+        literal(&conclusion[1], negated.1)?,
+    );
+    let matches = |(a, b): (&Rc<Term>, &Rc<Term>)| *a == got.0 && *b == got.1;
+    if matches(sides) || allow_symmetry && matches((sides.1, sides.0)) {
+        Ok(())
+    } else {
+        Err(CheckerError::EquivSidesMismatch {
+            premise: premise.clone(),
+            got,
+            allow_symmetry,
+        })
+    }
 }
 
 pub fn ite1(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
@@ -233,6 +301,21 @@ pub fn ite1(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
     assert_eq(phi_3, &conclusion[1])
 }
 
+/// Similar to `ite1`, but compares terms modulo trivial simplifications of `ite` subterms. See
+/// [`polyeq_mod_ite`].
+pub fn ite1_mod_ite(
+    RuleArgs {
+        conclusion, premises, polyeq_time, ..
+    }: RuleArgs,
+) -> RuleResult {
+    assert_num_premises(premises, 1)?;
+    assert_clause_len(conclusion, 2)?;
+    let premise_term = get_premise_term(&premises[0])?;
+    let (phi_1, _, phi_3) = match_term_err!((ite phi_1 phi_2 phi_3) = premise_term)?;
+    assert_polyeq_mod_ite(phi_1, &conclusion[0], polyeq_time)?;
+    assert_polyeq_mod_ite(phi_3, &conclusion[1], polyeq_time)
+}
+
 pub fn ite2(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
     assert_num_premises(premises, 1)?;
     assert_clause_len(conclusion, 2)?;
@@ -242,6 +325,21 @@ pub fn ite2(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
     assert_eq(phi_2, &conclusion[1])
 }
 
+/// Similar to `ite2`, but compares terms modulo trivial simplifications of `ite` subterms. See
+/// [`polyeq_mod_ite`].
+pub fn ite2_mod_ite(
+    RuleArgs {
+        conclusion, premises, polyeq_time, ..
+    }: RuleArgs,
+) -> RuleResult {
+    assert_num_premises(premises, 1)?;
+    assert_clause_len(conclusion, 2)?;
+    let premise_term = get_premise_term(&premises[0])?;
+    let (phi_1, phi_2, _) = match_term_err!((ite phi_1 phi_2 phi_3) = premise_term)?;
+    assert_polyeq_mod_ite(phi_1, conclusion[0].remove_negation_err()?, polyeq_time)?;
+    assert_polyeq_mod_ite(phi_2, &conclusion[1], polyeq_time)
+}
+
 pub fn not_ite1(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
     assert_num_premises(premises, 1)?;
     assert_clause_len(conclusion, 2)?;
@@ -261,10 +359,55 @@ pub fn not_ite2(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
 }
 
 pub fn ite_intro(RuleArgs { conclusion, polyeq_time, .. }: RuleArgs) -> RuleResult {
+    generic_ite_intro(conclusion, polyeq_time, polyeq, false)
+}
+
+/// Similar to `ite_intro`, but compares terms modulo trivial simplifications of `ite` subterms. See
+/// [`polyeq_mod_ite`].
+pub fn ite_intro_mod_ite(RuleArgs { conclusion, polyeq_time, .. }: RuleArgs) -> RuleResult {
+    generic_ite_intro(conclusion, polyeq_time, polyeq_mod_ite, false)
+}
+
+/// Similar to `ite_intro`, but requires that every `ite` subterm of the root term is introduced.
+pub fn strict_ite_intro(RuleArgs { conclusion, polyeq_time, .. }: RuleArgs) -> RuleResult {
+    generic_ite_intro(conclusion, polyeq_time, polyeq, true)
+}
+
+/// Collects the `ite` subterms of a term, without descending into binder or `let` terms, since
+/// `ite_intro` can't introduce `ite` terms that contain bound variables.
+fn collect_ite_subterms(term: &Rc<Term>, acc: &mut IndexSet<Rc<Term>>) {
+    if acc.contains(term) {
+        return;
+    }
+    match term.as_ref() {
+        Term::App(f, args) => {
+            collect_ite_subterms(f, acc);
+            args.iter().for_each(|a| collect_ite_subterms(a, acc));
+        }
+        Term::Op(op, args) => {
+            args.iter().for_each(|a| collect_ite_subterms(a, acc));
+            if *op == Operator::Ite {
+                acc.insert(term.clone());
+            }
+        }
+        Term::ParamOp { args, .. } => args.iter().for_each(|a| collect_ite_subterms(a, acc)),
+        _ => (),
+    }
+}
+
+fn generic_ite_intro(
+    conclusion: &[Rc<Term>],
+    polyeq_time: &mut Duration,
+    polyeq: fn(&Rc<Term>, &Rc<Term>, &mut Duration) -> bool,
+    strict: bool,
+) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
 
     let (root_term, right_side) = match_term_err!((= t u) = &conclusion[0])?;
 
+    let mut ite_subterms = IndexSet::new();
+    collect_ite_subterms(root_term, &mut ite_subterms);
+
     // In some cases, no `ite` subterm is extracted from `t` (even if `t` has `ite` subterms), so
     // the conjunction in the right side of the equality has only one term: `t` itself, modulo
     // reordering of equalities. One example where this happens is the test file
@@ -281,14 +424,23 @@ pub fn ite_intro(RuleArgs { conclusion, polyeq_time, .. }: RuleArgs) -> RuleResu
     // For cases like this, we first check if `t` equals the right side term modulo reordering of
     // equalities. If not, we unwrap the conjunction and continue checking the rule normally.
     if polyeq(root_term, right_side, polyeq_time) {
+        if strict {
+            if let Some(missing) = ite_subterms.first() {
+                return Err(CheckerError::IteIntroMissing(missing.clone()));
+            }
+        }
         return Ok(());
     }
     let us = match_term_err!((and ...) = right_side)?;
 
     // `us` must be a conjunction where the first term is the root term
-    assert_polyeq(&us[0], root_term, polyeq_time)?;
+    if !polyeq(&us[0], root_term, polyeq_time) {
+        return Err(EqualityError::ExpectedEqual(us[0].clone(), root_term.clone()).into());
+    }
 
-    // The remaining terms in `us` should be of the correct form
+    // The remaining terms in `us` should be of the correct form, and each one must introduce a
+    // different `ite` subterm of the root term
+    let mut introduced: Vec<&Rc<Term>> = Vec::with_capacity(us.len() - 1);
     for u_i in &us[1..] {
         let (cond, (a, b), (c, d)) = match_term_err!((ite cond (= a b) (= c d)) = u_i)?;
 
@@ -305,53 +457,100 @@ pub fn ite_intro(RuleArgs { conclusion, polyeq_time, .. }: RuleArgs) -> RuleResu
         };
         // Since the (= r_1 s_1) and (= r_2 s_2) equalities may be flipped, we have to check all
         // four possibilities: neither are flipped, either one is flipped, or both are flipped
-        let is_valid = is_valid(a, b, c, d)
-            || is_valid(b, a, c, d)
-            || is_valid(a, b, d, c)
-            || is_valid(b, a, d, c);
-
-        if !is_valid {
+        let ite_term = if is_valid(a, b, c, d) || is_valid(a, b, d, c) {
+            b
+        } else if is_valid(b, a, c, d) || is_valid(b, a, d, c) {
+            a
+        } else {
             return Err(CheckerError::IsNotValidIteIntro(u_i.clone()));
+        };
+
+        if !ite_subterms
+            .iter()
+            .any(|t| polyeq(t, ite_term, polyeq_time))
+        {
+            return Err(CheckerError::IteIntroNotSubterm(
+                ite_term.clone(),
+                root_term.clone(),
+            ));
+        }
+        if introduced.iter().any(|t| polyeq(t, ite_term, polyeq_time)) {
+            return Err(CheckerError::IteIntroDuplicate(ite_term.clone()));
+        }
+        introduced.push(ite_term);
+    }
+
+    if strict {
+        let missing = ite_subterms
+            .iter()
+            .find(|t| !introduced.iter().any(|i| polyeq(t, i, polyeq_time)));
+        if let Some(missing) = missing {
+            return Err(CheckerError::IteIntroMissing(missing.clone()));
         }
     }
     Ok(())
 }
 
-pub fn connective_def(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
+/// A function that builds the definition of an application of a connective, or returns `None` if
+/// the term doesn't have the expected form, for example, if it has the wrong number of arguments.
+type ConnectiveDefinition = fn(&mut dyn TermPool, &Rc<Term>) -> Option<Rc<Term>>;
+
+/// The connectives that can be expanded by the `connective_def` rule, together with their
+/// definitions.
+const CONNECTIVE_DEFINITIONS: &[(&str, ConnectiveDefinition)] = &[
+    // phi_1 xor phi_2 <-> (¬phi_1 ^ phi_2) v (phi_1 ^ ¬phi_2)
+    ("xor", |pool, term| {
+        let (a, b) = match_term!((xor a b) = term)?;
+        let (a, b) = (a.clone(), b.clone());
+        Some(build_term!(pool, (or (and (not {a.clone()}) {b.clone()}) (and {a} (not {b})))))
+    }),
+    // (phi_1 <-> phi_2) <-> (phi_1 -> phi_2) ^ (phi_2 -> phi_1)
+    ("=", |pool, term| {
+        let (a, b) = match_term!((= a b) = term)?;
+        let (a, b) = (a.clone(), b.clone());
+        Some(build_term!(pool, (and (=> {a.clone()} {b.clone()}) (=> {b} {a}))))
+    }),
+    // (phi_1 -> phi_2) <-> ¬phi_1 v phi_2
+    ("=>", |pool, term| {
+        let (a, b) = match_term!((=> a b) = term)?;
+        Some(build_term!(pool, (or (not {a.clone()}) {b.clone()})))
+    }),
+    // ite phi_1 phi_2 phi_3 <-> (phi_1 -> phi_2) ^ (¬phi_1 -> phi_3)
+    ("ite", |pool, term| {
+        let (a, b, c) = match_term!((ite a b c) = term)?;
+        let (a, b, c) = (a.clone(), b.clone(), c.clone());
+        Some(build_term!(pool, (and (=> {a.clone()} {b}) (=> (not {a}) {c}))))
+    }),
+    // (forall x. phi) <-> ¬(exists x. ¬phi)
+    ("forall", |pool, term| {
+        let (bindings, inner) = match_term!((forall ... f) = term)?;
+        let negated = build_term!(pool, (not {inner.clone()}));
+        let exists = pool.add(Term::Binder(Binder::Exists, bindings.clone(), negated));
+        Some(build_term!(pool, (not { exists })))
+    }),
+    // (exists x. phi) <-> ¬(forall x. ¬phi)
+    ("exists", |pool, term| {
+        let (bindings, inner) = match_term!((exists ... f) = term)?;
+        let negated = build_term!(pool, (not {inner.clone()}));
+        let forall = pool.add(Term::Binder(Binder::Forall, bindings.clone(), negated));
+        Some(build_term!(pool, (not { forall })))
+    }),
+];
+
+pub fn connective_def(RuleArgs { conclusion, pool, .. }: RuleArgs) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
 
     let (first, second) = match_term_err!((= f s) = &conclusion[0])?;
 
-    if let Some((phi_1, phi_2)) = match_term!((xor phi_1 phi_2) = first) {
-        // phi_1 xor phi_2 <-> (¬phi_1 ^ phi_2) v (phi_1 ^ ¬phi_2)
-        let ((a, b), (c, d)) = match_term_err!((or (and (not a) b) (and c (not d))) = second)?;
-        assert_eq(a, phi_1)?;
-        assert_eq(b, phi_2)?;
-        assert_eq(c, phi_1)?;
-        assert_eq(d, phi_2)
-    } else if let Some((phi_1, phi_2)) = match_term!((= phi_1 phi_2) = first) {
-        // (phi_1 <-> phi_2) <-> (phi_1 -> phi_2) ^ (phi_2 -> phi_1)
-        let ((a, b), (c, d)) = match_term_err!((and (=> a b) (=> c d)) = second)?;
-        assert_eq(a, phi_1)?;
-        assert_eq(b, phi_2)?;
-        assert_eq(c, phi_2)?;
-        assert_eq(d, phi_1)
-    } else if let Some((phi_1, phi_2, phi_3)) = match_term!((ite phi_1 phi_2 phi_3) = first) {
-        // ite phi_1 phi_2 phi_3 <-> (phi_1 -> phi_2) ^ (¬phi_1 -> phi_3)
-        let ((a, b), (c, d)) = match_term_err!((and (=> a b) (=> (not c) d)) = second)?;
-        assert_eq(a, phi_1)?;
-        assert_eq(b, phi_2)?;
-        assert_eq(c, phi_1)?;
-        assert_eq(d, phi_3)
-    } else if let Some((first_bindings, first_inner)) = match_term!((forall ... f) = first) {
-        let (second_bindings, second_inner) = match_term_err!((not (exists ... (not s))) = second)?;
-        assert_eq(first_inner, second_inner)?;
-        assert_eq(first_bindings, second_bindings)
-    } else if let Some((first_bindings, first_inner)) = match_term!((exists ... f) = first) {
-        let (second_bindings, second_inner) = match_term_err!((not (forall ... (not s))) = second)?;
-        assert_eq(first_inner, second_inner)?;
-        assert_eq(first_bindings, second_bindings)
-    } else {
-        Err(CheckerError::TermIsNotConnective(first.clone()))
-    }
+    let connective = match first.as_ref() {
+        Term::Op(op, _) => op.to_string(),
+        Term::Binder(binder, _, _) => binder.to_string(),
+        _ => return Err(CheckerError::TermIsNotConnective(first.clone())),
+    };
+    let expected = CONNECTIVE_DEFINITIONS
+        .iter()
+        .find(|(name, _)| *name == connective)
+        .and_then(|(_, definition)| definition(pool, first))
+        .ok_or_else(|| CheckerError::NoConnectiveDefinition(connective, first.clone()))?;
+    assert_is_expected(second, expected)
 }
@@ -1,11 +1,13 @@
-use super::{assert_clause_len, get_premise_term, CheckerError, RuleArgs, RuleResult};
+use super::{assert_clause_len, get_premise_term, CheckerError, Premise, RuleArgs, RuleResult};
 use crate::ast::*;
 
 /// Function to find a transitive chain given a conclusion equality and a series of premise
-/// equalities.
+/// equalities. If `allow_symmetry` is `false`, each premise equality must be used in the same
+/// orientation in which it appears in the chain.
 fn find_chain(
     conclusion: (&Rc<Term>, &Rc<Term>),
     premises: &mut [(&Rc<Term>, &Rc<Term>)],
+    allow_symmetry: bool,
 ) -> RuleResult {
     // When the conclusion is of the form (= a a), it is trivially valid
     if conclusion.0 == conclusion.1 {
@@ -21,7 +23,7 @@ fn find_chain(
         .find_map(|(i, &(t, u))| {
             if t == conclusion.0 {
                 Some((i, (t, u)))
-            } else if u == conclusion.0 {
+            } else if allow_symmetry && u == conclusion.0 {
                 Some((i, (u, t)))
             } else {
                 None
@@ -39,10 +41,10 @@ fn find_chain(
     // The new conclusion will be the terms in the conclusion and the found equality that didn't
     // match. For example, if the conclusion was (= a d) and we found in the premises (= a b), the
     // new conclusion will be (= b d)
-    find_chain((eq.1, conclusion.1), &mut premises[1..])
+    find_chain((eq.1, conclusion.1), &mut premises[1..], allow_symmetry)
 }
 
-pub fn eq_transitive(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
+fn generic_eq_transitive(conclusion: &[Rc<Term>], allow_symmetry: bool) -> RuleResult {
     assert_clause_len(conclusion, 3..)?;
 
     // The last term in the conclusion clause should be an equality, and it will be the conclusion
@@ -56,10 +58,27 @@ pub fn eq_transitive(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
         .map(|term| match_term_err!((not (= t u)) = term))
         .collect::<Result<_, _>>()?;
 
-    find_chain(chain_conclusion, &mut premises)
+    find_chain(chain_conclusion, &mut premises, allow_symmetry)
 }
 
-pub fn trans(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+/// Both the concluded equality and the premise disequalities may appear in either orientation. For
+/// example, `(cl (not (= a b)) (not (= c b)) (= c a))` is accepted. In strict mode,
+/// [`strict_eq_transitive`] is used instead.
+pub fn eq_transitive(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
+    generic_eq_transitive(conclusion, true)
+}
+
+/// Like [`eq_transitive`], but requires every equality to be in the orientation of the transitive
+/// chain, from the left side of the concluded equality to its right side.
+pub fn strict_eq_transitive(RuleArgs { conclusion, .. }: RuleArgs) -> RuleResult {
+    generic_eq_transitive(conclusion, false)
+}
+
+fn generic_trans(
+    conclusion: &[Rc<Term>],
+    premises: &[Premise],
+    allow_symmetry: bool,
+) -> RuleResult {
     assert_clause_len(conclusion, 1)?;
 
     let conclusion = match_term_err!((= t u) = &conclusion[0])?;
@@ -68,5 +87,13 @@ pub fn trans(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
         .map(|premise| match_term_err!((= t u) = get_premise_term(premise)?))
         .collect::<Result<_, _>>()?;
 
-    find_chain(conclusion, &mut premises)
+    find_chain(conclusion, &mut premises, allow_symmetry)
+}
+
+pub fn trans(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+    generic_trans(conclusion, premises, true)
+}
+
+pub fn strict_trans(RuleArgs { conclusion, premises, .. }: RuleArgs) -> RuleResult {
+    generic_trans(conclusion, premises, false)
 }
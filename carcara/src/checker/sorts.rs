@@ -0,0 +1,176 @@
+//! Sort checking of terms during proof checking. See [`sort_of`].
+
+use crate::{ast::*, parser::SortError};
+use indexmap::IndexMap;
+use std::collections::HashSet;
+
+/// Computes the sort of a term, checking along the way that the arguments of every operator and
+/// function application in it have the expected sorts. For example, the arguments of `and` must
+/// be of sort `Bool`, and the arguments of `+` must be of sort `Int` or `Real`.
+///
+/// Terms built by the parser are already sort checked, but terms in proofs constructed by other
+/// means, such as [`ProofBuilder`], are not. Operators whose argument sorts are not verified by
+/// this function are trusted, but their arguments are still checked recursively.
+pub fn sort_of(pool: &mut dyn TermPool, term: &Rc<Term>) -> Result<Sort, SortError> {
+    check_sorts(pool, term, &mut HashSet::new())?;
+    Ok(pool.sort(term).as_sort().unwrap().clone())
+}
+
+fn check_sorts(
+    pool: &mut dyn TermPool,
+    term: &Rc<Term>,
+    checked: &mut HashSet<Rc<Term>>,
+) -> Result<(), SortError> {
+    if checked.contains(term) {
+        return Ok(());
+    }
+
+    let mut sort_of_arg = |pool: &mut dyn TermPool, arg: &Rc<Term>| -> Result<Sort, SortError> {
+        check_sorts(pool, arg, checked)?;
+        Ok(pool.sort(arg).as_sort().unwrap().clone())
+    };
+    match term.as_ref() {
+        Term::Const(_) | Term::Var(..) | Term::Sort(_) => (),
+        Term::App(f, args) => {
+            let f_sort = sort_of_arg(pool, f)?;
+            let arg_sorts = args
+                .iter()
+                .map(|a| sort_of_arg(pool, a))
+                .collect::<Result<Vec<_>, _>>()?;
+            check_app_sorts(pool, f_sort, &arg_sorts)?;
+        }
+        Term::Op(op, args) => {
+            let sorts = args
+                .iter()
+                .map(|a| sort_of_arg(pool, a))
+                .collect::<Result<Vec<_>, _>>()?;
+            check_op_sorts(*op, &sorts)?;
+        }
+        Term::ParamOp { args, .. } => {
+            for a in args {
+                sort_of_arg(pool, a)?;
+            }
+        }
+        Term::Binder(binder, _, inner) => {
+            let inner_sort = sort_of_arg(pool, inner)?;
+            if matches!(binder, Binder::Forall | Binder::Exists) {
+                SortError::assert_eq(&Sort::Bool, &inner_sort)?;
+            }
+        }
+        Term::Let(bindings, inner) => {
+            for (_, value) in bindings {
+                sort_of_arg(pool, value)?;
+            }
+            sort_of_arg(pool, inner)?;
+        }
+    }
+    checked.insert(term.clone());
+    Ok(())
+}
+
+/// Checks that a function of sort `f_sort` can be applied to arguments of sorts `arg_sorts`. If
+/// the function is parametric, its parameters must match the argument sorts with a consistent
+/// instantiation of its sort variables.
+fn check_app_sorts(
+    pool: &mut dyn TermPool,
+    f_sort: Sort,
+    arg_sorts: &[Sort],
+) -> Result<(), SortError> {
+    let (params, is_parametric) = match &f_sort {
+        Sort::Function(params) => (params, false),
+        Sort::ParamSort(_, inner) => match inner.as_sort() {
+            Some(Sort::Function(params)) => (params, true),
+            _ => return Err(not_a_function(pool, f_sort.clone(), arg_sorts.len())),
+        },
+        _ => return Err(not_a_function(pool, f_sort, arg_sorts.len())),
+    };
+    let (result, params) = params.split_last().unwrap();
+    if params.len() != arg_sorts.len() {
+        let mut got: Vec<_> = arg_sorts
+            .iter()
+            .map(|s| pool.add(Term::Sort(s.clone())))
+            .collect();
+        got.push(result.clone());
+        return Err(SortError {
+            expected: vec![Sort::Function(
+                params.iter().chain([result]).cloned().collect(),
+            )],
+            got: Sort::Function(got),
+        });
+    }
+    let mut instantiation = IndexMap::new();
+    for (param, got) in params.iter().zip(arg_sorts) {
+        let param = param.as_sort().unwrap();
+        if !is_parametric {
+            SortError::assert_eq(param, got)?;
+        } else if !param.match_with(got, &mut instantiation) {
+            return Err(SortError {
+                expected: vec![param.clone()],
+                got: got.clone(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Builds the error for a term of sort `got` that is applied to `num_args` arguments, but is not a
+/// function. The expected sort is a function sort whose parameters and result are all unknown.
+fn not_a_function(pool: &mut dyn TermPool, got: Sort, num_args: usize) -> SortError {
+    let any = pool.add(Term::Sort(Sort::Atom("?".to_owned(), Vec::new())));
+    SortError {
+        expected: vec![Sort::Function(vec![any; num_args + 1])],
+        got,
+    }
+}
+
+fn check_op_sorts(op: Operator, sorts: &[Sort]) -> Result<(), SortError> {
+    let numeric = [Sort::Int, Sort::Real];
+    match op {
+        Operator::Not | Operator::Implies | Operator::And | Operator::Or | Operator::Xor => {
+            for s in sorts {
+                SortError::assert_eq(&Sort::Bool, s)?;
+            }
+        }
+        Operator::Equals | Operator::Distinct => {
+            SortError::assert_all_eq(&sorts.iter().collect::<Vec<_>>())?;
+        }
+        Operator::Ite => {
+            if let [condition, a, b] = sorts {
+                SortError::assert_eq(&Sort::Bool, condition)?;
+                SortError::assert_eq(a, b)?;
+            }
+        }
+        // Since the parser may allow `Int` and `Real` terms to be mixed, we only check that the
+        // arguments are numeric
+        Operator::Add
+        | Operator::Sub
+        | Operator::Mult
+        | Operator::RealDiv
+        | Operator::Abs
+        | Operator::LessThan
+        | Operator::GreaterThan
+        | Operator::LessEq
+        | Operator::GreaterEq => {
+            for s in sorts {
+                SortError::assert_one_of(&numeric, s)?;
+            }
+        }
+        Operator::IntDiv | Operator::Mod | Operator::ToReal => {
+            for s in sorts {
+                SortError::assert_eq(&Sort::Int, s)?;
+            }
+        }
+        Operator::ToInt | Operator::IsInt => {
+            for s in sorts {
+                SortError::assert_eq(&Sort::Real, s)?;
+            }
+        }
+        Operator::StrConcat => {
+            for s in sorts {
+                SortError::assert_eq(&Sort::String, s)?;
+            }
+        }
+        _ => (),
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Checking a proof one command at a time. See [`StepwiseChecker`].
+
+use super::{Config, ContextStack, ProofChecker};
+use crate::{ast::*, CarcaraResult};
+use indexmap::IndexSet;
+use std::collections::HashMap;
+
+/// A checker that receives the proof one command at a time, and checks each command as soon as it
+/// is pushed. This is useful to build a proof interactively, getting immediate feedback on every
+/// command.
+///
+/// Commands are pushed to the root proof, and may reference any of the previously pushed commands
+/// as premises, using the same premise indices as [`ProofStep::premises`]. A subproof is pushed as
+/// a single [`ProofCommand::Subproof`], and all of its commands are checked at once, with its
+/// context pushed while they are checked and popped afterwards.
+///
+/// # Examples
+///
+/// ```
+/// # use carcara::{ast::*, checker::{Config, StepwiseChecker}};
+/// let mut pool = PrimitivePool::new();
+/// let bool_sort = pool.add(Term::Sort(Sort::Bool));
+/// let p = pool.add(Term::new_var("p", bool_sort));
+/// let not_p = pool.add(Term::Op(Operator::Not, vec![p.clone()]));
+///
+/// let mut problem = Problem::new();
+/// problem.premises.extend([p.clone(), not_p.clone()]);
+/// let mut checker = StepwiseChecker::new(&mut pool, Config::new(), &problem);
+/// checker
+///     .push_command(ProofCommand::Assume { id: "h1".into(), term: p })
+///     .unwrap();
+/// checker
+///     .push_command(ProofCommand::Assume { id: "h2".into(), term: not_p })
+///     .unwrap();
+/// checker
+///     .push_command(ProofCommand::Step(ProofStep {
+///         id: "t1".into(),
+///         clause: Vec::new(),
+///         rule: "resolution".into(),
+///         premises: vec![(0, 0), (0, 1)],
+///         args: Vec::new(),
+///         discharge: Vec::new(),
+///     }))
+///     .unwrap();
+/// assert!(checker.reached_empty_clause());
+/// ```
+pub struct StepwiseChecker<'c> {
+    checker: ProofChecker<'c>,
+    premises: IndexSet<Rc<Term>>,
+    commands: Vec<ProofCommand>,
+}
+
+impl<'c> StepwiseChecker<'c> {
+    /// Constructs a new `StepwiseChecker` for a proof of the given problem. `assume` commands are
+    /// checked against the problem premises. The `expand_distinct` option is ignored.
+    pub fn new(pool: &'c mut PrimitivePool, config: Config, problem: &Problem) -> Self {
+        Self {
+            checker: ProofChecker::new(pool, Config { expand_distinct: false, ..config }),
+            premises: problem.premises.clone(),
+            commands: Vec::new(),
+        }
+    }
+
+    /// Checks a command against the commands already pushed, and, if it is valid, adds it to the
+    /// proof. If the command is invalid, it is discarded, so a corrected version of it can be
+    /// pushed next.
+    pub fn push_command(&mut self, command: ProofCommand) -> CarcaraResult<()> {
+        self.commands.push(command);
+        let result = self
+            .checker
+            .check_last_command(&self.premises, &self.commands);
+        if result.is_err() {
+            // If the command is a subproof, the error may have happened while its context was
+            // still pushed, so we need to reset the context stack
+            self.checker.context = ContextStack::new();
+            self.commands.pop();
+        }
+        result
+    }
+
+    /// Returns the commands pushed so far.
+    pub fn commands(&self) -> &[ProofCommand] {
+        &self.commands
+    }
+
+    /// Returns `true` if any of the pushed commands concludes the empty clause.
+    pub fn reached_empty_clause(&self) -> bool {
+        self.checker.reached_empty_clause
+    }
+
+    /// Returns `true` if any of the pushed commands is a hole.
+    pub fn is_holey(&self) -> bool {
+        self.checker.is_holey
+    }
+
+    /// Finishes building the proof, and returns it.
+    pub fn into_proof(self) -> Proof {
+        Proof {
+            constant_definitions: Vec::new(),
+            commands: self.commands,
+            comments: HashMap::new(),
+        }
+    }
+}
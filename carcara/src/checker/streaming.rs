@@ -0,0 +1,124 @@
+//! Checking a proof while it is parsed, without keeping all of its commands in memory. See
+//! [`check_file_streaming`](crate::check_file_streaming).
+
+use super::{Config, ProofChecker};
+use crate::{ast::*, CarcaraResult, Error};
+use indexmap::IndexSet;
+use std::collections::HashMap;
+
+/// A summary of a streaming checking run, as returned by
+/// [`check_file_streaming`](crate::check_file_streaming).
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StreamingSummary {
+    /// The number of root-level commands in the proof. Each subproof counts as a single command.
+    pub num_commands: usize,
+
+    /// The largest number of root-level commands that were held in memory at the same time.
+    pub peak_retained: usize,
+}
+
+/// Checks the root-level commands of a proof one at a time, as they are parsed. Each step is
+/// evicted as soon as the last command that references it is checked, while `assume` commands are
+/// kept until the end.
+pub struct StreamingChecker {
+    config: Config,
+
+    /// For each command id, the index of the last root-level command that references it.
+    last_uses: HashMap<String, usize>,
+
+    /// The root-level commands parsed so far. Evicted commands are replaced by a placeholder, so
+    /// that the premise indices computed by the parser remain valid.
+    commands: Vec<ProofCommand>,
+
+    /// For each root-level command index, the commands that can be evicted after it is checked.
+    pending_evictions: HashMap<usize, Vec<usize>>,
+
+    summary: StreamingSummary,
+    num_retained: usize,
+
+    /// Whether the last command checked so far is a step concluding the empty clause.
+    concludes_empty_clause: bool,
+    reached_empty_clause: bool,
+    is_holey: bool,
+}
+
+impl StreamingChecker {
+    pub fn new(config: Config, last_uses: HashMap<String, usize>) -> Self {
+        Self {
+            // The proof is never expanded, so the rules must not expect it to be
+            config: Config { expand_distinct: false, ..config },
+            last_uses,
+            commands: Vec::new(),
+            pending_evictions: HashMap::new(),
+            summary: StreamingSummary::default(),
+            num_retained: 0,
+            concludes_empty_clause: false,
+            reached_empty_clause: false,
+            is_holey: false,
+        }
+    }
+
+    /// Checks the next root-level command of the proof, and evicts the commands that are no longer
+    /// referenced.
+    pub fn check_command(
+        &mut self,
+        pool: &mut PrimitivePool,
+        premises: &IndexSet<Rc<Term>>,
+        command: ProofCommand,
+    ) -> CarcaraResult<()> {
+        let index = self.commands.len();
+        let last_use = self.last_uses.remove(command.id());
+        let is_assume = matches!(command, ProofCommand::Assume { .. });
+        self.concludes_empty_clause =
+            matches!(&command, ProofCommand::Step(s) if s.clause.is_empty());
+        self.commands.push(command);
+        self.num_retained += 1;
+        self.summary.num_commands += 1;
+        self.summary.peak_retained = self.summary.peak_retained.max(self.num_retained);
+
+        let mut checker = ProofChecker::new(pool, std::mem::take(&mut self.config));
+        let result = checker.check_last_command(premises, &self.commands);
+        self.config = std::mem::take(&mut checker.config);
+        self.reached_empty_clause |= checker.reached_empty_clause;
+        self.is_holey |= checker.is_holey;
+        result?;
+
+        if !is_assume {
+            match last_use {
+                Some(i) if i > index => self.pending_evictions.entry(i).or_default().push(index),
+                _ => self.evict(pool, index),
+            }
+        }
+        for i in self.pending_evictions.remove(&index).unwrap_or_default() {
+            self.evict(pool, i);
+        }
+        Ok(())
+    }
+
+    /// Finishes checking the proof, after all of its commands were checked. Returns `true` if the
+    /// proof contains holes.
+    pub fn finish(self) -> CarcaraResult<(bool, StreamingSummary)> {
+        if self.config.require_empty_clause && !self.concludes_empty_clause {
+            return Err(Error::DoesNotReachEmptyClause);
+        }
+        if self.reached_empty_clause {
+            Ok((self.is_holey, self.summary))
+        } else {
+            Err(Error::DoesNotReachEmptyClause)
+        }
+    }
+
+    fn evict(&mut self, pool: &mut PrimitivePool, index: usize) {
+        // The placeholder concludes a tautology, so even if it is referenced by mistake, it can
+        // never be used to derive the empty clause
+        self.commands[index] = ProofCommand::Step(ProofStep {
+            id: String::new(),
+            clause: vec![pool.bool_true()],
+            rule: String::new(),
+            premises: Vec::new(),
+            args: Vec::new(),
+            discharge: Vec::new(),
+        });
+        self.num_retained -= 1;
+    }
+}
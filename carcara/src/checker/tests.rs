@@ -0,0 +1,1968 @@
+use super::*;
+use crate::parser::{self, parse_instance};
+
+fn parse(problem: &str, proof: &str) -> (Problem, Proof, PrimitivePool) {
+    parse_instance(problem.as_bytes(), proof.as_bytes(), parser::Config::new()).unwrap()
+}
+
+#[test]
+fn test_check_with_summary() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t1 (cl (= p p)) :rule refl)
+        (step t2 (cl (not p) p) :rule hole)
+        (step t3 (cl) :rule resolution :premises (h1 h2))",
+    );
+    let mut checker = ProofChecker::new(&mut pool, Config::new());
+    let (is_holey, summary) = checker.check_with_summary(&problem, &proof).unwrap();
+    assert!(is_holey);
+    assert_eq!(summary.num_steps, 3);
+    assert!(summary.duration > Duration::ZERO);
+}
+
+#[test]
+fn test_require_empty_clause() {
+    let problem = "(declare-const p Bool) (assert p) (assert (not p))";
+    let cases = [
+        (
+            "(assume h1 p)
+            (assume h2 (not p))
+            (step t1 (cl) :rule resolution :premises (h1 h2))",
+            true,
+        ),
+        (
+            "(assume h1 p)
+            (assume h2 (not p))
+            (step t1 (cl) :rule resolution :premises (h1 h2))
+            (step t2 (cl (= p p)) :rule refl)",
+            false,
+        ),
+    ];
+    for (proof, expected) in cases {
+        let (problem, proof, mut pool) = parse(problem, proof);
+        assert_eq!(expected, proof.concludes_empty_clause());
+
+        // Without the option, it is enough for any step to conclude the empty clause
+        let config = Config::new();
+        assert!(ProofChecker::new(&mut pool, config)
+            .check(&problem, &proof)
+            .is_ok());
+
+        let config = Config::new().require_empty_clause(true);
+        let result = ProofChecker::new(&mut pool, config).check(&problem, &proof);
+        assert_eq!(expected, result.is_ok());
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serialize_proof() {
+    use crate::ast::SerializedProof;
+
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (declare-fun f (Int) Int) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t1 (cl (= (f 0) (f 0))) :rule refl)
+        (anchor :step t2 :args ((x Int)))
+        (step t2.t1 (cl (= (= x x) (= x x))) :rule refl)
+        (step t2 (cl (= (forall ((x Int)) (= x x)) (forall ((x Int)) (= x x)))) :rule bind)
+        (step t3 (cl) :rule resolution :premises (h1 h2))",
+    );
+    let bytes = bincode::serialize(&proof).unwrap();
+    let serialized: SerializedProof = bincode::deserialize(&bytes).unwrap();
+    let loaded = serialized.into_proof(&mut pool).unwrap();
+
+    // Since the terms were re-added to the same pool, the loaded proof shares them with the original
+    assert_eq!(proof, loaded);
+    assert!(ProofChecker::new(&mut pool, Config::new())
+        .check(&problem, &loaded)
+        .is_ok());
+}
+
+#[test]
+fn test_bool_simplify_error() {
+    let problem = "(declare-const p Bool) (declare-const q Bool)";
+    let cases = [
+        (
+            "(step t1 (cl (= (not (and p q)) (or (not p) (not q)))) :rule bool_simplify)",
+            None,
+        ),
+        (
+            "(step t1 (cl (= (and p q) (or p q))) :rule bool_simplify)",
+            Some(Operator::And),
+        ),
+    ];
+    for (proof, expected) in cases {
+        let (problem, proof, mut pool) = parse(problem, proof);
+        let result = ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof);
+        match (result, expected) {
+            // The step itself is valid, but the proof does not conclude the empty clause
+            (Err(Error::DoesNotReachEmptyClause), None) => (),
+            (
+                Err(Error::Checker {
+                    inner: CheckerError::NoSimplificationPatternApplies { rule, operator, patterns },
+                    ..
+                }),
+                Some(expected),
+            ) => {
+                assert_eq!(rule, "bool_simplify");
+                assert_eq!(operator, expected);
+                assert!(patterns.contains(&"(not (and phi_1 phi_2))"));
+            }
+            (other, _) => panic!("unexpected result: {other:?}"),
+        }
+    }
+}
+
+#[test]
+fn test_implies_simplify_error() {
+    let check = |step: &str| {
+        let (problem, proof, mut pool) = parse("(declare-const p Bool)", step);
+        match ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof) {
+            Err(Error::Checker { inner, .. }) => inner.to_string(),
+            other => panic!("expected checker error, got {:?}", other),
+        }
+    };
+
+    assert_eq!(
+        check("(step t1 (cl (= (=> true p) true)) :rule implies_simplify)"),
+        "the `implies_simplify` case '(=> true phi) => phi' applies to '(=> true p)', so expected \
+        result to be 'p', got 'true'",
+    );
+    assert_eq!(
+        check("(step t1 (cl (= (=> p false) p)) :rule implies_simplify)"),
+        "the `implies_simplify` case '(=> phi false) => (not phi)' applies to '(=> p false)', so \
+        expected result to be '(not p)', got 'p'",
+    );
+    assert_eq!(
+        check("(step t1 (cl (= (=> false p) p)) :rule implies_simplify)"),
+        "the `implies_simplify` case '(=> false phi) => true' applies to '(=> false p)', so \
+        expected result to be 'true', got 'p'",
+    );
+    assert_eq!(
+        check("(step t1 (cl (= (=> p true) p)) :rule implies_simplify)"),
+        "the `implies_simplify` case '(=> phi true) => true' applies to '(=> p true)', so \
+        expected result to be 'true', got 'p'",
+    );
+}
+
+#[test]
+fn test_wrong_literal_index() {
+    let check = |step: &str| {
+        let (problem, proof, mut pool) = parse(
+            "(declare-const p Bool) (declare-const q Bool) (declare-const r Bool)",
+            step,
+        );
+        match ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof) {
+            Err(Error::Checker { inner, .. }) => inner,
+            other => panic!("expected checker error, got {:?}", other),
+        }
+    };
+
+    let e = check("(step t1 (cl (not (and p q r)) r) :rule and_pos :args (1))");
+    assert!(matches!(
+        e,
+        CheckerError::WrongLiteralIndex {
+            asked: 1,
+            available: 3,
+            found: Some(2)
+        }
+    ));
+    assert_eq!(
+        e.to_string(),
+        "expected literal to be argument 1 of a term with 3 arguments, but it is argument 2",
+    );
+
+    let e = check("(step t1 (cl (or p q) (not r)) :rule or_neg :args (2))");
+    assert!(matches!(
+        e,
+        CheckerError::WrongLiteralIndex { asked: 2, available: 2, found: None }
+    ));
+
+    let e = check("(step t1 (cl (not (or p q r)) p r q) :rule or_pos)");
+    assert!(matches!(
+        e,
+        CheckerError::WrongLiteralIndex {
+            asked: 1,
+            available: 3,
+            found: Some(2)
+        }
+    ));
+
+    // If the literal is not an argument of the term at all, the error is not about the index
+    let e = check("(step t1 (cl (or p q) (not r)) :rule or_neg :args (0))");
+    assert!(matches!(e, CheckerError::TermEquality(_)));
+}
+
+#[test]
+fn test_bfun_elim_errors() {
+    use error::BfunElimError;
+
+    fn run(premise: &str, conclusion: &str) -> BfunElimError {
+        let problem = format!(
+            "(declare-fun f (Bool) Bool)
+            (declare-fun g (Bool Bool) Bool)
+            (declare-const a Bool)
+            (declare-const b Bool)
+            (assert {premise})"
+        );
+        let proof = format!(
+            "(assume h1 {premise})
+            (step t1 (cl {conclusion}) :rule bfun_elim :premises (h1))"
+        );
+        let (problem, proof, mut pool) = parse(&problem, &proof);
+        match ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof) {
+            Err(Error::Checker {
+                inner: CheckerError::BfunElim(e), ..
+            }) => e,
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    // One boolean argument
+    let e = run("(forall ((x Bool)) (f x))", "(f false)");
+    assert!(matches!(e, BfunElimError::WrongNumberOfCases(_, 2, 1)));
+
+    let e = run("(forall ((x Bool)) (f x))", "(and (f true) (f false))");
+    assert!(matches!(e, BfunElimError::CaseOutOfOrder(0, 1)));
+
+    let e = run("(f a)", "(ite a (f false) (f true))");
+    assert!(
+        matches!(e, BfunElimError::WrongArgumentExpansion { arg, .. } if arg.to_string() == "a")
+    );
+
+    // Two boolean arguments
+    let e = run(
+        "(exists ((x Bool) (y Bool)) (g x y))",
+        "(or (g false false) (g true false) (g false true) (g a true))",
+    );
+    match e {
+        BfunElimError::WrongCase { index, assignment, .. } => {
+            assert_eq!(index, 3);
+            assert_eq!(assignment, [("x".to_owned(), true), ("y".to_owned(), true)]);
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+
+    let e = run(
+        "(g a b)",
+        "(ite a (ite b (g true true) (g true false)) (ite a (g false true) (g false false)))",
+    );
+    assert!(
+        matches!(e, BfunElimError::WrongArgumentExpansion { arg, .. } if arg.to_string() == "b")
+    );
+}
+
+#[test]
+fn test_normalize_ite() {
+    let problem = "
+        (declare-const p Bool)
+        (declare-const q Bool)
+        (declare-const r Bool)
+        (declare-const s Bool)
+        (declare-const a Int)
+        (declare-const b Int)
+        (declare-fun f (Int) Bool)
+        (assert (ite p q (ite r s s)))";
+    let cases = [
+        "(assume h1 (ite p q (ite r s s)))
+        (step t1 (cl p s) :rule ite1 :premises (h1))",
+        "(step t1 (cl (= (ite p (ite true q r) s) (ite p q s))) :rule ite_simplify)",
+        "(step t1 (cl (= (f (ite p a b)) (and
+            (f (ite p a b))
+            (ite p (= (ite p a b) a) (= (ite p a b) (ite q b b)))
+        ))) :rule ite_intro)",
+    ];
+    for proof in cases {
+        let proof = format!("{proof}\n(step end (cl) :rule hole)");
+        let (problem, proof, mut pool) = parse(problem, &proof);
+
+        let config = Config::new();
+        assert!(ProofChecker::new(&mut pool, config)
+            .check(&problem, &proof)
+            .is_err());
+
+        let config = Config::new().normalize_ite(true);
+        assert!(ProofChecker::new(&mut pool, config)
+            .check(&problem, &proof)
+            .is_ok());
+    }
+}
+
+#[test]
+fn test_nary_elim_errors() {
+    fn run(conclusion: &str) -> CheckerError {
+        let problem = "(declare-const p Bool) (declare-const q Bool) (declare-const r Bool)
+            (declare-const a Int) (declare-const b Int) (declare-const c Int)";
+        let proof = format!("(step t1 (cl {conclusion}) :rule nary_elim)");
+        let (problem, proof, mut pool) = parse(problem, &proof);
+        match ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof) {
+            Err(Error::Checker { inner, .. }) => inner,
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    let e = run("(= (+ a b c) (+ a (+ b c)))");
+    assert!(matches!(
+        e,
+        CheckerError::NaryElimWrongAssociativity(Operator::Add, "left-associative")
+    ));
+    let e = run("(= (+ a b c) (+ (+ (+ 1 a) b) c))");
+    assert!(matches!(
+        e,
+        CheckerError::NaryElimWrongIdentity { op: Operator::Add, got, .. } if got.to_string() == "1"
+    ));
+
+    let e = run("(= (and p q r) (and p (and q r)))");
+    assert!(matches!(
+        e,
+        CheckerError::NaryElimWrongAssociativity(Operator::And, "left-associative")
+    ));
+    let e = run("(= (and p q) (and (and false p) q))");
+    assert!(matches!(
+        e,
+        CheckerError::NaryElimWrongIdentity { op: Operator::And, got, .. } if got.is_bool_false()
+    ));
+
+    let e = run("(= (xor p q r) (xor (xor p q) r))");
+    assert!(matches!(
+        e,
+        CheckerError::NaryElimUnsupportedOperator(Operator::Xor)
+    ));
+}
+
+#[test]
+fn test_forward_reference() {
+    fn run(proof: &Proof, problem: &Problem, pool: &mut PrimitivePool) -> (String, String) {
+        match ProofChecker::new(pool, Config::new()).check(problem, proof) {
+            Err(Error::Checker {
+                inner: CheckerError::ForwardReference { step, premise },
+                ..
+            }) => (step, premise),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    let (problem, mut proof, mut pool) = parse(
+        "(declare-const p Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t3 (cl p) :rule hole :premises (h1))
+        (step t4 (cl p) :rule hole :premises (t3))
+        (step t5 (cl) :rule resolution :premises (t4 h2))",
+    );
+    let set_premises = |proof: &mut Proof, index: usize, premises: Vec<(usize, usize)>| {
+        let ProofCommand::Step(step) = &mut proof.commands[index] else {
+            unreachable!()
+        };
+        step.premises = premises;
+    };
+
+    // A step that references itself
+    set_premises(&mut proof, 2, vec![(0, 2)]);
+    assert_eq!(run(&proof, &problem, &mut pool), ("t3".into(), "t3".into()));
+
+    // Two steps that reference each other
+    set_premises(&mut proof, 2, vec![(0, 3)]);
+    assert_eq!(run(&proof, &problem, &mut pool), ("t3".into(), "t4".into()));
+}
+
+#[test]
+fn test_local_assumptions() {
+    let problem = "(declare-const p Bool)";
+    let run = |proof: &str, config: Config| {
+        let (problem, proof, mut pool) = parse(problem, proof);
+        ProofChecker::new(&mut pool, config).check(&problem, &proof)
+    };
+
+    // A local assumption that is correctly discharged
+    let result = run(
+        "(anchor :step t1)
+        (assume t1.h1 p)
+        (step t1.t2 (cl p) :rule hole :premises (t1.h1))
+        (step t1 (cl (not p) p) :rule subproof :discharge (t1.h1))",
+        Config::new(),
+    );
+    assert!(matches!(result, Err(Error::DoesNotReachEmptyClause)));
+
+    // A local assumption that is not discharged by the closing step
+    let undischarged = |result| {
+        matches!(
+            result,
+            Err(Error::Checker {
+                inner: CheckerError::Subproof(SubproofError::LocalAssumeNotDischarged(id)),
+                ..
+            }) if id == "t1.h1"
+        )
+    };
+    let result = run(
+        "(anchor :step t1)
+        (assume t1.h1 p)
+        (step t1.t2 (cl p) :rule hole :premises (t1.h1))
+        (step t1 (cl p) :rule subproof)",
+        Config::new(),
+    );
+    assert!(undischarged(result));
+
+    // This is also checked when the rule of the closing step is ignored
+    let result = run(
+        "(anchor :step t1)
+        (assume t1.h1 p)
+        (step t1.t2 (cl p) :rule hole :premises (t1.h1))
+        (step t1 (cl p) :rule foo)",
+        Config::new().ignore_unknown_rules(true),
+    );
+    assert!(undischarged(result));
+}
+
+#[test]
+fn test_strict() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const a Int) (declare-const b Int) (declare-const c Int)
+        (assert (= b a)) (assert (= b c)) (assert (not (= a c)))",
+        "(assume h1 (= b a))
+        (assume h2 (= b c))
+        (assume h3 (not (= a c)))
+        (step t4 (cl (= a c)) :rule trans :premises (h1 h2))
+        (step t5 (cl) :rule resolution :premises (t4 h3))",
+    );
+
+    // The `trans` step implicitly flips the first premise, which is only allowed in non-strict mode
+    let result = ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof);
+    assert!(result.is_ok());
+
+    let result = ProofChecker::new(&mut pool, Config::new().strict(true)).check(&problem, &proof);
+    assert!(matches!(
+        result,
+        Err(Error::Checker {
+            inner: CheckerError::BrokenTransitivityChain(..),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_strict_eq_transitive() {
+    let problem = "(declare-const a Int) (declare-const b Int) (declare-const c Int)";
+    let check = |proof: &str, strict: bool| {
+        let (problem, proof, mut pool) = parse(problem, proof);
+        ProofChecker::new(&mut pool, Config::new().strict(strict)).check(&problem, &proof)
+    };
+
+    // The concluded equality follows the direction of the transitive chain
+    let proof = "(step t1 (cl (not (= a b)) (not (= b c)) (= a c)) :rule eq_transitive)
+        (step t2 (cl) :rule hole)";
+    assert!(check(proof, false).is_ok());
+    assert!(check(proof, true).is_ok());
+
+    // The concluded equality is flipped, which is only allowed in non-strict mode
+    let proof = "(step t1 (cl (not (= a b)) (not (= b c)) (= c a)) :rule eq_transitive)
+        (step t2 (cl) :rule hole)";
+    assert!(check(proof, false).is_ok());
+    assert!(matches!(
+        check(proof, true),
+        Err(Error::Checker {
+            inner: CheckerError::BrokenTransitivityChain(..),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_eq_congruent_pred_missing_premise() {
+    let problem = "(declare-const a Bool) (declare-const b Bool) (declare-const i Int)
+        (declare-const j Int) (declare-const r Real) (declare-const s Real)
+        (declare-fun mixed (Int Bool Real) Bool)";
+    let check = |clause: &str, strict: bool| {
+        let proof = format!(
+            "(step t1 (cl {}) :rule eq_congruent_pred) (step t2 (cl) :rule hole)",
+            clause
+        );
+        let (problem, proof, mut pool) = parse(problem, &proof);
+        ProofChecker::new(&mut pool, Config::new().strict(strict)).check(&problem, &proof)
+    };
+    let missing_at = |result: CarcaraResult<bool>| match result {
+        Err(Error::Checker {
+            inner: CheckerError::Cong(error::CongruenceError::MissingPremise(i, _, _)),
+            ..
+        }) => Some(i),
+        _ => None,
+    };
+
+    // Only the second argument differs, so in non-strict mode no other equalities are needed
+    let clause = "(not (= a b)) (not (mixed i a r)) (mixed i b r)";
+    assert!(check(clause, false).is_ok());
+    assert_eq!(missing_at(check(clause, true)), Some(1));
+
+    // The equality for the second argument is missing, even though there is one for the third
+    let clause = "(not (= r s)) (not (mixed i a r)) (mixed i b s)";
+    assert_eq!(missing_at(check(clause, false)), Some(1));
+
+    let clause = "(not (= a b)) (not (mixed i a r)) (mixed i b s)";
+    assert_eq!(missing_at(check(clause, false)), Some(2));
+}
+
+#[test]
+fn test_connective_def_unsupported() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (declare-const q Bool)",
+        "(step t1 (cl (= (and p q) (not (or (not p) (not q))))) :rule connective_def)
+        (step t2 (cl) :rule hole)",
+    );
+    let mut checker = ProofChecker::new(&mut pool, Config::new());
+    assert!(matches!(
+        checker.check(&problem, &proof),
+        Err(Error::Checker {
+            inner: CheckerError::NoConnectiveDefinition(connective, _),
+            ..
+        }) if connective == "and"
+    ));
+}
+
+#[test]
+fn test_strict_prod_simplify() {
+    let problem = "(declare-const x Real) (declare-const y Real)";
+    let check = |proof: &str, strict: bool| {
+        let (problem, proof, mut pool) = parse(problem, proof);
+        ProofChecker::new(&mut pool, Config::new().strict(strict)).check(&problem, &proof)
+    };
+
+    let proof = "(step t1 (cl (= (* x 2.0 y) (* 2.0 x y))) :rule prod_simplify)
+        (step t2 (cl) :rule hole)";
+    assert!(check(proof, false).is_ok());
+    assert!(check(proof, true).is_ok());
+
+    // The factors are reordered, which is only allowed in non-strict mode
+    let proof = "(step t1 (cl (= (* x 2.0 y) (* 2.0 y x))) :rule prod_simplify)
+        (step t2 (cl) :rule hole)";
+    assert!(check(proof, false).is_ok());
+    assert!(check(proof, true).is_err());
+}
+
+#[test]
+fn test_strict_ite_intro() {
+    let problem = "(declare-const p Bool) (declare-const q Bool)
+        (declare-const a Int) (declare-const b Int) (declare-const c Int) (declare-const d Int)";
+    let check = |proof: &str, strict: bool| {
+        let (problem, proof, mut pool) = parse(problem, proof);
+        ProofChecker::new(&mut pool, Config::new().strict(strict)).check(&problem, &proof)
+    };
+
+    // Both `ite` subterms are introduced
+    let proof = "(step t1 (cl (=
+            (< (ite p a b) (ite q c d))
+            (and
+                (< (ite p a b) (ite q c d))
+                (ite p (= a (ite p a b)) (= b (ite p a b)))
+                (ite q (= c (ite q c d)) (= d (ite q c d))))
+        )) :rule ite_intro)
+        (step t2 (cl) :rule hole)";
+    assert!(check(proof, false).is_ok());
+    assert!(check(proof, true).is_ok());
+
+    // The second `ite` subterm is skipped, which is only allowed in non-strict mode
+    let proof = "(step t1 (cl (=
+            (< (ite p a b) (ite q c d))
+            (and
+                (< (ite p a b) (ite q c d))
+                (ite p (= a (ite p a b)) (= b (ite p a b))))
+        )) :rule ite_intro)
+        (step t2 (cl) :rule hole)";
+    assert!(check(proof, false).is_ok());
+    assert!(matches!(
+        check(proof, true),
+        Err(Error::Checker {
+            inner: CheckerError::IteIntroMissing(t),
+            ..
+        }) if t.to_string() == "(ite q c d)"
+    ));
+
+    // The first `ite` subterm is introduced twice, which is never allowed
+    let proof = "(step t1 (cl (=
+            (< (ite p a b) (ite q c d))
+            (and
+                (< (ite p a b) (ite q c d))
+                (ite p (= a (ite p a b)) (= b (ite p a b)))
+                (ite p (= a (ite p a b)) (= b (ite p a b))))
+        )) :rule ite_intro)
+        (step t2 (cl) :rule hole)";
+    for strict in [false, true] {
+        assert!(matches!(
+            check(proof, strict),
+            Err(Error::Checker {
+                inner: CheckerError::IteIntroDuplicate(t),
+                ..
+            }) if t.to_string() == "(ite p a b)"
+        ));
+    }
+}
+
+#[test]
+fn test_strict_equiv() {
+    let problem = "(declare-const p Bool) (declare-const q Bool) (assert (= q p))";
+    let check = |proof: &str, strict: bool| {
+        let (problem, proof, mut pool) = parse(problem, proof);
+        ProofChecker::new(&mut pool, Config::new().strict(strict)).check(&problem, &proof)
+    };
+
+    // The premise is in the opposite orientation of the concluded literals, which is only allowed
+    // in non-strict mode
+    let proof = "(assume h1 (= q p))
+        (step t1 (cl (not p) q) :rule equiv1 :premises (h1))
+        (step t2 (cl) :rule hole)";
+    assert!(check(proof, false).is_ok());
+    match check(proof, true) {
+        Err(Error::Checker { inner, step, .. }) => {
+            assert_eq!(step, "t1");
+            assert!(matches!(
+                inner,
+                CheckerError::EquivSidesMismatch { allow_symmetry: false, .. }
+            ));
+            assert_eq!(
+                inner.to_string(),
+                "expected 'p' and 'q' to be the sides of '(= q p)', in the same order"
+            );
+        }
+        other => panic!("expected checker error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_check_with_problem() {
+    let (_, proof, mut pool) = parse(
+        "(declare-const a Int) (declare-const b Int) (assert (= a b)) (assert (not (= a b)))",
+        "(assume h1 (= a b))
+        (assume h2 (not (= a b)))
+        (step t3 (cl) :rule resolution :premises (h1 h2))",
+    );
+
+    // The assertions don't need to come from a parsed problem, and are matched modulo reordering
+    // of equalities
+    let int_sort = pool.add(Term::Sort(Sort::Int));
+    let [a, b] = ["a", "b"].map(|name| pool.add(Term::new_var(name, int_sort.clone())));
+    let assertions = [
+        build_term!(pool, (= {b.clone()} {a.clone()})),
+        build_term!(pool, (not (= {a.clone()} {b.clone()}))),
+    ];
+    let mut checker = ProofChecker::new(&mut pool, Config::new());
+    assert!(checker.check_with_problem(&proof, &assertions).is_ok());
+
+    // If an `assume` does not match any of the assertions, the check fails, even if it matches a
+    // premise in the original problem
+    let assertions = [assertions[1].clone()];
+    let mut checker = ProofChecker::new(&mut pool, Config::new());
+    assert!(matches!(
+        checker.check_with_problem(&proof, &assertions),
+        Err(Error::Checker { inner: CheckerError::Assume(_), .. })
+    ));
+}
+
+#[test]
+fn test_record_skipped() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t3 (cl p) :rule reslution :premises (h1))
+        (step t4 (cl) :rule resolution :premises (t3 h2))",
+    );
+
+    // Without recording, the typo'd rule name is an error
+    let mut checker = ProofChecker::new(&mut pool, Config::new());
+    assert!(matches!(
+        checker.check(&problem, &proof),
+        Err(Error::Checker {
+            inner: CheckerError::UnknownRule,
+            ..
+        })
+    ));
+    assert!(checker.skipped_steps().is_empty());
+
+    let mut checker = ProofChecker::new(&mut pool, Config::new().record_skipped(true));
+    assert!(checker.check(&problem, &proof).unwrap());
+    assert_eq!(
+        checker.skipped_steps(),
+        [("t3".to_owned(), "reslution".to_owned())]
+    );
+}
+
+#[test]
+fn test_forbid_trust() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t3 (cl (not p)) :rule trust)
+        (anchor :step t4)
+        (assume t4.a0 p)
+        (step t4.t1 (cl p) :rule trust_me)
+        (step t4 (cl (not p) p) :rule subproof :discharge (t4.a0))
+        (step t5 (cl) :rule resolution :premises (h1 t3))",
+    );
+    assert_eq!(uses_trust(&proof), ["t3", "t4.t1"]);
+
+    // If the trust rules are allowed, they are considered holes
+    let config = Config::new().ignore_unknown_rules(true);
+    let mut checker = ProofChecker::new(&mut pool, config.clone());
+    assert!(checker.check(&problem, &proof).unwrap());
+
+    let mut checker = ProofChecker::new(&mut pool, config.forbid_trust(true));
+    assert!(matches!(
+        checker.check(&problem, &proof),
+        Err(Error::Checker {
+            inner: CheckerError::TrustForbidden(rule),
+            step,
+            ..
+        }) if rule == "trust" && step == "t3"
+    ));
+}
+
+#[test]
+fn test_check_and_attest() {
+    let problem = "(declare-const p Bool) (declare-const q Bool) (assert p) (assert (not p))";
+    let attest_with = |problem: &str, proof: &str, config: Config| {
+        let (problem, proof, mut pool) = parse(problem, proof);
+        ProofChecker::new(&mut pool, config).check_and_attest(&problem, &proof)
+    };
+    let attest = |proof: &str, config: Config| attest_with(problem, proof, config);
+    let proof =
+        "(assume h1 p) (assume h2 (not p)) (step t3 (cl) :rule resolution :premises (h1 h2))";
+
+    // The same proof, written differently, produces the same attestation
+    let first = attest(proof, Config::new()).unwrap();
+    let second = attest(
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t3 (cl)
+            :rule resolution
+            :premises (h1 h2))",
+        Config::new(),
+    )
+    .unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first.digest(), second.digest());
+    assert!(!first.is_holey);
+
+    // Different proofs or checker options produce different attestations
+    let other = attest(
+        "(assume h2 (not p)) (assume h1 p) (step t3 (cl) :rule resolution :premises (h1 h2))",
+        Config::new(),
+    )
+    .unwrap();
+    assert_ne!(first.proof_hash, other.proof_hash);
+    let strict = attest(proof, Config::new().strict(true)).unwrap();
+    assert_eq!(first.proof_hash, strict.proof_hash);
+    assert_ne!(first.digest(), strict.digest());
+
+    // The same proof checked against a different problem produces a different attestation
+    let other_problem = attest_with(
+        "(declare-const p Bool) (declare-const q Bool) (assert p) (assert (not p)) (assert q)",
+        proof,
+        Config::new(),
+    )
+    .unwrap();
+    assert_eq!(first.proof_hash, other_problem.proof_hash);
+    assert_ne!(first.problem_hash, other_problem.problem_hash);
+    assert_ne!(first.digest(), other_problem.digest());
+
+    // Invalid proofs are not attested
+    let invalid =
+        "(assume h1 p) (assume h2 (not p)) (step t3 (cl q) :rule resolution :premises (h1 h2))";
+    assert!(attest(invalid, Config::new()).is_err());
+}
+
+#[test]
+fn test_collect_warnings() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (declare-const q Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t3 (cl (or p (not p) q)) :rule hole)
+        (step t4 (cl q true) :rule hole)
+        (step t5 (cl p q) :rule hole)
+        (step t6 (cl) :rule resolution :premises (h1 h2))",
+    );
+
+    // Warnings are not collected by default
+    let mut checker = ProofChecker::new(&mut pool, Config::new());
+    assert!(checker.check(&problem, &proof).is_ok());
+    assert!(checker.warnings().is_empty());
+
+    // Trivially true clauses are flagged, but don't make the check fail
+    let bool_sort = pool.add(Term::Sort(Sort::Bool));
+    let p = pool.add(Term::new_var("p", bool_sort));
+    let mut checker = ProofChecker::new(&mut pool, Config::new().collect_warnings(true));
+    assert!(checker.check(&problem, &proof).is_ok());
+    assert_eq!(
+        checker.warnings(),
+        [
+            Warning::ComplementaryLiterals { step: "t3".to_owned(), literal: p },
+            Warning::ClauseContainsTrue { step: "t4".to_owned() },
+        ]
+    );
+}
+
+#[test]
+fn test_rule_alias_warning() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t3 (cl) :rule th_resolution :premises (h1 h2))",
+    );
+    let mut checker = ProofChecker::new(&mut pool, Config::new().collect_warnings(true));
+    assert!(checker.check(&problem, &proof).is_ok());
+    assert_eq!(
+        checker.warnings(),
+        [Warning::RuleAlias {
+            step: "t3".to_owned(),
+            rule: "th_resolution".to_owned(),
+            canonical: "resolution",
+        }]
+    );
+    assert_eq!(
+        checker.warnings()[0].to_string(),
+        "step 't3' uses the rule 'th_resolution', which is an alias; consider using 'resolution'"
+    );
+}
+
+#[test]
+fn test_memory_estimate() {
+    let subproofs: String = (0..50)
+        .map(|i| {
+            format!(
+                "(anchor :step s{i} :args ((x{i} Int) (:= (y{i} Int) x{i})))
+                (step s{i}.t1 (cl (= (> (+ y{i} 1) 0) (> (+ x{i} 1) 0))) :rule refl)
+                (step s{i} (cl (= (forall ((y{i} Int)) (> (+ y{i} 1) 0))
+                    (forall ((x{i} Int)) (> (+ x{i} 1) 0)))) :rule bind)\n"
+            )
+        })
+        .collect();
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (assert p) (assert (not p))",
+        &format!(
+            "(assume h1 p) (assume h2 (not p)) {subproofs}
+            (step t3 (cl) :rule resolution :premises (h1 h2))"
+        ),
+    );
+
+    let mut checker = ProofChecker::new(&mut pool, Config::new());
+    let before = checker.memory_estimate();
+    assert!(before.pool_bytes > 0);
+    assert!(checker.check(&problem, &proof).is_ok());
+    let after = checker.memory_estimate();
+    assert!(after.total() > before.total());
+    assert!(after.context_bytes > before.context_bytes);
+    assert_eq!(after.context_depth, 0);
+}
+
+#[test]
+fn test_check_many() {
+    let (problem, proofs, mut pool) = parser::parse_instance_multi(
+        "(declare-const p Bool) (assert p) (assert (not p))".as_bytes(),
+        "unsat
+        (assume h1 p)
+        (assume h2 (not p))
+        (step t3 (cl) :rule resolution :premises (h1 h2))
+        unsat
+        (assume h1 p)
+        (step t2 (cl) :rule resolution :premises (h1 h1))"
+            .as_bytes(),
+        parser::Config::new(),
+    )
+    .unwrap();
+    assert_eq!(proofs.len(), 2);
+
+    let results = ProofChecker::new(&mut pool, Config::new()).check_many(&problem, &proofs);
+    assert!(matches!(results[..], [Ok(false), Err(_)]));
+}
+
+#[test]
+fn test_expand_distinct() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const a Int) (declare-const b Int) (declare-const c Int)
+        (assert (distinct a b)) (assert (distinct a b c)) (assert (= a b))",
+        "(assume h1 (distinct a b))
+        (assume h2 (distinct a b c))
+        (assume h3 (= a b))
+        (step t4 (cl (not (= a b))) :rule and :premises (h2) :args (0))
+        (step t5 (cl) :rule resolution :premises (h1 h3))",
+    );
+
+    // Without the pre-pass, `(distinct a b)` can't be used as the pivot `(not (= a b))`
+    let result = ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof);
+    assert!(result.is_err());
+
+    let config = Config::new().expand_distinct(true);
+    let result = ProofChecker::new(&mut pool, config).check(&problem, &proof);
+    assert!(result.is_ok());
+}
+
+#[test]
+fn test_expand_distinct_with_distinct_elim() {
+    // The conclusions of `distinct_elim` steps are also expanded, so they still match the steps
+    // that use them as premises
+    let (problem, proof, mut pool) = parse(
+        "(declare-const a Int) (declare-const b Int) (declare-const c Int)
+        (assert (distinct a b)) (assert (= a b))",
+        "(assume h1 (distinct a b))
+        (assume h2 (= a b))
+        (step t2 (cl (= (distinct a b) (not (= a b)))) :rule distinct_elim)
+        (step t3 (cl (not (distinct a b)) (not (= a b))) :rule equiv1 :premises (t2))
+        (step t4 (cl (= (distinct a b c) (and (not (= a b)) (not (= a c)) (not (= b c)))))
+            :rule distinct_elim)
+        (step t5 (cl (not (= a b))) :rule resolution :premises (t3 h1))
+        (step t6 (cl) :rule resolution :premises (t5 h2))",
+    );
+    for expand_distinct in [false, true] {
+        let config = Config::new().expand_distinct(expand_distinct);
+        let result = ProofChecker::new(&mut pool, config).check(&problem, &proof);
+        assert!(result.is_ok(), "{result:?}");
+    }
+
+    // Invalid `distinct_elim` steps are still rejected
+    for conclusion in [
+        "(= (distinct a b) (not (= a c)))",
+        "(= (distinct a b c) (and (not (= a b)) (not (= a c))))",
+        "(= (distinct a b) (= a b))",
+    ] {
+        let (problem, proof, mut pool) = parse(
+            "(declare-const a Int) (declare-const b Int) (declare-const c Int)",
+            &format!("(step t1 (cl {conclusion}) :rule distinct_elim)"),
+        );
+        let config = Config::new().expand_distinct(true);
+        let result = ProofChecker::new(&mut pool, config).check(&problem, &proof);
+        assert!(
+            matches!(result, Err(Error::Checker { .. })),
+            "{conclusion}: {result:?}"
+        );
+    }
+}
+
+#[test]
+fn test_supported_rules() {
+    let rules = ProofChecker::supported_rules();
+    for name in ["resolution", "th_resolution", "cp_addition", "refl", "hole"] {
+        assert!(rules.contains(&name), "rule '{name}' is missing");
+    }
+
+    // Every rule is listed once, and every listed rule is recognized by `get_rule`
+    let unique: HashSet<_> = rules.iter().collect();
+    assert_eq!(unique.len(), rules.len());
+    for name in rules {
+        assert!(ProofChecker::get_rule(name, &Config::new()).is_some());
+    }
+    assert!(ProofChecker::get_rule("not_a_rule", &Config::new()).is_none());
+
+    assert_eq!(ProofChecker::version(), env!("CARGO_PKG_VERSION"));
+}
+
+#[test]
+fn test_rule_overrides() {
+    fn always_fail(_: RuleArgs) -> RuleResult {
+        Err(CheckerError::Unspecified)
+    }
+
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t1 (cl (= p p)) :rule refl)
+        (step t2 (cl) :rule resolution :premises (h1 h2))",
+    );
+    assert!(ProofChecker::new(&mut pool, Config::new())
+        .check(&problem, &proof)
+        .is_ok());
+
+    let config = Config::new().rule_override("refl", always_fail);
+    match ProofChecker::new(&mut pool, config).check(&problem, &proof) {
+        Err(Error::Checker {
+            inner: CheckerError::Unspecified,
+            rule,
+            step,
+        }) => {
+            assert_eq!(rule, "refl");
+            assert_eq!(step, "t1");
+        }
+        other => panic!("expected `refl` step to fail, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_trace_sink() {
+    #[derive(Clone, Default)]
+    struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+    impl io::Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (declare-const q Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t1 (cl (= p q)) :rule refl)
+        (step t2 (cl) :rule resolution :premises (h1 h2))",
+    );
+    let buffer = SharedBuffer::default();
+    let config = Config::new().trace_sink(buffer.clone());
+    assert!(ProofChecker::new(&mut pool, config)
+        .check(&problem, &proof)
+        .is_err());
+
+    let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<_> = trace.lines().collect();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].starts_with("(step t1 :rule refl :premises () :result (fail \""));
+
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t1 (cl (= p p)) :rule refl)
+        (step t2 (cl) :rule resolution :premises (h1 h2))",
+    );
+    let buffer = SharedBuffer::default();
+    let config = Config::new().trace_sink(buffer.clone());
+    assert!(ProofChecker::new(&mut pool, config)
+        .check(&problem, &proof)
+        .is_ok());
+
+    let trace = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+    let lines: Vec<_> = trace.lines().collect();
+    assert_eq!(
+        lines,
+        [
+            "(step t1 :rule refl :premises () :result pass)",
+            "(step t2 :rule resolution :premises ((h1 (cl p)) (h2 (cl (not p)))) :result pass)",
+        ]
+    );
+}
+
+#[test]
+fn test_check_only() {
+    let problem = "(declare-const p Bool) (declare-const q Bool) (assert p) (assert (not p))";
+    let proof = "(assume h1 p)
+        (assume h2 (not p))
+        (step t1 (cl (= p p)) :rule refl)
+        (step t2 (cl (= q q)) :rule refl)
+        (step t3 (cl (not (= p p)) (not p) p) :rule equiv_pos2)
+        (step t4 (cl (not p) p) :rule resolution :premises (t1 t3))
+        (step t5 (cl q) :rule hole)
+        (step t6 (cl (= q p)) :rule refl)
+        (anchor :step t7)
+        (step t7.t1 (cl (= p p)) :rule refl)
+        (step t7 (cl (= p p)) :rule bind)
+        (step t8 (cl p) :rule resolution :premises (h1 t4))
+        (step t9 (cl) :rule resolution :premises (t8 h2))";
+    let (_, proof, mut pool) = parse(problem, proof);
+
+    // Step `t6` is invalid, but `t9` does not depend on it, so it is skipped
+    let result = ProofChecker::new(&mut pool, Config::new()).check_only(&proof, "t9");
+    assert!(!result.unwrap());
+
+    let result = ProofChecker::new(&mut pool, Config::new()).check_only(&proof, "t6");
+    assert!(matches!(result, Err(Error::Checker { step, .. }) if step == "t6"));
+
+    // Focusing on a step inside a subproof also checks the subproof's context
+    let result = ProofChecker::new(&mut pool, Config::new()).check_only(&proof, "t7.t1");
+    assert!(!result.unwrap());
+
+    let result = ProofChecker::new(&mut pool, Config::new()).check_only(&proof, "t10");
+    assert!(matches!(result, Err(Error::StepNotFound(id)) if id == "t10"));
+}
+
+#[test]
+fn test_step_hooks() {
+    use std::sync::{Arc, Mutex};
+
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t1 (cl (= p p)) :rule refl)
+        (anchor :step t2)
+        (step t2.t1 (cl (= p p)) :rule refl)
+        (step t2 (cl (= p p)) :rule hole)
+        (step t3 (cl (= p (not p))) :rule refl)
+        (step t4 (cl) :rule resolution :premises (h1 h2))",
+    );
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let config = Config::new()
+        .on_step_begin({
+            let events = events.clone();
+            move |step| events.lock().unwrap().push(format!("begin {}", step.id))
+        })
+        .on_step_end({
+            let events = events.clone();
+            move |step, result| {
+                let result = if result.is_ok() { "ok" } else { "error" };
+                events
+                    .lock()
+                    .unwrap()
+                    .push(format!("end {} {}", step.id, result));
+            }
+        });
+    let result = ProofChecker::new(&mut pool, config).check(&problem, &proof);
+    assert!(matches!(result, Err(Error::Checker { step, .. }) if step == "t3"));
+    assert_eq!(
+        *events.lock().unwrap(),
+        [
+            "begin t1",
+            "end t1 ok",
+            "begin t2.t1",
+            "end t2.t1 ok",
+            "begin t2",
+            "end t2 ok",
+            "begin t3",
+            "end t3 error",
+        ]
+    );
+}
+
+#[test]
+fn test_error_display() {
+    let problem = "(declare-const p Bool) (assert p)";
+
+    let result = parse_instance(
+        problem.as_bytes(),
+        "(assume h1 p)\n(step t1 (cl p) :rule)".as_bytes(),
+        parser::Config::new(),
+    );
+    let error: crate::Error = result.err().unwrap();
+    assert_eq!(
+        error.to_string(),
+        "parser error: unexpected token: ')' (on line 2, column 22)",
+    );
+
+    let (problem, proof, mut pool) = parse(
+        problem,
+        "(assume h1 p) (step t1 (cl (= p (not p))) :rule refl)",
+    );
+    let error = ProofChecker::new(&mut pool, Config::new())
+        .check(&problem, &proof)
+        .unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "checking failed on step 't1' with rule 'refl'"
+    );
+}
+
+#[test]
+fn test_wrong_count_errors() {
+    let check = |step: &str| {
+        let (problem, proof, mut pool) = parse(
+            "(declare-const x1 Int) (assert (>= x1 1))",
+            &format!("(assume c1 (>= x1 1)) {step}"),
+        );
+        match ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof) {
+            Err(Error::Checker { inner, .. }) => inner.to_string(),
+            other => panic!("expected checker error, got {:?}", other),
+        }
+    };
+
+    assert_eq!(
+        check("(step t1 (cl (>= (* 2 x1) 2)) :rule cp_multiplication :premises (c1) :args (2 3))"),
+        "expected 1 argument, got 2",
+    );
+    assert_eq!(
+        check("(step t1 (cl (>= (* 2 x1) 2)) :rule cp_multiplication :premises (c1 c1) :args (2))"),
+        "expected 1 premise, got 2",
+    );
+    assert_eq!(
+        check("(step t1 (cl) :rule cp_multiplication :premises (c1) :args (2))"),
+        "expected 1 term in clause, got 0",
+    );
+    assert_eq!(
+        check("(step t1 (cl (>= x1 1)) :rule eq_transitive)"),
+        "expected at least 3 terms in clause, got 1",
+    );
+}
+
+#[test]
+fn test_cp_dropped_literal_error() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const x1 Int) (declare-const x2 Int) (assert (>= (+ (* 1 x1) (* 2 x2)) 1))",
+        "(assume c1 (>= (+ (* 1 x1) (* 2 x2)) 1))
+        (step t1 (cl (>= (* 2 x1) 2)) :rule cp_multiplication :premises (c1) :args (2))",
+    );
+    match ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof) {
+        Err(Error::Checker { inner, .. }) => assert_eq!(
+            inner.to_string(),
+            "Literals of the conclusion differ from the expected ones: missing [x2], unexpected []"
+        ),
+        other => panic!("expected checker error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_la_tautology_errors() {
+    let check = |clause: &str| {
+        let (problem, proof, mut pool) = parse(
+            "(declare-const x1 Int)",
+            &format!("(step t1 (cl {clause}) :rule la_tautology)"),
+        );
+        match ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof) {
+            Err(Error::Checker { inner, .. }) => Err(inner.to_string()),
+            _ => Ok(()),
+        }
+    };
+
+    assert!(check("(<= x1 (+ 1 x1))").is_ok());
+    assert!(check("(or (not (<= x1 5)) (<= x1 6))").is_ok());
+    assert_eq!(
+        check("(<= x1 5)").unwrap_err(),
+        "'(<= x1 5)' is not a tautology: it is falsified by x1 = 6",
+    );
+    assert_eq!(
+        check("(>= (* 2 x1) 3)").unwrap_err(),
+        "'(>= (* 2 x1) 3)' is not a tautology: it is falsified by x1 = 1",
+    );
+    assert_eq!(
+        check("(< 1 0)").unwrap_err(),
+        "'(< 1 0)' is not a tautology: it is falsified by any assignment",
+    );
+    assert_eq!(
+        check("(or (not (<= x1 6)) (<= x1 5))").unwrap_err(),
+        "'(or (not (<= x1 6)) (<= x1 5))' is not a tautology: it is falsified by x1 = 6",
+    );
+}
+
+#[test]
+fn test_cong_errors() {
+    use error::CongruenceError;
+
+    fn run_rule(rule: &str, premises: &[&str], conclusion: &str) -> CongruenceError {
+        let mut proof = String::new();
+        for (i, p) in premises.iter().enumerate() {
+            proof += &format!("(assume h{i} {p})\n");
+        }
+        let ids: Vec<_> = (0..premises.len()).map(|i| format!("h{i}")).collect();
+        proof += &format!(
+            "(step t1 (cl {conclusion}) :rule {rule} :premises ({}))",
+            ids.join(" ")
+        );
+        let mut problem = "(declare-fun f (Int Int Int) Int) (declare-fun g (Int Int Int) Int)
+            (declare-const a Int) (declare-const b Int)
+            (declare-const c Int) (declare-const d Int)"
+            .to_owned();
+        for p in premises {
+            problem += &format!("(assert {p})");
+        }
+        let (problem, proof, mut pool) = parse(&problem, &proof);
+        match ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof) {
+            Err(Error::Checker { inner: CheckerError::Cong(e), .. }) => e,
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+    let run = |premises: &[&str], conclusion: &str| run_rule("cong", premises, conclusion);
+
+    let e = run(&["(= a b)"], "(= (+ a c) (+ b c d))");
+    assert!(matches!(
+        e,
+        CongruenceError::DifferentNumberOfArguments(2, 3)
+    ));
+
+    let e = run(&["(= c d)"], "(= (f a b c) (f a d c))");
+    assert!(matches!(
+        e,
+        CongruenceError::PremiseDoesntJustifyArgs { index: 1, .. }
+    ));
+
+    let e = run(&["(= a b)"], "(= (f a b c) (f b b d))");
+    assert!(matches!(e, CongruenceError::MissingPremise(2, _, _)));
+
+    // In `ho_cong`, the applied functions are not counted as an argument position
+    let ho_cong = |premises: &[&str], conclusion: &str| run_rule("ho_cong", premises, conclusion);
+    let e = ho_cong(&["(= f g)", "(= c d)"], "(= (f a b c) (g a d c))");
+    assert!(matches!(
+        e,
+        CongruenceError::PremiseDoesntJustifyArgs { index: 1, .. }
+    ));
+    let e = ho_cong(&["(= f g)"], "(= (f a b c) (g b b c))");
+    assert!(matches!(e, CongruenceError::MissingPremise(0, _, _)));
+    let e = ho_cong(&["(= a b)"], "(= (f a b c) (g b b c))");
+    assert!(matches!(e, CongruenceError::FunctionsNotJustified(_, _)));
+}
+
+#[test]
+fn test_named_terms() {
+    // A name introduced with a `:named` annotation, either in the problem or in the proof, can be
+    // used later in the proof to refer to the annotated term
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool)
+        (declare-const q Bool)
+        (assert (! (and p q) :named n))
+        (assert (not p))",
+        "(assume h1 n)
+        (assume h2 (not p))
+        (step t3 (cl p) :rule and :premises (h1) :args (0))
+        (step t4 (cl (= n (and p q))) :rule refl)
+        (step t5 (cl (not n) p) :rule and_pos :args (0))
+        (step t6 (cl (not (! (or p q) :named m)) p q) :rule or_pos)
+        (step t7 (cl (= m (or p q))) :rule refl)
+        (step t8 (cl) :rule resolution :premises (t3 h2))",
+    );
+    let assumed = match &proof.commands[0] {
+        ProofCommand::Assume { term, .. } => term.clone(),
+        _ => unreachable!(),
+    };
+    assert_eq!(assumed.to_string(), "(and p q)");
+    assert_eq!(problem.premises[0], assumed);
+
+    let result = ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof);
+    assert!(!result.unwrap());
+}
+
+#[test]
+fn test_tautology_large_clause() {
+    const N: usize = 2000;
+
+    let mut problem = String::new();
+    for i in 0..N {
+        problem += &format!("(declare-const p{i} Bool)");
+    }
+    let literals: Vec<_> = (0..N).map(|i| format!("p{i}")).collect();
+    let literals = literals.join(" ");
+
+    // The complementary literals are the first and last ones, so the whole clause must be scanned
+    let check = |last_literal: &str| {
+        let proof = format!(
+            "(step t1 (cl {literals} {last_literal}) :rule hole)
+            (step t2 (cl true) :rule tautology :premises (t1))"
+        );
+        let (problem, proof, mut pool) = parse(&problem, &proof);
+        let mut checker = ProofChecker::new(&mut pool, Config::new());
+        match checker.check(&problem, &proof) {
+            Ok(_) | Err(Error::DoesNotReachEmptyClause) => true,
+            Err(Error::Checker { step, .. }) if step == "t2" => false,
+            Err(e) => panic!("unexpected error: {e:?}"),
+        }
+    };
+    assert!(check("(not p0)"));
+    assert!(check("(not (not (not p0)))"));
+    assert!(!check("(not (not p0))"));
+    assert!(!check("p0"));
+}
+
+#[test]
+fn test_la_disequality_errors() {
+    use error::LinearArithmeticError;
+
+    fn run(clause: &str) -> Result<(), LinearArithmeticError> {
+        let (problem, proof, mut pool) = parse(
+            "(declare-const x Real) (declare-const y Real)",
+            &format!("(step t1 (cl {clause}) :rule la_disequality)"),
+        );
+        match ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof) {
+            Err(Error::DoesNotReachEmptyClause) => Ok(()),
+            Err(Error::Checker {
+                inner: CheckerError::LinearArithmetic(e),
+                ..
+            }) => Err(e),
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    assert!(run("(or (= x y) (not (<= x y)) (not (<= y x)))").is_ok());
+
+    let e = run("(or (= x y) (not (<= y x)) (not (<= y x)))").unwrap_err();
+    assert!(matches!(
+        e,
+        LinearArithmeticError::DisequalityWrongInequality { branch: "first", .. }
+    ));
+    let e = run("(or (= x y) (not (<= x y)) (not (<= x y)))").unwrap_err();
+    assert!(matches!(
+        e,
+        LinearArithmeticError::DisequalityWrongInequality { branch: "second", .. }
+    ));
+}
+
+#[test]
+fn test_minimize() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (declare-const q Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t3 (cl (= q q)) :rule refl)
+        (anchor :step t4)
+        (step t4.t1 (cl (= p p)) :rule refl)
+        (step t4.t2 (cl (= q q)) :rule refl)
+        (step t4 (cl (= p p)) :rule refl)
+        (step t5 (cl) :rule resolution :premises (h1 h2))",
+    );
+    let minimized = proof.minimize();
+    let ids: Vec<_> = minimized.iter().map(ProofCommand::id).collect();
+    assert_eq!(ids, ["h1", "h2", "t5"]);
+
+    let result = ProofChecker::new(&mut pool, Config::new()).check(&problem, &minimized);
+    assert!(!result.unwrap());
+}
+
+#[test]
+fn test_check_file_streaming() {
+    const NUM_STEPS: usize = 5000;
+
+    // Each step only references the previous one, except for the last step, which also references
+    // the first one
+    let mut proof = String::from("(assume h1 p)\n(assume h2 (not p))\n");
+    proof.push_str("(step t1 (cl p) :rule contraction :premises (h1))\n");
+    for i in 2..=NUM_STEPS {
+        proof.push_str(&format!(
+            "(step t{} (cl p) :rule contraction :premises (t{}))\n",
+            i,
+            i - 1
+        ));
+    }
+    proof.push_str("(anchor :step t.s)\n(assume t.h1 p)\n");
+    proof.push_str("(step t.s (cl (not p) p) :rule subproof :discharge (t.h1))\n");
+    proof.push_str(&format!(
+        "(step t.end (cl) :rule resolution :premises (t1 t{} h2))\n",
+        NUM_STEPS
+    ));
+
+    let dir = std::env::temp_dir().join(format!("carcara-streaming-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let problem_path = dir.join("problem.smt2");
+    let proof_path = dir.join("problem.smt2.alethe");
+    std::fs::write(
+        &problem_path,
+        "(declare-const p Bool) (assert p) (assert (not p))",
+    )
+    .unwrap();
+    std::fs::write(&proof_path, &proof).unwrap();
+
+    let result = crate::check_file_streaming(
+        &problem_path,
+        &proof_path,
+        parser::Config::new(),
+        Config::new(),
+    );
+
+    // A broken step must still be caught, even after the steps before it were evicted
+    let broken = proof.replace("(step t4000 (cl p)", "(step t4000 (cl (not p))");
+    std::fs::write(&proof_path, broken).unwrap();
+    let broken_result = crate::check_file_streaming(
+        &problem_path,
+        &proof_path,
+        parser::Config::new(),
+        Config::new(),
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    let (is_holey, summary) = result.unwrap();
+    assert!(!is_holey);
+    assert_eq!(summary.num_commands, NUM_STEPS + 4);
+
+    // Only the assumptions, the first step, and the last few steps need to be held at once, along
+    // with the step being checked
+    assert!(summary.peak_retained <= 5, "{:?}", summary);
+
+    match broken_result {
+        Err(crate::Error::Checker { step, .. }) => assert_eq!(step, "t4000"),
+        other => panic!("expected checker error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn test_check_batch() {
+    let dir = std::env::temp_dir().join(format!("carcara-batch-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let problem_path = dir.join("problem.smt2");
+    std::fs::write(
+        &problem_path,
+        "(declare-const p Bool) (assert p) (assert (not p))",
+    )
+    .unwrap();
+
+    let valid_path = dir.join("valid.alethe");
+    std::fs::write(
+        &valid_path,
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t1 (cl (= p p)) :rule refl)
+        (step t2 (cl) :rule resolution :premises (h1 h2))",
+    )
+    .unwrap();
+    let invalid_path = dir.join("invalid.alethe");
+    std::fs::write(
+        &invalid_path,
+        "(assume h1 p)
+        (step t1 (cl) :rule resolution :premises (h1))",
+    )
+    .unwrap();
+
+    let instances = [
+        (problem_path.clone(), invalid_path.clone()),
+        (problem_path.clone(), valid_path.clone()),
+        (problem_path, dir.join("missing.alethe")),
+    ];
+    let summaries = crate::check_batch(&instances, parser::Config::new(), Config::new());
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    // The failure in the first instance does not stop the others from being checked
+    assert_eq!(summaries.len(), 3);
+    let [invalid, valid, missing] = &summaries[..] else {
+        unreachable!()
+    };
+
+    assert_eq!(invalid.proof_path, invalid_path);
+    assert!(matches!(&invalid.result, Err(Error::Checker { step, .. }) if step == "t1"));
+    assert_eq!(invalid.num_steps, 1);
+
+    assert_eq!(valid.proof_path, valid_path);
+    assert!(matches!(valid.result, Ok(false)));
+    assert_eq!(valid.num_steps, 2);
+    let (rule, time) = valid.slowest_rule.as_ref().unwrap();
+    assert!(["assume", "refl", "resolution"].contains(&rule.as_str()));
+    assert!(*time <= valid.time);
+
+    assert!(matches!(missing.result, Err(Error::Io(_))));
+    assert_eq!(missing.num_steps, 0);
+    assert!(missing.slowest_rule.is_none());
+}
+
+#[test]
+fn test_stepwise_checker() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (declare-const q Bool) (assert (and p q)) (assert (not p))",
+        "(assume h1 (and p q))
+        (step t2 (cl p) :rule and :premises (h1) :args (0))
+        (assume h3 (not p))
+        (step t4 (cl) :rule resolution :premises (t2 h3))",
+    );
+    let mut commands = proof.commands.into_iter();
+    let mut checker = StepwiseChecker::new(&mut pool, Config::new(), &problem);
+    checker.push_command(commands.next().unwrap()).unwrap();
+
+    // A step that is missing its premise is rejected, and is not added to the proof
+    let step = commands.next().unwrap();
+    let ProofCommand::Step(mut wrong) = step.clone() else {
+        unreachable!()
+    };
+    wrong.premises.clear();
+    let wrong = ProofCommand::Step(wrong);
+    assert!(matches!(
+        checker.push_command(wrong),
+        Err(Error::Checker { step, .. }) if step == "t2"
+    ));
+    assert_eq!(checker.commands().len(), 1);
+
+    // The corrected step depends on the assumption pushed before it
+    checker.push_command(step).unwrap();
+    assert!(!checker.reached_empty_clause());
+    for command in commands {
+        checker.push_command(command).unwrap();
+    }
+    assert!(checker.reached_empty_clause());
+    assert!(!checker.is_holey());
+
+    let ids: Vec<_> = checker
+        .into_proof()
+        .iter()
+        .map(|c| c.id().to_owned())
+        .collect();
+    assert_eq!(ids, ["h1", "t2", "h3", "t4"]);
+}
+
+#[test]
+fn test_bind_context_errors() {
+    let check = |proof: &str| {
+        let (_, proof, mut pool) = parse("(declare-const p Bool) (declare-const q Bool)", proof);
+        match ProofChecker::new(&mut pool, Config::new()).check_only(&proof, "t1") {
+            Ok(_) => Ok(()),
+            Err(Error::Checker { inner, .. }) => Err(inner.to_string()),
+            Err(other) => panic!("expected checker error, got {:?}", other),
+        }
+    };
+
+    let well_formed = "(anchor :step t1 :args ((y Real) (:= (x Real) y)))
+        (step t1.t1 (cl (= p q)) :rule hole)
+        (step t1 (cl (= (forall ((x Real)) p) (forall ((y Real)) q))) :rule bind)";
+    assert!(check(well_formed).is_ok());
+
+    let not_in_context = "(anchor :step t1 :args ((y Real) (:= (x Real) y)))
+        (step t1.t1 (cl (= p q)) :rule hole)
+        (step t1 (cl (= (forall ((x Real)) p) (forall ((w Real)) q))) :rule bind)";
+    assert_eq!(
+        check(not_in_context).unwrap_err(),
+        "binding 'w' was not introduced in context"
+    );
+
+    let not_renamed = "(anchor :step t1 :args ((y Real) (w Real) (:= (x Real) y)))
+        (step t1.t1 (cl (= p q)) :rule hole)
+        (step t1 (cl (= (forall ((x Real)) p) (forall ((w Real)) q))) :rule bind)";
+    assert_eq!(
+        check(not_renamed).unwrap_err(),
+        "the anchor renames 'x' to 'y', but 'y' is not bound on the right side of the conclusion"
+    );
+}
+
+#[test]
+fn test_ac_simp_preserves_operand_order() {
+    // `ac_simp` does not sort the operands, it keeps the order in which they first appear. The
+    // problem declares the constants in a different order in each run, so the terms are added to
+    // the pool in a different order, but the expected conclusion must be the same
+    let run = |declarations: &str| {
+        let (_, proof, mut pool) = parse(
+            declarations,
+            "(step t1 (cl (= (and p (and r q) (and q (and p s))) (and p q))) :rule ac_simp)",
+        );
+        match ProofChecker::new(&mut pool, Config::new()).check_only(&proof, "t1") {
+            Err(Error::Checker { inner, .. }) => inner.to_string(),
+            other => panic!("expected checker error, got {:?}", other),
+        }
+    };
+    let first = run("(declare-const p Bool) (declare-const q Bool)
+        (declare-const r Bool) (declare-const s Bool)");
+    let second = run("(declare-const s Bool) (declare-const r Bool)
+        (declare-const q Bool) (declare-const p Bool) (assert (and s r q p))");
+    assert_eq!(first, second);
+    assert!(first.contains("(and p r q s)"), "{}", first);
+}
+
+#[test]
+fn test_resolution_pivot_errors() {
+    let check = |proof: &str| {
+        let (_, proof, mut pool) = parse(
+            "(declare-const p Bool) (declare-const q Bool)
+            (declare-const r Bool) (declare-const s Bool)",
+            proof,
+        );
+        match ProofChecker::new(&mut pool, Config::new()).check_only(&proof, "t3") {
+            Err(Error::Checker { inner, .. }) => inner.to_string(),
+            other => panic!("expected checker error, got {:?}", other),
+        }
+    };
+
+    assert_eq!(
+        check(
+            "(step t1 (cl p q r) :rule hole)
+            (step t2 (cl (not q) s) :rule hole)
+            (step t3 (cl p r s) :rule resolution :premises (t1 t2) :args (r true))"
+        ),
+        "pivot was not found in clause: '(not r)'",
+    );
+    assert_eq!(
+        check(
+            "(step t1 (cl p q r) :rule hole)
+            (step t2 (cl (not q) s) :rule hole)
+            (step t3 (cl p r s) :rule resolution :premises (t1 t2) :args (q false))"
+        ),
+        "pivot was not found in clause: '(not q)'",
+    );
+}
+
+#[test]
+fn test_check_sorts() {
+    let mut pool = PrimitivePool::new();
+    let bool_sort = pool.add(Term::Sort(Sort::Bool));
+    let x = pool.add(Term::new_var("x", bool_sort.clone()));
+    let one = pool.add(Term::new_int(1));
+    let ill_sorted = pool.add(Term::Op(Operator::And, vec![one.clone(), x.clone()]));
+    let sum = pool.add(Term::Op(Operator::Add, vec![one.clone(), one]));
+    assert_eq!(sort_of(&mut pool, &sum).unwrap(), Sort::Int);
+    assert_eq!(
+        sort_of(&mut pool, &ill_sorted).unwrap_err().to_string(),
+        "expected 'Bool', got 'Int'"
+    );
+
+    // Applying a term that is not a function is a sort error, not a panic
+    let int_sort = pool.add(Term::Sort(Sort::Int));
+    let c = pool.add(Term::new_var("c", int_sort));
+    let bad_app = pool.add(Term::App(c, vec![x.clone()]));
+    assert_eq!(
+        sort_of(&mut pool, &bad_app).unwrap_err().to_string(),
+        "expected '(Func ? ?)', got 'Int'"
+    );
+
+    // Parametric functions are accepted if their arguments match the parameters consistently
+    let var_t = pool.add(Term::Sort(Sort::Var("T".to_owned())));
+    let param_sort = {
+        let function = pool.add(Term::Sort(Sort::Function(vec![
+            var_t.clone(),
+            var_t.clone(),
+            bool_sort.clone(),
+        ])));
+        pool.add(Term::Sort(Sort::ParamSort(vec![var_t.clone()], function)))
+    };
+    let eq = pool.add(Term::new_var("eq", param_sort));
+    let one = pool.add(Term::new_int(1));
+    let good_app = pool.add(Term::App(eq.clone(), vec![one.clone(), one.clone()]));
+    assert_eq!(sort_of(&mut pool, &good_app).unwrap(), Sort::Bool);
+    let identity_sort = {
+        let function = pool.add(Term::Sort(Sort::Function(vec![
+            var_t.clone(),
+            var_t.clone(),
+        ])));
+        pool.add(Term::Sort(Sort::ParamSort(vec![var_t.clone()], function)))
+    };
+    let identity = pool.add(Term::new_var("id", identity_sort));
+    let app = pool.add(Term::App(identity, vec![one.clone()]));
+    assert_eq!(sort_of(&mut pool, &app).unwrap(), Sort::Int);
+    let bad_app = pool.add(Term::App(eq, vec![one, x]));
+    assert_eq!(
+        sort_of(&mut pool, &bad_app).unwrap_err().to_string(),
+        "expected 'T', got 'Bool'"
+    );
+
+    let mut builder = ProofBuilder::new();
+    builder.step("hole", vec![ill_sorted], &[], Vec::new());
+    let proof = builder.build();
+    let problem = Problem::new();
+
+    // Without sort checking, the ill-sorted term is not noticed
+    let mut checker = ProofChecker::new(&mut pool, Config::new());
+    assert!(checker
+        .check(&problem, &proof)
+        .is_err_and(|e| matches!(e, Error::DoesNotReachEmptyClause)));
+
+    let mut checker = ProofChecker::new(&mut pool, Config::new().check_sorts(true));
+    match checker.check(&problem, &proof) {
+        Err(Error::Checker { inner, step, .. }) => {
+            assert_eq!(step, "t1");
+            assert_eq!(inner.to_string(), "expected 'Bool', got 'Int'");
+        }
+        other => panic!("expected checker error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_qnt_simplify_empty_binder() {
+    // The parser doesn't accept quantifiers with an empty binding list, so we need to build the
+    // term by hand
+    let mut pool = PrimitivePool::new();
+    let bool_sort = pool.add(Term::Sort(Sort::Bool));
+    let p = pool.add(Term::new_var("p", bool_sort));
+    let empty = pool.add(Term::Binder(
+        Binder::Forall,
+        BindingList(Vec::new()),
+        p.clone(),
+    ));
+
+    let check = |pool: &mut PrimitivePool, right: &Rc<Term>| {
+        let conclusion = pool.add(Term::Op(
+            Operator::Equals,
+            vec![empty.clone(), right.clone()],
+        ));
+        let mut builder = ProofBuilder::new();
+        builder.step("qnt_simplify", vec![conclusion], &[], Vec::new());
+        let proof = builder.build();
+        let mut checker = ProofChecker::new(pool, Config::new());
+        match checker.check(&Problem::new(), &proof) {
+            Err(Error::DoesNotReachEmptyClause) => true,
+            Err(Error::Checker { .. }) => false,
+            other => panic!("unexpected result: {:?}", other),
+        }
+    };
+    assert!(check(&mut pool, &p));
+    let not_p = pool.add(Term::Op(Operator::Not, vec![p.clone()]));
+    assert!(!check(&mut pool, &not_p));
+}
+
+#[test]
+fn test_check_assumptions() {
+    let (problem, proof, mut pool) = parse(
+        "(declare-const a Int) (declare-const b Int) (declare-const p Bool)
+        (assert (= a b)) (assert p)",
+        "(assume h1 (= b a))
+        (assume h2 p)
+        (anchor :step t3)
+        (assume t3.h1 (not p))
+        (step t3 (cl (not (not p))) :rule hole)",
+    );
+    let premises: Vec<_> = problem.premises.iter().cloned().collect();
+
+    // The assumptions may match the premises modulo reordering of equalities, and local
+    // assumptions in subproofs are ignored
+    let checker = ProofChecker::new(&mut pool, Config::new());
+    assert!(checker.check_assumptions(&proof, &premises).is_ok());
+    let checker = ProofChecker::new(&mut pool, Config::new().strict(true));
+    assert!(matches!(
+        checker.check_assumptions(&proof, &premises),
+        Err(CheckerError::Assume(t)) if t.to_string() == "(= b a)"
+    ));
+
+    let checker = ProofChecker::new(&mut pool, Config::new());
+    match checker.check_assumptions(&proof, &premises[..1]) {
+        Err(CheckerError::Assume(t)) => assert_eq!(t.to_string(), "p"),
+        other => panic!("expected assume error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_error_source_chain() {
+    use std::error::Error as _;
+
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (declare-const q Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t3 (cl q) :rule and :premises (h1) :args (0))",
+    );
+    let err = ProofChecker::new(&mut pool, Config::new())
+        .check(&problem, &proof)
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "checking failed on step 't3' with rule 'and'"
+    );
+    let source = err.source().expect("checker errors should have a source");
+    assert!(source.downcast_ref::<CheckerError>().is_some());
+
+    // Walking the whole chain must not repeat any message
+    let mut messages = vec![err.to_string()];
+    let mut current = err.source();
+    while let Some(e) = current {
+        let message = e.to_string();
+        assert!(messages.iter().all(|m| !m.contains(&message)));
+        messages.push(message);
+        current = e.source();
+    }
+    assert_eq!(messages.len(), 2);
+
+    // Sort errors are reported transparently, so they are not repeated either
+    let mut pool = PrimitivePool::new();
+    let one = pool.add(Term::new_int(1));
+    let not_one = pool.add(Term::Op(Operator::Not, vec![one]));
+    let mut builder = ProofBuilder::new();
+    builder.step("hole", vec![not_one], &[], Vec::new());
+    let err = ProofChecker::new(&mut pool, Config::new().check_sorts(true))
+        .check(&Problem::new(), &builder.build())
+        .unwrap_err();
+    let inner = err.source().unwrap();
+    assert!(matches!(
+        inner.downcast_ref::<CheckerError>(),
+        Some(CheckerError::Sort(_))
+    ));
+    assert_eq!(inner.to_string(), "expected 'Bool', got 'Int'");
+    assert!(inner.source().is_none());
+}
+
+#[test]
+fn test_hole_rules_are_holey() {
+    // These rules accept any step without checking it, so a proof that uses them is only accepted
+    // as holey
+    for rule in ["hole", "lia_generic"] {
+        let (problem, proof, mut pool) = parse(
+            "(declare-const a Int) (declare-const b Int)",
+            &format!("(step t1 (cl (< a b) (< b a)) :rule {rule}) (step t2 (cl) :rule {rule})"),
+        );
+        let mut checker = ProofChecker::new(&mut pool, Config::new());
+        assert!(checker.check(&problem, &proof).is_ok_and(|holey| holey));
+    }
+}
+
+/// Measures how much of the time spent checking a resolution step with many premises goes into
+/// building the premise vector passed to the rule in `RuleArgs`. The step has 1001 premises: 1000
+/// unit clauses and one clause with the negation of each of them. Since this only measures time,
+/// it is ignored by default, and should be run in release mode with:
+///
+/// ```text
+/// cargo test --release -p carcara -- --ignored bench_resolution_premises --nocapture
+/// ```
+#[test]
+#[ignore]
+fn bench_resolution_premises() {
+    use std::time::Instant;
+
+    const NUM_LITERALS: usize = 1000;
+    const NUM_RUNS: usize = 20;
+
+    let mut problem = String::new();
+    let mut proof = String::new();
+    for i in 0..NUM_LITERALS {
+        problem += &format!("(declare-const p{i} Bool)\n");
+        proof += &format!("(step h{i} (cl p{i}) :rule hole)\n");
+    }
+    let negated: Vec<_> = (0..NUM_LITERALS).map(|i| format!("(not p{i})")).collect();
+    let premise_ids: Vec<_> = (0..NUM_LITERALS).map(|i| format!("h{i}")).collect();
+    proof += &format!("(step c (cl {}) :rule hole)\n", negated.join(" "));
+    proof += &format!(
+        "(step t (cl) :rule resolution :premises (c {}))\n",
+        premise_ids.join(" ")
+    );
+    let (problem, proof, mut pool) = parse(&problem, &proof);
+    let Some(ProofCommand::Step(step)) = proof.commands.last() else {
+        unreachable!();
+    };
+
+    let median = |mut times: Vec<Duration>| {
+        times.sort_unstable();
+        times[times.len() / 2]
+    };
+    let (mut build_times, mut rule_times, mut check_times) = (Vec::new(), Vec::new(), Vec::new());
+    for _ in 0..NUM_RUNS {
+        let start = Instant::now();
+        let premises: Vec<_> = step
+            .premises
+            .iter()
+            .map(|&p| Premise::new(p, &proof.commands[p.1]))
+            .collect();
+        build_times.push(start.elapsed());
+
+        let mut polyeq_time = Duration::ZERO;
+        let start = Instant::now();
+        rules::resolution::resolution(RuleArgs {
+            conclusion: &step.clause,
+            premises: &premises,
+            args: &step.args,
+            pool: &mut pool,
+            context: &mut ContextStack::new(),
+            previous_command: None,
+            discharge: &[],
+            polyeq_time: &mut polyeq_time,
+        })
+        .unwrap();
+        rule_times.push(start.elapsed());
+
+        let start = Instant::now();
+        ProofChecker::new(&mut pool, Config::new())
+            .check(&problem, &proof)
+            .unwrap();
+        check_times.push(start.elapsed());
+    }
+    println!("building the premise vector: {:?}", median(build_times));
+    println!("running the resolution rule: {:?}", median(rule_times));
+    println!("checking the whole proof:    {:?}", median(check_times));
+}
+
+#[test]
+fn test_stats_distinguish_th_resolution() {
+    use crate::benchmarking::{Metrics, OnlineBenchmarkResults};
+
+    let (problem, proof, mut pool) = parse(
+        "(declare-const p Bool) (declare-const q Bool) (assert p) (assert (not p))",
+        "(assume h1 p)
+        (assume h2 (not p))
+        (step t3 (cl q (not q)) :rule hole)
+        (step t4 (cl (not q)) :rule th_resolution :premises (t3 t3))
+        (step t5 (cl) :rule resolution :premises (h1 h2))
+        (step t6 (cl) :rule resolution :premises (h1 h2))",
+    );
+    let mut stats = CheckerStatistics {
+        file_name: "test",
+        polyeq_time: Duration::ZERO,
+        assume_time: Duration::ZERO,
+        assume_core_time: Duration::ZERO,
+        results: OnlineBenchmarkResults::new(),
+    };
+    ProofChecker::new(&mut pool, Config::new())
+        .check_with_stats(&problem, &proof, &mut stats)
+        .unwrap();
+
+    // Both rules are checked by the same function, but their steps are recorded under the rule
+    // name used in the proof
+    let by_rule = stats.results.step_time_by_rule();
+    assert_eq!(by_rule["th_resolution"].count(), 1);
+    assert_eq!(by_rule["resolution"].count(), 2);
+}
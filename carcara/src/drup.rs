@@ -40,6 +40,8 @@ pub enum DrupFormatError {
     CheckingRatInEmptyClause,
     #[error("the clause isn't in RAT format")]
     NotInRatFormat,
+    #[error("argument '{0}' is not a clause")]
+    ArgIsNotClause(Rc<Term>),
 }
 
 pub fn hash_term<T: Borrow<Rc<Term>>>(pool: &mut dyn TermPool, term: T) -> u64 {
@@ -246,7 +248,8 @@ pub fn check_drup(
             continue;
         }
 
-        let terms = match_term!((cl ...) = &t).unwrap();
+        let terms =
+            match_term!((cl ...) = &t).ok_or_else(|| DrupFormatError::ArgIsNotClause(t.clone()))?;
         let mut unit_history = rup(pool, premises.borrow(), terms);
         if unit_history.is_none() && !terms.is_empty() && check_rat {
             unit_history = check_drat(pool, premises.borrow(), terms);
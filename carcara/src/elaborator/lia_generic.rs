@@ -137,6 +137,7 @@ fn parse_and_check_solver_proof(
         allow_int_real_subtyping: true,
         strict: false,
         parse_hole_args: false,
+        keep_comments: false,
     };
     let (problem, proof) = parser::parse_instance_with_pool(problem, proof, config, pool)?;
 
@@ -29,7 +29,7 @@ pub fn resolution(
                     id: ids.next_id(),
                     depth: step.depth,
                     clause: Vec::new(),
-                    rule: "resolution".to_owned(),
+                    rule: step.rule.clone(),
                     premises: vec![step.premises[0].clone(), true_step],
                     args: [true, false].map(|a| pool.bool_constant(a)).to_vec(),
                     ..Default::default()
@@ -70,7 +70,9 @@ pub fn resolution(
         id: step.id.clone(),
         depth: step.depth,
         clause: step.clause.clone(),
-        rule: "resolution".to_owned(),
+        // We keep the original rule name, so that `th_resolution` steps can still be
+        // distinguished from regular `resolution` steps after elaboration
+        rule: step.rule.clone(),
         premises,
         args: pivots,
         ..Default::default()
@@ -135,7 +137,7 @@ pub fn resolution(
             id: ids.next_id(),
             depth: step.depth,
             clause: vec![double_not_c],
-            rule: "resolution".to_owned(),
+            rule: step.rule.clone(),
             premises: vec![resolution_step, first_not_not_step, second_not_not_step],
             args,
             ..Default::default()
@@ -144,3 +146,36 @@ pub fn resolution(
         Ok(Rc::new(ProofNode::Step(resolution_step)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{self, parse_instance};
+
+    #[test]
+    fn test_resolution_keeps_rule_name() {
+        let problem: &[u8] = b"
+            (declare-const a Bool)
+            (declare-const b Bool)
+        ";
+        for rule in ["resolution", "th_resolution"] {
+            let proof = format!(
+                "(step t1 (cl a b) :rule hole)
+                (step t2 (cl (not a)) :rule hole)
+                (step t3 (cl b) :rule {} :premises (t1 t2))",
+                rule
+            );
+            let (_, proof, mut pool) =
+                parse_instance(problem, proof.as_bytes(), parser::Config::new()).unwrap();
+            let proof = ProofNode::from_commands(proof.commands);
+            let ProofNode::Step(step) = proof.as_ref() else {
+                unreachable!();
+            };
+
+            let got = resolution(&mut pool, &mut ContextStack::new(), step).unwrap();
+            let got = got.as_step().unwrap();
+            assert_eq!(got.rule, rule);
+            assert_eq!(got.args.len(), 2);
+        }
+    }
+}
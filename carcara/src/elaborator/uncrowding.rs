@@ -204,6 +204,11 @@ fn get_weakening_clause(current: &[Rc<Term>], target: &[Rc<Term>]) -> Vec<Rc<Ter
         }
     }
 
+    // The missing terms are sorted so that the weakened clause doesn't depend on the iteration
+    // order of the hash map
+    let mut missing: Vec<_> = missing.into_iter().collect();
+    missing.sort_by(|(a, _), (b, _)| StructuralOrder.compare(a, b));
+
     let mut result = current.to_vec();
     for (term, n) in missing {
         for _ in 0..n {
@@ -315,7 +320,14 @@ fn reorder_premises<'a>(
     let mut new_order: Vec<usize> = (0..premises.len()).collect();
     let ordered: Vec<(&Literal, &LiteralInfo)> = {
         let mut v: Vec<_> = literals_info.iter().collect();
-        v.sort_unstable_by_key(|(_, info)| info.eliminator);
+        // Ties are broken by the literals themselves, to make the new order deterministic
+        v.sort_unstable_by(|(a_lit, a_info), (b_lit, b_info)| {
+            a_info
+                .eliminator
+                .cmp(&b_info.eliminator)
+                .then_with(|| a_lit.0.cmp(&b_lit.0))
+                .then_with(|| StructuralOrder.compare(a_lit.1, b_lit.1))
+        });
         v
     };
     for (lit, info) in ordered {
@@ -431,4 +443,24 @@ mod tests {
         let expected = ProofNode::from_commands(expected.commands);
         assert!(compare_nodes(&expected, &got));
     }
+
+    #[test]
+    fn test_weakening_clause_order() {
+        // The constants are declared in a different order in each run, so they are added to the
+        // pool in a different order. The added literals must still follow the structural order
+        let run = |declarations: &str| {
+            let proof = b"(step t1 (cl c a d b) :rule hole)";
+            let (_, proof, _) =
+                parse_instance(declarations.as_bytes(), proof, parser::Config::new()).unwrap();
+            let target = proof.commands[0].clause().to_vec();
+            let weakened = get_weakening_clause(&target[2..3], &target);
+            weakened.iter().map(ToString::to_string).collect::<Vec<_>>()
+        };
+        let first = run("(declare-const a Bool) (declare-const b Bool)
+            (declare-const c Bool) (declare-const d Bool)");
+        let second = run("(declare-const d Bool) (declare-const c Bool)
+            (declare-const b Bool) (declare-const a Bool)");
+        assert_eq!(first, ["d", "a", "b", "c"]);
+        assert_eq!(first, second);
+    }
 }
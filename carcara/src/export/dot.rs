@@ -0,0 +1,122 @@
+//! Exporting proofs to the DOT format, used by Graphviz.
+
+use crate::ast::{build_dependency_graph, DepNode, Proof, ProofCommand};
+use std::io;
+
+/// Writes the dependency graph of a proof in the DOT format. Each `assume` and `step` command is a
+/// node, labeled by its id and rule, and each premise reference is an edge from the premise to the
+/// step that uses it. The commands in each subproof are grouped in a cluster. See
+/// [`build_dependency_graph`] for more details on how the graph is computed.
+pub fn proof_to_dot(proof: &Proof, out: &mut dyn io::Write) -> io::Result<()> {
+    let graph = build_dependency_graph(proof);
+
+    writeln!(out, "digraph proof {{")?;
+    writeln!(out, "    node [shape=box];")?;
+
+    // The nodes of the graph are in the same order as the commands are yielded by `ProofIter`, so
+    // we can find them by traversing the proof in the same order
+    let mut next_node = 0;
+    let mut next_cluster = 0;
+    write_commands(
+        out,
+        &proof.commands,
+        &graph.nodes,
+        &mut next_node,
+        &mut next_cluster,
+        1,
+    )?;
+
+    for &(consumer, premise) in &graph.edges {
+        writeln!(out, "    n{} -> n{};", premise, consumer)?;
+    }
+    writeln!(out, "}}")
+}
+
+fn write_commands(
+    out: &mut dyn io::Write,
+    commands: &[ProofCommand],
+    nodes: &[DepNode],
+    next_node: &mut usize,
+    next_cluster: &mut usize,
+    indentation: usize,
+) -> io::Result<()> {
+    let indent = "    ".repeat(indentation);
+    for command in commands {
+        if let ProofCommand::Subproof(s) = command {
+            writeln!(out, "{}subgraph cluster_{} {{", indent, next_cluster)?;
+            *next_cluster += 1;
+            writeln!(out, "{}    label=\"{}\";", indent, escape(command.id()))?;
+            write_commands(
+                out,
+                &s.commands,
+                nodes,
+                next_node,
+                next_cluster,
+                indentation + 1,
+            )?;
+            writeln!(out, "{}}}", indent)?;
+        } else {
+            let node = &nodes[*next_node];
+            writeln!(
+                out,
+                "{}n{} [label=\"{}\\n{}\"];",
+                indent,
+                next_node,
+                escape(&node.id),
+                escape(&node.rule),
+            )?;
+            *next_node += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Escapes a string so it can be used inside a quoted DOT identifier.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ast::PrimitivePool, parser::tests::parse_proof};
+
+    #[test]
+    fn test_proof_to_dot() {
+        let proof = "
+            (assume h0 (= 0 0))
+            (assume h1 (= 1 1))
+            (step t2 (cl true) :rule blah :premises (h0 h1))
+            (anchor :step t3)
+                (step t3.t1 (cl true) :rule blah :premises (t2))
+                (step t3 (cl true) :rule blah :premises (t3.t1))
+            (step t4 (cl) :rule other :premises (t2 t3))
+        ";
+        let mut pool = PrimitivePool::new();
+        let proof = parse_proof(&mut pool, proof);
+
+        let mut out = Vec::new();
+        proof_to_dot(&proof, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.starts_with("digraph proof {\n"));
+        assert!(out.contains("    n0 [label=\"h0\\nassume\"];\n"));
+        assert!(out.contains("    subgraph cluster_0 {\n        label=\"t3\";\n"));
+        assert!(out.contains("        n3 [label=\"t3.t1\\nblah\"];\n"));
+        assert!(out.contains("        n4 [label=\"t3\\nblah\"];\n"));
+        assert!(out.contains("    n5 [label=\"t4\\nother\"];\n"));
+
+        let edges: Vec<_> = out.lines().filter(|l| l.contains("->")).collect();
+        assert_eq!(
+            edges,
+            [
+                "    n0 -> n2;",
+                "    n1 -> n2;",
+                "    n2 -> n3;",
+                "    n3 -> n4;",
+                "    n2 -> n5;",
+                "    n4 -> n5;",
+            ]
+        );
+    }
+}
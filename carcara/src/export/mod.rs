@@ -0,0 +1,4 @@
+//! Exporters that convert proofs into formats used by external tools.
+
+pub mod dot;
+pub mod smt2;
@@ -0,0 +1,172 @@
+//! Exporting the problem of a proof as an SMT-LIB script.
+
+use crate::ast::{ProblemPrelude, Proof, ProofCommand, Rc, Sort, Term};
+use indexmap::IndexSet;
+use std::{collections::HashMap, io};
+
+/// Writes an SMT-LIB script that reconstructs the original problem of a proof from its root-level
+/// `assume` commands. The script declares every sort and function symbol used by the assumptions,
+/// asserts each assumption, and ends with a `(check-sat)` command, so it can be given to any SMT
+/// solver to re-run the original query.
+///
+/// Since the proof doesn't include the problem's declarations, they are inferred from the free
+/// variables of the assumptions and from the sorts of those variables. The logic is always set to
+/// `ALL`.
+pub fn proof_to_smt2_problem(proof: &Proof, out: &mut dyn io::Write) -> io::Result<()> {
+    let assumptions: Vec<&Rc<Term>> = proof
+        .commands
+        .iter()
+        .filter_map(|c| match c {
+            ProofCommand::Assume { term, .. } => Some(term),
+            _ => None,
+        })
+        .collect();
+
+    let mut collector = DeclarationCollector::default();
+    let mut free_vars = IndexSet::new();
+    for term in &assumptions {
+        free_vars.extend(collector.free_vars(term));
+    }
+    let free_vars: Vec<(String, Rc<Term>)> = free_vars
+        .iter()
+        .map(|var| match var.as_ref() {
+            Term::Var(name, sort) => (name.clone(), sort.clone()),
+            _ => unreachable!(),
+        })
+        .collect();
+    for (_, sort) in &free_vars {
+        collector.add_sort(sort);
+    }
+
+    let mut prelude = ProblemPrelude::new();
+    prelude.sort_declarations = collector.sorts.into_iter().collect();
+    prelude.function_declarations = free_vars;
+    write!(out, "{}", prelude)?;
+
+    for term in assumptions {
+        writeln!(out, "(assert {:#})", term)?;
+    }
+    writeln!(out, "(check-sat)")
+}
+
+#[derive(Default)]
+struct DeclarationCollector {
+    free_vars_cache: HashMap<Rc<Term>, IndexSet<Rc<Term>>>,
+    sorts: IndexSet<(String, usize)>,
+}
+
+impl DeclarationCollector {
+    /// Returns the free variables of a term. Since the terms may come from any pool, bound
+    /// variables are removed by name, instead of by identity.
+    fn free_vars(&mut self, term: &Rc<Term>) -> IndexSet<Rc<Term>> {
+        if let Some(vars) = self.free_vars_cache.get(term) {
+            return vars.clone();
+        }
+        let vars = match term.as_ref() {
+            Term::Var(..) => IndexSet::from([term.clone()]),
+            Term::App(f, args) => {
+                let mut vars = self.free_vars(f);
+                for a in args {
+                    vars.extend(self.free_vars(a));
+                }
+                vars
+            }
+            Term::Op(_, args) | Term::ParamOp { args, .. } => {
+                let mut vars = IndexSet::new();
+                for a in args {
+                    vars.extend(self.free_vars(a));
+                }
+                vars
+            }
+            Term::Binder(_, bindings, inner) => {
+                for (_, sort) in bindings {
+                    self.add_sort(sort);
+                }
+                let mut vars = self.free_vars(inner);
+                vars.retain(|v| bindings.iter().all(|(name, _)| v.as_var() != Some(name)));
+                vars
+            }
+            Term::Let(bindings, inner) => {
+                let mut vars = self.free_vars(inner);
+                vars.retain(|v| bindings.iter().all(|(name, _)| v.as_var() != Some(name)));
+                for (_, value) in bindings {
+                    vars.extend(self.free_vars(value));
+                }
+                vars
+            }
+            Term::Const(_) | Term::Sort(_) => IndexSet::new(),
+        };
+        self.free_vars_cache.insert(term.clone(), vars.clone());
+        vars
+    }
+
+    /// Adds the user-declared sorts that appear in `sort` to the sort declarations.
+    fn add_sort(&mut self, sort: &Rc<Term>) {
+        match sort.as_sort() {
+            Some(Sort::Atom(name, args)) => {
+                self.sorts.insert((name.clone(), args.len()));
+                args.iter().for_each(|a| self.add_sort(a));
+            }
+            Some(Sort::Function(sorts)) => sorts.iter().for_each(|s| self.add_sort(s)),
+            Some(Sort::Array(x, y)) => {
+                self.add_sort(x);
+                self.add_sort(y);
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        ast::PrimitivePool,
+        parser::{self, Parser},
+    };
+
+    #[test]
+    fn test_proof_to_smt2_problem() {
+        let proof = "
+            (assume h1 (= (f a) b))
+            (assume h2 (forall ((x U)) (not (= (f x) b))))
+            (assume h3 (let ((c (g a))) (p c)))
+            (step t1 (cl) :rule hole :premises (h1 h2 h3))
+        ";
+        let mut pool = PrimitivePool::new();
+        let definitions = "
+            (declare-sort U 0)
+            (declare-sort V 0)
+            (declare-fun f (U) V)
+            (declare-fun g (U) Int)
+            (declare-fun p (Int) Bool)
+            (declare-const a U)
+            (declare-const b V)
+            (declare-const unused Bool)
+        ";
+        let mut parser = Parser::new(&mut pool, parser::Config::new(), definitions.as_bytes())
+            .and_then(|mut p| p.parse_problem().map(|_| p))
+            .unwrap();
+        parser.reset(proof.as_bytes()).unwrap();
+        let proof = parser.parse_proof().unwrap();
+
+        let mut out = Vec::new();
+        proof_to_smt2_problem(&proof, &mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.contains("(declare-sort U 0)\n"));
+        assert!(out.contains("(declare-fun f (U) V)\n"));
+        assert!(out.contains("(declare-fun a () U)\n"));
+        assert!(!out.contains("unused"));
+        assert!(!out.contains("(declare-fun x "));
+        assert!(!out.contains("(declare-fun c "));
+        assert!(out.ends_with("(check-sat)\n"));
+
+        let mut pool = PrimitivePool::new();
+        let problem = Parser::new(&mut pool, parser::Config::new(), out.as_bytes())
+            .and_then(|mut p| p.parse_problem())
+            .unwrap();
+        assert_eq!(problem.premises.len(), 3);
+        assert_eq!(out.matches("(assert ").count(), 3);
+    }
+}
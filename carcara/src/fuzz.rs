@@ -0,0 +1,321 @@
+//! Generation of random proofs for fuzzing the checker, enabled by the `arbitrary` feature. See
+//! [`FuzzInstance`].
+
+use crate::{ast::*, checker::ProofChecker};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use std::fmt;
+
+/// The maximum depth of the generated terms.
+const MAX_TERM_DEPTH: usize = 3;
+
+/// The maximum number of root-level commands in the generated proofs.
+const MAX_COMMANDS: usize = 24;
+
+/// The maximum number of commands in each generated subproof, excluding the step that ends it.
+const MAX_SUBPROOF_COMMANDS: usize = 4;
+
+/// The maximum number of literals in the conclusion clause of each generated step.
+const MAX_CLAUSE_LEN: usize = 4;
+
+/// A randomly generated problem and proof, together with the term pool that contains their terms.
+///
+/// Since terms are hash-consed, and the checker compares them by pointer, the terms of a proof must
+/// be added to the same pool that is later used to check it. Because of that, `Arbitrary` can't be
+/// implemented for terms or proofs by themselves, and is instead implemented for this type, which
+/// generates all three at once.
+///
+/// The generated proofs are meant to exercise the checker, so, while they are usually not valid,
+/// they are well-formed enough to not be trivially rejected: all terms are well-sorted, and are
+/// built from a small set of variables, so the same terms appear often; premises and discharged
+/// assumptions only refer to previous commands; and steps use the rules supported by the checker.
+///
+/// # Examples
+///
+/// ```
+/// # use carcara::{checker::*, fuzz::FuzzInstance};
+/// use arbitrary::{Arbitrary, Unstructured};
+///
+/// let data = [42; 256];
+/// let FuzzInstance { problem, proof, mut pool } =
+///     FuzzInstance::arbitrary(&mut Unstructured::new(&data)).unwrap();
+/// let result = ProofChecker::new(&mut pool, Config::new()).check(&problem, &proof);
+/// ```
+pub struct FuzzInstance {
+    pub problem: Problem,
+    pub proof: Proof,
+    pub pool: PrimitivePool,
+}
+
+impl fmt::Debug for FuzzInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FuzzInstance")
+            .field("premises", &self.problem.premises)
+            .field("proof", &self.proof)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Arbitrary<'a> for FuzzInstance {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let mut pool = PrimitivePool::new();
+        let mut generator = Generator::new(&mut pool);
+
+        let mut problem = Problem::new();
+        problem.prelude.function_declarations = generator.declarations();
+        for _ in 0..u.int_in_range(0..=4)? {
+            let premise = generator.bool_term(u, MAX_TERM_DEPTH)?;
+            problem.premises.insert(premise);
+        }
+
+        let commands = generator.commands(u, &problem)?;
+        let proof = Proof {
+            constant_definitions: Vec::new(),
+            commands,
+            comments: Default::default(),
+        };
+        Ok(Self { problem, proof, pool })
+    }
+}
+
+struct Generator<'p> {
+    pool: &'p mut PrimitivePool,
+    bool_vars: Vec<Rc<Term>>,
+    int_vars: Vec<Rc<Term>>,
+
+    /// The terms generated so far. New terms are often picked from here, so that terms are
+    /// repeated across commands, like in real proofs.
+    seen: Vec<Rc<Term>>,
+
+    rules: Vec<&'static str>,
+    next_context_id: usize,
+}
+
+impl<'p> Generator<'p> {
+    fn new(pool: &'p mut PrimitivePool) -> Self {
+        let bool_sort = pool.add(Term::Sort(Sort::Bool));
+        let int_sort = pool.add(Term::Sort(Sort::Int));
+        let bool_vars = ["p", "q", "r"]
+            .map(|name| pool.add(Term::new_var(name, bool_sort.clone())))
+            .into();
+        let int_vars = ["a", "b", "c"]
+            .map(|name| pool.add(Term::new_var(name, int_sort.clone())))
+            .into();
+        let mut rules = ProofChecker::supported_rules();
+        // We also include a rule that doesn't exist, to exercise the handling of unknown rules
+        rules.push("not_a_rule");
+        Self {
+            pool,
+            bool_vars,
+            int_vars,
+            seen: Vec::new(),
+            rules,
+            next_context_id: 0,
+        }
+    }
+
+    fn declarations(&mut self) -> Vec<(String, Rc<Term>)> {
+        self.bool_vars
+            .iter()
+            .chain(&self.int_vars)
+            .map(|var| match var.as_ref() {
+                Term::Var(name, sort) => (name.clone(), sort.clone()),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    fn bool_term(&mut self, u: &mut Unstructured, depth: usize) -> Result<Rc<Term>> {
+        if !self.seen.is_empty() && u.ratio(1, 3)? {
+            return Ok(u.choose(&self.seen)?.clone());
+        }
+        let term = if depth == 0 || u.ratio(1, 4)? {
+            match u.int_in_range(0..=4)? {
+                0 => self.pool.bool_true(),
+                1 => self.pool.bool_false(),
+                _ => u.choose(&self.bool_vars)?.clone(),
+            }
+        } else {
+            let depth = depth - 1;
+            let op = *u.choose(&[
+                Operator::Not,
+                Operator::And,
+                Operator::Or,
+                Operator::Implies,
+                Operator::Equals,
+                Operator::Ite,
+                Operator::LessEq,
+                Operator::LessThan,
+            ])?;
+            let args = match op {
+                Operator::Not => vec![self.bool_term(u, depth)?],
+                Operator::And | Operator::Or => {
+                    let n = u.int_in_range(2..=3)?;
+                    (0..n)
+                        .map(|_| self.bool_term(u, depth))
+                        .collect::<Result<_>>()?
+                }
+                Operator::Implies => vec![self.bool_term(u, depth)?, self.bool_term(u, depth)?],
+                Operator::Equals if u.arbitrary()? => {
+                    vec![self.bool_term(u, depth)?, self.bool_term(u, depth)?]
+                }
+                Operator::Ite => vec![
+                    self.bool_term(u, depth)?,
+                    self.bool_term(u, depth)?,
+                    self.bool_term(u, depth)?,
+                ],
+                _ => vec![self.int_term(u, depth)?, self.int_term(u, depth)?],
+            };
+            self.pool.add(Term::Op(op, args))
+        };
+        self.seen.push(term.clone());
+        Ok(term)
+    }
+
+    fn int_term(&mut self, u: &mut Unstructured, depth: usize) -> Result<Rc<Term>> {
+        if depth == 0 || u.ratio(1, 3)? {
+            return Ok(if u.arbitrary()? {
+                u.choose(&self.int_vars)?.clone()
+            } else {
+                self.pool.add(Term::new_int(u.int_in_range(-3..=3)?))
+            });
+        }
+        let depth = depth - 1;
+        let op = *u.choose(&[Operator::Add, Operator::Sub, Operator::Mult, Operator::Ite])?;
+        let args = match op {
+            Operator::Ite => vec![
+                self.bool_term(u, depth)?,
+                self.int_term(u, depth)?,
+                self.int_term(u, depth)?,
+            ],
+            _ => vec![self.int_term(u, depth)?, self.int_term(u, depth)?],
+        };
+        Ok(self.pool.add(Term::Op(op, args)))
+    }
+
+    fn commands(&mut self, u: &mut Unstructured, problem: &Problem) -> Result<Vec<ProofCommand>> {
+        let num_commands = u.int_in_range(1..=MAX_COMMANDS)?;
+        let mut commands = Vec::with_capacity(num_commands);
+        for i in 0..num_commands {
+            let command = match u.int_in_range(0..=5)? {
+                0 => {
+                    let term = match problem.premises.iter().nth(u.choose_index(4)?) {
+                        Some(premise) => premise.clone(),
+                        None => self.bool_term(u, MAX_TERM_DEPTH)?,
+                    };
+                    ProofCommand::Assume { id: format!("h{}", i), term }
+                }
+                1 => self.subproof(u, i)?,
+                _ => {
+                    let premises = premises(u, &[i])?;
+                    ProofCommand::Step(self.step(u, format!("t{}", i), premises, Vec::new())?)
+                }
+            };
+            commands.push(command);
+        }
+        Ok(commands)
+    }
+
+    /// Generates a subproof at the root-level index `index`.
+    fn subproof(&mut self, u: &mut Unstructured, index: usize) -> Result<ProofCommand> {
+        let mut args = Vec::new();
+        if u.arbitrary()? {
+            let int_sort = self.pool.add(Term::Sort(Sort::Int));
+            args.push(AnchorArg::Variable((format!("x{}", index), int_sort)));
+        }
+
+        let num_commands = u.int_in_range(0..=MAX_SUBPROOF_COMMANDS)?;
+        let mut commands = Vec::with_capacity(num_commands + 1);
+        let mut assumptions = Vec::new();
+        for i in 0..num_commands {
+            let id = format!("t{}.t{}", index, i);
+            let command = if u.ratio(1, 3)? {
+                assumptions.push((1, i));
+                let term = self.bool_term(u, MAX_TERM_DEPTH)?;
+                ProofCommand::Assume { id, term }
+            } else {
+                let premises = premises(u, &[index, i])?;
+                ProofCommand::Step(self.step(u, id, premises, Vec::new())?)
+            };
+            commands.push(command);
+        }
+
+        let discharge = if u.ratio(3, 4)? {
+            assumptions
+        } else {
+            Vec::new()
+        };
+        let end_step = self.step(u, format!("t{}", index), Vec::new(), discharge)?;
+        commands.push(ProofCommand::Step(end_step));
+
+        let context_id = self.next_context_id;
+        self.next_context_id += 1;
+        Ok(ProofCommand::Subproof(Subproof {
+            commands,
+            args,
+            context_id,
+        }))
+    }
+
+    fn step(
+        &mut self,
+        u: &mut Unstructured,
+        id: String,
+        premises: Vec<(usize, usize)>,
+        discharge: Vec<(usize, usize)>,
+    ) -> Result<ProofStep> {
+        let clause = (0..u.int_in_range(0..=MAX_CLAUSE_LEN)?)
+            .map(|_| self.bool_term(u, MAX_TERM_DEPTH))
+            .collect::<Result<_>>()?;
+        let rule = (*u.choose(&self.rules)?).to_owned();
+        let mut args = Vec::new();
+        for _ in 0..u.int_in_range(0..=2)? {
+            args.push(match u.arbitrary()? {
+                true => self.bool_term(u, 1)?,
+                false => self.int_term(u, 1)?,
+            });
+        }
+        Ok(ProofStep {
+            id,
+            clause,
+            rule,
+            premises,
+            args,
+            discharge,
+        })
+    }
+}
+
+/// Generates the premises of a step. `lengths` contains, for each depth of the subproof stack,
+/// the number of commands before the step in that depth.
+fn premises(u: &mut Unstructured, lengths: &[usize]) -> Result<Vec<(usize, usize)>> {
+    let mut premises = Vec::new();
+    for _ in 0..u.int_in_range(0..=3)? {
+        let depth = u.choose_index(lengths.len())?;
+        if lengths[depth] > 0 {
+            premises.push((depth, u.choose_index(lengths[depth])?));
+        }
+    }
+    Ok(premises)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::checker::Config;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn test_checker_does_not_panic() {
+        let mut rng = StdRng::seed_from_u64(0);
+        for _ in 0..200 {
+            let mut data = vec![0u8; 1024];
+            rng.fill(data.as_mut_slice());
+            let FuzzInstance { problem, proof, mut pool } =
+                FuzzInstance::arbitrary(&mut Unstructured::new(&data)).unwrap();
+            for config in [Config::new(), Config::new().ignore_unknown_rules(true)] {
+                // We only care that the checker doesn't panic, not about the result
+                let _ = ProofChecker::new(&mut pool, config).check(&problem, &proof);
+            }
+        }
+    }
+}
@@ -35,18 +35,28 @@
 
 #[macro_use]
 pub mod ast;
+#[cfg(feature = "async")]
+mod async_check;
 pub mod benchmarking;
 pub mod checker;
 mod drup;
 pub mod elaborator;
+pub mod export;
+#[cfg(feature = "arbitrary")]
+pub mod fuzz;
 pub mod parser;
 mod resolution;
 mod utils;
 
-use crate::benchmarking::{CollectResults, OnlineBenchmarkResults, RunMeasurement};
+#[cfg(feature = "async")]
+pub use async_check::{check_async, CheckFuture};
+
+use crate::benchmarking::{CollectResults, Metrics, OnlineBenchmarkResults, RunMeasurement};
 use checker::{error::CheckerError, CheckerStatistics};
 use parser::{ParserError, Position};
-use std::io;
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 use thiserror::Error;
 
@@ -61,16 +71,23 @@ fn wrap_parser_error_message(e: &ParserError, pos: &Position) -> String {
     }
 }
 
+/// The error type for all operations in Carcara, which unifies the errors that can happen while
+/// reading, parsing, or checking a proof. Its `Display` implementation includes the location of the
+/// error, that is, the line and column for parser errors, and the step id and rule for checker
+/// errors. For IO and checker errors, the underlying error is not part of this message, and is
+/// instead exposed through `std::error::Error::source`, so that printers that walk the error chain
+/// don't repeat it.
 #[derive(Debug, Error)]
 pub enum Error {
-    #[error("IO error: {0}")]
+    #[error("IO error")]
     Io(#[from] io::Error),
 
     #[error("{}", wrap_parser_error_message(.0, .1))]
     Parser(ParserError, Position),
 
-    #[error("checking failed on step '{step}' with rule '{rule}': {inner}")]
+    #[error("checking failed on step '{step}' with rule '{rule}'")]
     Checker {
+        #[source]
         inner: CheckerError,
         rule: String,
         step: String,
@@ -80,6 +97,9 @@ pub enum Error {
     // checker errors, so we model it as a different variant
     #[error("checker error: proof does not conclude empty clause")]
     DoesNotReachEmptyClause,
+
+    #[error("step '{0}' was not found in the proof")]
+    StepNotFound(String),
 }
 
 pub fn check<T: io::BufRead>(
@@ -201,6 +221,122 @@ pub fn check_parallel<T: io::BufRead>(
     }
 }
 
+/// Checks a proof while it is parsed, keeping only the commands that may still be needed in memory.
+///
+/// The proof file is read twice. The first pass only tokenizes it, to find the last command that
+/// references each step as a premise. The second pass parses and checks each root-level command
+/// (or whole subproof) in turn, and evicts a step from memory as soon as the last command that
+/// references it is checked. `assume` commands are kept for the whole run. Note that terms are
+/// still kept in the term pool, so this only bounds the memory used by the proof commands
+/// themselves.
+///
+/// Since the proof is never available as a whole, the `expand_distinct` checker option is ignored,
+/// and skipped steps and warnings are not recorded. Returns `true` if the proof contains holes,
+/// along with a [`StreamingSummary`](checker::StreamingSummary) with the number of commands that
+/// were held in memory at the same time.
+pub fn check_file_streaming<P: AsRef<Path>>(
+    problem_path: P,
+    proof_path: P,
+    parser_config: parser::Config,
+    checker_config: checker::Config,
+) -> Result<(bool, checker::StreamingSummary), Error> {
+    let last_uses = parser::find_last_premise_uses(BufReader::new(File::open(&proof_path)?))?;
+
+    let mut pool = ast::PrimitivePool::new();
+    let problem = BufReader::new(File::open(problem_path)?);
+    let mut parser = parser::Parser::new(&mut pool, parser_config, problem)?;
+    let problem = parser.parse_problem()?;
+    parser.reset(BufReader::new(File::open(proof_path)?))?;
+
+    let mut checker = checker::StreamingChecker::new(checker_config, last_uses);
+    let mut state = parser.start_proof(false)?;
+    while let Some(command) = parser.parse_root_command(&mut state)? {
+        checker.check_command(parser.pool(), &problem.premises, command)?;
+    }
+    parser.finish_proof(state)?;
+    checker.finish()
+}
+
+/// A summary of checking a single instance in a batch, as returned by [`check_batch`].
+#[derive(Debug)]
+pub struct FileSummary {
+    /// The path of the proof file.
+    pub proof_path: PathBuf,
+
+    /// The result of parsing and checking the instance. On success, this is `true` if the proof
+    /// contains holes.
+    pub result: CarcaraResult<bool>,
+
+    /// The total time spent parsing and checking the instance.
+    pub time: Duration,
+
+    /// The number of `step` commands in the proof, including the ones inside subproofs. This is
+    /// zero if the proof could not be parsed.
+    pub num_steps: usize,
+
+    /// The rule of the slowest checked command, and the time spent checking it. This is `None` if
+    /// no command was checked.
+    pub slowest_rule: Option<(String, Duration)>,
+}
+
+/// Parses and checks each instance, given as pairs of problem and proof paths, and returns a
+/// [`FileSummary`] for each of them. An error in one instance does not prevent the others from
+/// being checked, and is instead recorded in its summary.
+pub fn check_batch(
+    instances: &[(PathBuf, PathBuf)],
+    parser_config: parser::Config,
+    checker_config: checker::Config,
+) -> Vec<FileSummary> {
+    // The same pool is reused for every instance, so its tables don't have to be reallocated
+    let mut pool = ast::PrimitivePool::new();
+    instances
+        .iter()
+        .map(|(problem_path, proof_path)| {
+            let file_name = proof_path.to_string_lossy();
+            let mut stats = CheckerStatistics {
+                file_name: &file_name,
+                polyeq_time: Duration::ZERO,
+                assume_time: Duration::ZERO,
+                assume_core_time: Duration::ZERO,
+                results: OnlineBenchmarkResults::new(),
+            };
+            let mut num_steps = 0;
+
+            let time = Instant::now();
+            let result = (|| {
+                pool.clear();
+                let (problem, proof) = parser::parse_instance_with_pool(
+                    BufReader::new(File::open(problem_path)?),
+                    BufReader::new(File::open(proof_path)?),
+                    parser_config,
+                    &mut pool,
+                )?;
+                num_steps = proof.iter().filter(|c| c.is_step()).count();
+                checker::ProofChecker::new(&mut pool, checker_config.clone())
+                    .check_with_stats(&problem, &proof, &mut stats)
+            })();
+            let time = time.elapsed();
+
+            let slowest_rule = stats
+                .results
+                .step_time_by_file()
+                .get(file_name.as_ref())
+                .filter(|metrics| !metrics.is_empty())
+                .map(|metrics| {
+                    let (id, time) = metrics.max();
+                    (id.rule.to_string(), *time)
+                });
+            FileSummary {
+                proof_path: proof_path.clone(),
+                result,
+                time,
+                num_steps,
+                slowest_rule,
+            }
+        })
+        .collect()
+}
+
 pub fn check_and_elaborate<T: io::BufRead>(
     problem: T,
     proof: T,
@@ -58,7 +58,7 @@ pub enum ParserError {
     EmptySequence,
 
     /// An error in sort checking.
-    #[error("sort error: {0}")]
+    #[error(transparent)]
     SortError(#[from] SortError),
 
     /// Expected `BvSort`
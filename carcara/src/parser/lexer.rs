@@ -1,10 +1,13 @@
 //! A lexer for the SMT-LIB and Alethe formats.
 
-use crate::{parser::ParserError, utils::is_symbol_character, CarcaraResult, Error};
+use crate::{
+    ast::BitVecRadix, parser::ParserError, utils::is_symbol_character, CarcaraResult, Error,
+};
 use rug::{ops::Pow, Integer, Rational};
 use std::{
     io::{self, BufRead},
     str::FromStr,
+    vec::IntoIter,
 };
 
 /// A token in the SMT-LIB and Alethe formats.
@@ -32,8 +35,12 @@ pub enum Token {
     /// A decimal numeral literal.
     Decimal(Rational),
 
-    /// A bitvector literal.
-    Bitvector { value: Integer, width: u64 },
+    /// A bitvector literal, in either binary or hexadecimal notation.
+    Bitvector {
+        value: Integer,
+        width: u64,
+        radix: BitVecRadix,
+    },
 
     /// A string literal.
     String(String),
@@ -150,9 +157,13 @@ pub type Position = (usize, usize);
 /// A lexer for the SMT-LIB and Alethe formats.
 pub struct Lexer<R> {
     input: R,
-    current_line: Option<std::vec::IntoIter<char>>,
+    current_line: Option<IntoIter<char>>,
     current_char: Option<char>,
     position: Position,
+
+    /// The comments read since the last call to [`Lexer::take_comments`]. This is `None` if
+    /// comments are not being kept.
+    comments: Option<Vec<String>>,
 }
 
 impl<R: BufRead> Lexer<R> {
@@ -168,6 +179,7 @@ impl<R: BufRead> Lexer<R> {
                 current_line: None,
                 current_char: None,
                 position: (0, 0),
+                comments: None,
             })
         } else {
             let mut line = buf.chars().collect::<Vec<_>>().into_iter();
@@ -177,10 +189,26 @@ impl<R: BufRead> Lexer<R> {
                 current_line: Some(line),
                 current_char,
                 position: (1, 1),
+                comments: None,
             })
         }
     }
 
+    /// Sets whether the lexer should keep the comments it reads, instead of discarding them. The
+    /// kept comments can be retrieved with [`Lexer::take_comments`].
+    pub fn set_keep_comments(&mut self, value: bool) {
+        self.comments = value.then(Vec::new);
+    }
+
+    /// Returns the comments read since the last call to this method, without the leading `;`
+    /// characters. Returns an empty vector if comments are not being kept.
+    pub fn take_comments(&mut self) -> Vec<String> {
+        self.comments
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
     /// Advances the lexer by one character, and returns the previous `current_char`.
     fn next_char(&mut self) -> io::Result<Option<char>> {
         // If there are no more characters in the current line, go to the next line
@@ -249,6 +277,15 @@ impl<R: BufRead> Lexer<R> {
     fn consume_whitespace(&mut self) -> io::Result<()> {
         self.drop_while_whitespace()?;
         while self.current_char == Some(';') {
+            if let Some(comments) = &mut self.comments {
+                let rest: String = self
+                    .current_line
+                    .iter()
+                    .flat_map(IntoIter::as_slice)
+                    .collect();
+                let text = rest.trim_start_matches(';').trim();
+                comments.push(text.to_owned());
+            }
             self.next_line()?;
             self.next_char()?;
             self.drop_while_whitespace()?;
@@ -339,9 +376,9 @@ impl<R: BufRead> Lexer<R> {
     /// no digits are provided.
     fn read_bitvector(&mut self) -> CarcaraResult<Token> {
         self.next_char()?; // Consume `#`
-        let (base, bits_per_char) = match self.next_char()? {
-            Some('b') => (2, 1),
-            Some('x') => (16, 4),
+        let (base, bits_per_char, radix) = match self.next_char()? {
+            Some('b') => (2, 1, BitVecRadix::Binary),
+            Some('x') => (16, 4, BitVecRadix::Hexadecimal),
             None => return Err(Error::Parser(ParserError::EmptyBitvector, self.position)),
             Some(other) => {
                 return Err(Error::Parser(
@@ -357,7 +394,7 @@ impl<R: BufRead> Lexer<R> {
 
         let width = s.len() as u64 * bits_per_char;
         let value = Integer::from_str_radix(&s, base).unwrap();
-        Ok(Token::Bitvector { value, width })
+        Ok(Token::Bitvector { value, width, radix })
     }
 
     /// Reads an integer or decimal numerical literal.
@@ -616,13 +653,26 @@ mod tests {
     fn test_bitvectors() {
         let input = "#b101010 #xdeadbeef #b1 #x0";
         let expected = vec![
-            Token::Bitvector { value: 42.into(), width: 6 },
+            Token::Bitvector {
+                value: 42.into(),
+                width: 6,
+                radix: BitVecRadix::Binary,
+            },
             Token::Bitvector {
                 value: 0xdeadbeefu64.into(),
                 width: 32,
+                radix: BitVecRadix::Hexadecimal,
+            },
+            Token::Bitvector {
+                value: 1.into(),
+                width: 1,
+                radix: BitVecRadix::Binary,
+            },
+            Token::Bitvector {
+                value: 0.into(),
+                width: 4,
+                radix: BitVecRadix::Hexadecimal,
             },
-            Token::Bitvector { value: 1.into(), width: 1 },
-            Token::Bitvector { value: 0.into(), width: 4 },
         ];
         assert_eq!(expected, lex_all(input));
 
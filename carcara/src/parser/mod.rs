@@ -17,7 +17,7 @@ use crate::{
 use error::assert_num_args;
 use indexmap::{IndexMap, IndexSet};
 use rug::{Integer, Rational};
-use std::{io::BufRead, str::FromStr};
+use std::{collections::HashMap, io::BufRead, str::FromStr};
 
 use self::error::assert_indexed_op_args_value;
 
@@ -49,6 +49,10 @@ pub struct Config {
     /// If `true`, the parser will parse arguments to the `hole` rule, expecting them to be valid
     /// terms.
     pub parse_hole_args: bool,
+
+    /// If `true`, the parser will keep the comments that precede each root-level command in the
+    /// proof, storing them in [`Proof::comments`]. Comments inside subproofs are discarded.
+    pub keep_comments: bool,
 }
 
 impl Config {
@@ -85,6 +89,104 @@ pub fn parse_instance_with_pool<T: BufRead>(
     Ok((problem, proof))
 }
 
+/// Parses an SMT problem instance and several proofs of it that are concatenated in the same input,
+/// such as the output of multiple `get-proof` commands.
+///
+/// Each proof must be preceded by the satisfiability result `unsat`, which is used to separate the
+/// proofs. The proofs are all parsed using the same term pool, so terms that appear in more than
+/// one of them are only allocated once.
+pub fn parse_instance_multi<T: BufRead>(
+    problem: T,
+    proofs: T,
+    config: Config,
+) -> CarcaraResult<(Problem, Vec<Proof>, PrimitivePool)> {
+    let mut pool = PrimitivePool::new();
+    let mut parser = Parser::new(&mut pool, config, problem)?;
+    let problem = parser.parse_problem()?;
+    parser.reset(proofs)?;
+    let proofs = parser.parse_proofs()?;
+    Ok((problem, proofs, pool))
+}
+
+/// Scans a proof in the Alethe format, without parsing its terms, and finds where each command is
+/// last referenced. Returns a map from each referenced command id to the index of the last
+/// root-level command that references it as a premise or discharged assumption. A reference made
+/// inside a subproof is attributed to the root-level subproof command that contains it.
+///
+/// Since the terms are not parsed, this does not validate the proof, and an id may be mapped even
+/// if it is not the id of a root-level command.
+pub(crate) fn find_last_premise_uses<T: BufRead>(
+    proof: T,
+) -> CarcaraResult<HashMap<String, usize>> {
+    let mut lexer = Lexer::new(proof)?;
+    let mut result = HashMap::new();
+
+    // The ids of the steps that will end each open subproof
+    let mut open_subproofs: Vec<String> = Vec::new();
+
+    // The index of the root-level command that is currently being read
+    let mut current = 0;
+    let mut num_root_commands = 0;
+    let mut previous = Token::Eof;
+    loop {
+        let (token, _) = lexer.next_token()?;
+        match (&previous, &token) {
+            (_, Token::Eof) => break,
+            (Token::OpenParen, Token::ReservedWord(Reserved::Assume | Reserved::Step)) => {
+                let (id, _) = lexer.next_token()?;
+                let Token::Symbol(id) = id else { continue };
+                if open_subproofs.is_empty() {
+                    current = num_root_commands;
+                    num_root_commands += 1;
+                } else if open_subproofs.last() == Some(&id) {
+                    open_subproofs.pop();
+                    if open_subproofs.is_empty() {
+                        num_root_commands += 1;
+                    }
+                }
+            }
+            (Token::Keyword(k), Token::Symbol(id)) if k == "step" => {
+                if open_subproofs.is_empty() {
+                    current = num_root_commands;
+                }
+                open_subproofs.push(id.clone());
+            }
+            (Token::Keyword(k), Token::OpenParen) if k == "premises" || k == "discharge" => {
+                while let (Token::Symbol(id), _) = lexer.next_token()? {
+                    result.insert(id, current);
+                }
+            }
+            _ => (),
+        }
+        previous = token;
+    }
+    Ok(result)
+}
+
+/// Prints a proof and parses it back, returning the re-parsed proof.
+///
+/// The problem prelude is printed and parsed again as well, so that all declarations are available
+/// when parsing the proof. Since the same term pool is used for both the original and the re-parsed
+/// proof, terms are shared between the two, and the result can be compared with the original
+/// proof using `==`. This is mostly useful for testing the printer and the parser against each
+/// other.
+pub fn roundtrip(
+    pool: &mut PrimitivePool,
+    prelude: &ProblemPrelude,
+    proof: &Proof,
+) -> CarcaraResult<Proof> {
+    let problem = prelude.to_string();
+    let printed = print_proof_to_string(pool, prelude, proof, false);
+
+    // We need to parse the arguments of `hole` steps, otherwise they would be dropped
+    let config = Config {
+        parse_hole_args: true,
+        ..Config::new()
+    };
+    parse_instance_with_pool(problem.as_bytes(), printed.as_bytes(), config, pool)
+        .map(|(_, proof)| proof)
+}
+
 /// A function definition, from a `define-fun` command.
 struct FunctionDef {
     params: Vec<SortedVar>,
@@ -139,6 +241,30 @@ struct ParserState {
     step_ids: HashMapStack<HashCache<String>, usize>,
 }
 
+/// The state of a proof that is being parsed one root-level command at a time. See
+/// [`Parser::start_proof`].
+pub(crate) struct ProofParsingState {
+    /// Whether an `unsat` token after the first command ends the proof.
+    stop_at_unsat: bool,
+
+    /// The subproofs that are currently open, each with the id of the step that will end it.
+    stack: Vec<(Subproof, String)>,
+
+    num_root_commands: usize,
+    next_subproof_context_id: usize,
+    finished_assumes: bool,
+    constant_definitions: Vec<(String, Rc<Term>)>,
+
+    /// Some proofs may include an extra set of surrounding parentheses around the whole proof.
+    has_extra_surrounding_parens: bool,
+    read_first_token: bool,
+
+    /// The comments read since the last root-level command, and the comments attached to each
+    /// root-level command so far, by command id.
+    pending_comments: Vec<String>,
+    comments: HashMap<String, Vec<String>>,
+}
+
 /// A parser for the Alethe proof format.
 pub struct Parser<'a, R> {
     pool: &'a mut PrimitivePool,
@@ -157,6 +283,7 @@ impl<'a, R: BufRead> Parser<'a, R> {
     /// This operation can fail if there is an IO or lexer error on the first token.
     pub fn new(pool: &'a mut PrimitivePool, config: Config, input: R) -> CarcaraResult<Self> {
         let mut lexer = Lexer::new(input)?;
+        lexer.set_keep_comments(config.keep_comments);
         let (current_token, current_position) = lexer.next_token()?;
         Ok(Parser {
             pool,
@@ -174,6 +301,7 @@ impl<'a, R: BufRead> Parser<'a, R> {
     /// including all function, constant and sort declarations.
     pub fn reset(&mut self, input: R) -> CarcaraResult<()> {
         let mut lexer = Lexer::new(input)?;
+        lexer.set_keep_comments(self.config.keep_comments);
         let (current_token, current_position) = lexer.next_token()?;
         self.lexer = lexer;
         self.current_token = current_token;
@@ -181,6 +309,11 @@ impl<'a, R: BufRead> Parser<'a, R> {
         Ok(())
     }
 
+    /// Returns the term pool used by the parser.
+    pub(crate) fn pool(&mut self) -> &mut PrimitivePool {
+        self.pool
+    }
+
     /// Advances the parser one token, and returns the previous `current_token`.
     fn next_token(&mut self) -> CarcaraResult<(Token, Position)> {
         use std::mem::replace;
@@ -804,61 +937,115 @@ impl<'a, R: BufRead> Parser<'a, R> {
     /// should already be in the parser state. Note that the `premises` field in the proof will not
     /// be set.
     pub fn parse_proof(&mut self) -> CarcaraResult<Proof> {
-        // To avoid stack overflows in proofs with many nested subproofs, we parse the subproofs
-        // iteratively, instead of recursively. Therefore, we need to manually keep a stack.
-        //
-        // Each frame of the stack stores the subproof that is being constructed, and the id of the
-        // step that will end it. The first frame of the stack represents the root proof, so every
-        // field except for the subproof commands is irrelevant.
-        let mut stack: Vec<(Subproof, String)> = vec![(Subproof::default(), String::new())];
-
-        let mut next_subproof_context_id = 0;
-
-        let mut finished_assumes = false;
+        self.parse_proof_impl(false)
+    }
 
-        let mut constant_definitions = Vec::new();
+    /// Parses several proofs in the Alethe format that are concatenated in the input, each one
+    /// preceded by an `unsat` token. As in [`Parser::parse_proof`], the `unsat` token is optional
+    /// for the first proof. Step ids are scoped to each proof, so different proofs may reuse the
+    /// same ids.
+    pub fn parse_proofs(&mut self) -> CarcaraResult<Vec<Proof>> {
+        let mut proofs = vec![self.parse_proof_impl(true)?];
+        while self.current_token != Token::Eof {
+            self.state.step_ids = HashMapStack::new();
+            proofs.push(self.parse_proof_impl(true)?);
+        }
+        Ok(proofs)
+    }
 
-        // Some proofs may include an extra set of surrounding parentheses around the whole proof
-        let mut has_extra_surrounding_parens = false;
-        let mut read_first_token = false;
+    /// Parses a single proof. If `stop_at_unsat` is `true`, an `unsat` token after the first
+    /// command marks the start of the next proof, and ends the current one.
+    fn parse_proof_impl(&mut self, stop_at_unsat: bool) -> CarcaraResult<Proof> {
+        let mut state = self.start_proof(stop_at_unsat)?;
+        let mut commands = Vec::new();
+        while let Some(command) = self.parse_root_command(&mut state)? {
+            commands.push(command);
+        }
+        let comments = std::mem::take(&mut state.comments);
+        let constant_definitions = self.finish_proof(state)?;
+        Ok(Proof {
+            constant_definitions,
+            commands,
+            comments,
+        })
+    }
 
+    /// Starts parsing a proof, one root-level command at a time. The commands are then parsed by
+    /// calling [`Parser::parse_root_command`] until it returns `None`, and the proof is finished
+    /// with [`Parser::finish_proof`].
+    pub(crate) fn start_proof(&mut self, stop_at_unsat: bool) -> CarcaraResult<ProofParsingState> {
         // Some solvers print the satisfiability result (unsat) together with the proof. To save the
         // user from having to remove this, we consume this first "unsat" token if it exists
         if self.current_token == Token::Symbol("unsat".into()) {
             self.next_token()?;
         }
+        Ok(ProofParsingState {
+            stop_at_unsat,
+            stack: Vec::new(),
+            num_root_commands: 0,
+            next_subproof_context_id: 0,
+            finished_assumes: false,
+            constant_definitions: Vec::new(),
+            has_extra_surrounding_parens: false,
+            read_first_token: false,
+            pending_comments: Vec::new(),
+            comments: HashMap::new(),
+        })
+    }
+
+    /// Parses the next command in the root proof. If this command is a subproof, all of its
+    /// commands are parsed. Returns `None` if there are no more commands in the proof.
+    pub(crate) fn parse_root_command(
+        &mut self,
+        state: &mut ProofParsingState,
+    ) -> CarcaraResult<Option<ProofCommand>> {
+        let unsat = Token::Symbol("unsat".into());
 
-        while self.current_token != Token::Eof && self.current_token != Token::CloseParen {
+        // To avoid stack overflows in proofs with many nested subproofs, we parse the subproofs
+        // iteratively, instead of recursively. Therefore, we need to manually keep a stack of the
+        // open subproofs
+        while self.current_token != Token::Eof
+            && self.current_token != Token::CloseParen
+            && !(state.stop_at_unsat && self.current_token == unsat)
+        {
+            // Since the current token is the opening parenthesis of the next command, all comments
+            // before that command were already read
+            let comments = self.lexer.take_comments();
+            if state.stack.is_empty() {
+                state.pending_comments.extend(comments);
+            }
             self.expect_token(Token::OpenParen)?;
 
-            if !read_first_token && self.current_token == Token::OpenParen
+            if !state.read_first_token && self.current_token == Token::OpenParen
                 || self.current_token == Token::CloseParen
             {
-                has_extra_surrounding_parens = true;
-                read_first_token = true;
+                state.has_extra_surrounding_parens = true;
+                state.read_first_token = true;
                 continue;
             }
-            read_first_token = true;
+            state.read_first_token = true;
 
             let (token, position) = self.next_token()?;
 
             let (id, command) = match token {
                 Token::ReservedWord(Reserved::Assume) => {
                     let (id, term) = self.parse_assume_command()?;
-                    if stack.len() == 1 && finished_assumes {
+                    if state.stack.is_empty() && state.finished_assumes {
                         log::warn!("`assume` command '{}' appears after `step` commands", &id);
                     }
                     (id.clone(), ProofCommand::Assume { id, term })
                 }
                 Token::ReservedWord(Reserved::Step) => {
-                    finished_assumes = true;
+                    state.finished_assumes = true;
                     let step = self.parse_step_command()?;
                     (step.id.clone(), ProofCommand::Step(step))
                 }
                 Token::ReservedWord(Reserved::DefineFun) => {
                     let (name, func_def) = self.parse_define_fun()?;
                     if func_def.params.is_empty() {
-                        constant_definitions.push((name.clone(), func_def.body.clone()));
+                        state
+                            .constant_definitions
+                            .push((name.clone(), func_def.body.clone()));
                     }
                     self.state.function_defs.insert(name, func_def);
                     continue;
@@ -867,19 +1054,18 @@ impl<'a, R: BufRead> Parser<'a, R> {
                     let (end_step_id, args) = self.parse_anchor_command()?;
 
                     // When we encounter an `anchor` command, we push a new scope into the step ids
-                    // symbol table, a fresh commands vector into the commands stack for the
-                    // subproof to fill, and the `anchor` data (end step and arguments) into their
-                    // respective stacks. All of this will be popped off at the end of the subproof.
-                    // We don't need to push a new scope into the symbol table because
+                    // symbol table, and a fresh subproof into the stack, together with the id of
+                    // the step that will end it. All of this will be popped off at the end of the
+                    // subproof. We don't need to push a new scope into the symbol table because
                     // `Parser::parse_anchor_command` already does that for us
                     self.state.step_ids.push_scope();
                     let subproof = Subproof {
                         commands: Vec::new(),
                         args,
-                        context_id: next_subproof_context_id,
+                        context_id: state.next_subproof_context_id,
                     };
-                    stack.push((subproof, end_step_id));
-                    next_subproof_context_id += 1;
+                    state.stack.push((subproof, end_step_id));
+                    state.next_subproof_context_id += 1;
                     continue;
                 }
                 _ => {
@@ -894,14 +1080,20 @@ impl<'a, R: BufRead> Parser<'a, R> {
                 ));
             }
 
-            let (top_subproof, top_end_step) = stack.last_mut().unwrap();
-            top_subproof.commands.push(command);
-            if top_end_step == id.as_ref() {
+            let mut command = command;
+            if let Some((top_subproof, top_end_step)) = state.stack.last_mut() {
+                top_subproof.commands.push(command);
+                if top_end_step != id.as_ref() {
+                    let index = top_subproof.commands.len() - 1;
+                    self.state.step_ids.insert(id, index);
+                    continue;
+                }
+
                 // If this is the last step in a subproof, we need to pop all the subproof data off
                 // of the stacks and build the subproof command with it
                 self.state.symbol_table.pop_scope();
                 self.state.step_ids.pop_scope();
-                let (subproof, _) = stack.pop().unwrap();
+                let (subproof, _) = state.stack.pop().unwrap();
 
                 // The subproof must contain at least two commands: the end step and the previous
                 // command it implicitly references
@@ -919,33 +1111,51 @@ impl<'a, R: BufRead> Parser<'a, R> {
                         position,
                     ));
                 }
+                command = ProofCommand::Subproof(subproof);
+            }
 
-                let (outer, _) = stack.last_mut().unwrap();
-                outer.commands.push(ProofCommand::Subproof(subproof));
+            match state.stack.last_mut() {
+                Some((outer, _)) => {
+                    outer.commands.push(command);
+                    let index = outer.commands.len() - 1;
+                    self.state.step_ids.insert(id, index);
+                }
+                None => {
+                    if !state.pending_comments.is_empty() {
+                        let comments = std::mem::take(&mut state.pending_comments);
+                        state.comments.insert(id.as_ref().clone(), comments);
+                    }
+                    self.state.step_ids.insert(id, state.num_root_commands);
+                    state.num_root_commands += 1;
+                    return Ok(Some(command));
+                }
             }
-            let index = stack.last().unwrap().0.commands.len() - 1;
-            self.state.step_ids.insert(id, index);
         }
+        Ok(None)
+    }
 
-        if has_extra_surrounding_parens {
+    /// Finishes parsing a proof, after [`Parser::parse_root_command`] returned `None`. Returns the
+    /// constant definitions found in the proof.
+    pub(crate) fn finish_proof(
+        &mut self,
+        state: ProofParsingState,
+    ) -> CarcaraResult<Vec<(String, Rc<Term>)>> {
+        if state.has_extra_surrounding_parens {
             self.expect_token(Token::CloseParen)?;
         }
-        self.expect_token(Token::Eof)?;
-
-        let commands = match stack.len() {
-            0 => unreachable!(),
-            1 => stack.pop().unwrap().0.commands,
+        if !(state.stop_at_unsat && self.current_token == Token::Symbol("unsat".into())) {
+            self.expect_token(Token::Eof)?;
+        }
 
-            // If there is more than one layer in the stack, we are inside a subproof that should be
-            // closed before the outer proof is finished
-            _ => {
-                return Err(Error::Parser(
-                    ParserError::UnclosedSubproof(stack.pop().unwrap().1),
-                    self.current_position,
-                ))
-            }
-        };
-        Ok(Proof { constant_definitions, commands })
+        // If the stack is not empty, we are inside a subproof that should be closed before the
+        // outer proof is finished
+        if let Some((_, end_step_id)) = state.stack.into_iter().next_back() {
+            return Err(Error::Parser(
+                ParserError::UnclosedSubproof(end_step_id),
+                self.current_position,
+            ));
+        }
+        Ok(state.constant_definitions)
     }
 
     /// Parses an `assume` proof command. This method assumes that the `(` and `assume` tokens were
@@ -1294,7 +1504,9 @@ impl<'a, R: BufRead> Parser<'a, R> {
     /// Parses a term.
     pub fn parse_term(&mut self) -> CarcaraResult<Rc<Term>> {
         let term = match self.next_token()? {
-            (Token::Bitvector { value, width }, _) => Term::new_bv(value, width),
+            (Token::Bitvector { value, width, radix }, _) => {
+                Term::Const(Constant::BitVec(value, width.into(), radix))
+            }
             (Token::Numeral(n), _) if self.interpret_ints_as_reals() => Term::new_real(n),
             (Token::Numeral(n), _) => Term::new_int(n),
             (Token::Decimal(r), _) => Term::new_real(r),
@@ -1323,7 +1535,9 @@ impl<'a, R: BufRead> Parser<'a, R> {
 
     pub fn parse_constant(&mut self) -> CarcaraResult<Constant> {
         let constant = match self.next_token()? {
-            (Token::Bitvector { value, width }, _) => Constant::BitVec(value, width.into()),
+            (Token::Bitvector { value, width, radix }, _) => {
+                Constant::BitVec(value, width.into(), radix)
+            }
             (Token::Numeral(n), _) if self.interpret_ints_as_reals() => Constant::Real(n.into()),
             (Token::Numeral(n), _) => Constant::Integer(n),
             (Token::Decimal(r), _) => Constant::Real(r),
@@ -1542,7 +1756,7 @@ impl<'a, R: BufRead> Parser<'a, R> {
                 let width = op_args[1].as_integer().unwrap();
                 assert_indexed_op_args_value(&[op_args[0].clone()], 0..)?;
                 assert_indexed_op_args_value(&[op_args[1].clone()], 1..)?;
-                return Ok(self.pool.add(Term::Const(Constant::BitVec(value, width))));
+                return Ok(self.pool.add(Term::new_bv(value, width)));
             }
             ParamOperator::BvExtract => {
                 /*
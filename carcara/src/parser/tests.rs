@@ -14,6 +14,7 @@ const TEST_CONFIG: Config = Config {
     allow_int_real_subtyping: false,
     strict: false,
     parse_hole_args: false,
+    keep_comments: false,
 };
 
 pub fn parse_terms<const N: usize>(
@@ -119,6 +120,36 @@ fn test_constant_terms() {
     assert_eq!(Term::new_real((3, 2)), *parse_term(&mut p, "1.5"));
     assert_eq!(Term::new_string("foo"), *parse_term(&mut p, "\"foo\""));
     assert_eq!(Term::new_bv(0, 4), *parse_term(&mut p, "(_ bv0 4)"));
+    assert_eq!(Term::new_bv(15, 8), *parse_term(&mut p, "#x0f"));
+    assert_eq!(Term::new_bv(5, 4), *parse_term(&mut p, "#b0101"));
+}
+
+#[test]
+fn test_bitvector_literal_radix() {
+    // Bitvector literals are printed back in the same notation they were written in
+    for literal in ["#x0f", "#b0101", "#xdeadBEEF", "#b0", "(_ bv42 8)"] {
+        let mut p = PrimitivePool::new();
+        let term = parse_term(&mut p, literal);
+        assert_eq!(literal.to_lowercase(), term.to_string());
+    }
+
+    // The notation is not part of the term, so literals with the same value and width are the same
+    // term, regardless of their notation. They are all printed in the first notation the pool saw
+    let mut p = PrimitivePool::new();
+    let [a, b, c] = ["#x0f", "#b00001111", "(_ bv15 8)"].map(|s| parse_term(&mut p, s));
+    assert_eq!(a, b);
+    assert_eq!(b, c);
+    assert_eq!("#x0f", c.to_string());
+
+    // Bitvector tokens are also printed in their original notation
+    for literal in ["#x0f", "#b0101"] {
+        let token = Lexer::new(literal.as_bytes())
+            .unwrap()
+            .next_token()
+            .unwrap()
+            .0;
+        assert_eq!(literal, token.to_string());
+    }
 }
 
 #[test]
@@ -650,6 +681,15 @@ fn test_step() {
             discharge: Vec::new(),
         })
     );
+
+    // Assignments are only allowed as anchor arguments
+    assert!(matches!(
+        parse_proof_err(
+            &mut p,
+            "(step t1 (cl) :rule rule-name :args ((:= (x Int) 1)))"
+        ),
+        Error::Parser(ParserError::UnexpectedToken(_), _),
+    ));
 }
 
 #[test]
@@ -834,3 +874,74 @@ fn test_proofs_with_extra_parens() {
         Error::Parser(ParserError::UnexpectedToken(Token::CloseParen), _)
     ));
 }
+
+#[test]
+fn test_multiple_proofs() {
+    let mut p = PrimitivePool::new();
+    let parse = |p: &mut PrimitivePool, input: &str| {
+        Parser::new(p, TEST_CONFIG, input.as_bytes()).and_then(|mut parser| parser.parse_proofs())
+    };
+
+    // Proofs may be surrounded by parentheses or not, and step ids are scoped to each proof
+    let proofs = parse(
+        &mut p,
+        "unsat ((assume h1 true) (assume h2 false)) unsat (assume h1 false)",
+    )
+    .unwrap();
+    assert_eq!(proofs.len(), 2);
+    assert_eq!(proofs[0].commands.len(), 2);
+    assert_eq!(
+        proofs[1].commands,
+        [ProofCommand::Assume {
+            id: "h1".into(),
+            term: p.bool_false()
+        }]
+    );
+
+    // The first `unsat` is optional
+    let proofs = parse(&mut p, "(assume h1 true)").unwrap();
+    assert_eq!(proofs.len(), 1);
+
+    // Step ids must still be unique within each proof
+    assert!(matches!(
+        parse(
+            &mut p,
+            "unsat (assume h1 true) unsat (assume h1 true) (assume h1 true)"
+        ),
+        Err(Error::Parser(ParserError::RepeatedStepId(_), _))
+    ));
+}
+
+#[test]
+fn test_roundtrip() {
+    fn run_tests(definitions: &str, cases: &[&str]) {
+        for &proof in cases {
+            let (problem, proof, mut pool) =
+                parse_instance(definitions.as_bytes(), proof.as_bytes(), TEST_CONFIG).unwrap();
+            let reparsed = roundtrip(&mut pool, &problem.prelude, &proof).unwrap();
+            assert_eq!(proof, reparsed);
+        }
+    }
+    let definitions = "
+        (declare-fun p () Bool)
+        (declare-fun a () Int)
+        (declare-fun x () Real)
+    ";
+    run_tests(
+        definitions,
+        &[
+            // `assume` commands
+            "(assume h1 (and p (= a 2)))",
+            "(assume h1 (< x 1.5))",
+            // `step` commands
+            "(assume h1 p)
+            (step t1 (cl (not p) (= \"a\"\"b\" \"c\")) :rule hole :args (a (- 3)))
+            (step t2 (cl) :rule resolution :premises (h1 t1))",
+            // Subproofs
+            "(anchor :step t1 :args ((y Int) (:= (z Int) a)))
+            (assume t1.a0 p)
+            (step t1.t1 (cl (= y z)) :rule hole)
+            (step t1 (cl (not p) (= y z)) :rule subproof :discharge (t1.a0))",
+        ],
+    );
+}
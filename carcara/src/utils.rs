@@ -182,6 +182,11 @@ impl<T: std::cmp::PartialOrd> Range<T> {
         self.0.as_ref().is_none_or(|bound| n >= *bound)
             && self.1.as_ref().is_none_or(|bound| n <= *bound)
     }
+
+    /// Returns `true` if the range contains exactly the number `n`, and nothing else.
+    pub fn is_exactly(&self, n: T) -> bool {
+        self.0.as_ref() == Some(&n) && self.1.as_ref() == Some(&n)
+    }
 }
 
 impl<T: fmt::Display + std::cmp::PartialEq> fmt::Display for Range<T> {
@@ -334,6 +334,7 @@ fn implies() {
         definitions = "
             (declare-fun a () Bool)
             (declare-fun b () Bool)
+            (declare-fun c () Bool)
         ",
         "Simple working examples" {
             "(assume h1 (=> a b))
@@ -356,6 +357,19 @@ fn implies() {
             "(assume h1 (=> (not a) b))
             (step t2 (cl a b) :rule implies :premises (h1))": false,
         }
+        "Implications with more than two arguments" {
+            "(assume h1 (=> a b c))
+            (step t2 (cl (not a) (not b) c) :rule implies :premises (h1))": true,
+
+            "(assume h1 (=> a b c))
+            (step t2 (cl (not a) (=> b c)) :rule implies :premises (h1))": false,
+
+            "(assume h1 (=> a b c))
+            (step t2 (cl (not a) b (not c)) :rule implies :premises (h1))": false,
+
+            "(assume h1 (=> a b c))
+            (step t2 (cl (not a) c) :rule implies :premises (h1))": false,
+        }
     }
 }
 
@@ -365,6 +379,7 @@ fn not_implies1() {
         definitions = "
             (declare-fun a () Bool)
             (declare-fun b () Bool)
+            (declare-fun c () Bool)
         ",
         "Simple working examples" {
             "(assume h1 (not (=> a b)))
@@ -387,6 +402,16 @@ fn not_implies1() {
             "(assume h1 (not (=> a b)))
             (step t2 (cl b) :rule not_implies1 :premises (h1))": false,
         }
+        "Implications with more than two arguments" {
+            "(assume h1 (not (=> a b c)))
+            (step t2 (cl a) :rule not_implies1 :premises (h1))": true,
+
+            "(assume h1 (not (=> a b c)))
+            (step t2 (cl b) :rule not_implies1 :premises (h1))": true,
+
+            "(assume h1 (not (=> a b c)))
+            (step t2 (cl c) :rule not_implies1 :premises (h1))": false,
+        }
     }
 }
 
@@ -396,6 +421,7 @@ fn not_implies2() {
         definitions = "
             (declare-fun a () Bool)
             (declare-fun b () Bool)
+            (declare-fun c () Bool)
         ",
         "Simple working examples" {
             "(assume h1 (not (=> a b)))
@@ -418,6 +444,13 @@ fn not_implies2() {
             "(assume h1 (not (=> a b)))
             (step t2 (cl (not a)) :rule not_implies2 :premises (h1))": false,
         }
+        "Implications with more than two arguments" {
+            "(assume h1 (not (=> a b c)))
+            (step t2 (cl (not c)) :rule not_implies2 :premises (h1))": true,
+
+            "(assume h1 (not (=> a b c)))
+            (step t2 (cl (not (=> b c))) :rule not_implies2 :premises (h1))": false,
+        }
     }
 }
 
@@ -445,6 +478,19 @@ fn nary_elim() {
             "(step t1 (cl (= (* a b) (* a b))) :rule nary_elim)": true,
             "(step t1 (cl (= (- a b c d) (- a (- b (- c d))))) :rule nary_elim)": false,
             "(step t1 (cl (= (+ a b c d) (+ (+ (+ d c) b) a))) :rule nary_elim)": false,
+            "(step t1 (cl (= (+ a b c) (+ a (+ b c)))) :rule nary_elim)": false,
+            "(step t1 (cl (= (and p q r) (and (and p q) r))) :rule nary_elim)": true,
+            "(step t1 (cl (= (or p q r s) (or (or (or p q) r) s))) :rule nary_elim)": true,
+            "(step t1 (cl (= (and p q r) (and p (and q r)))) :rule nary_elim)": false,
+        }
+        "Expansion ending in identity element" {
+            "(step t1 (cl (= (+ a b c) (+ (+ (+ 0 a) b) c))) :rule nary_elim)": true,
+            "(step t1 (cl (= (* a b) (* (* 1 a) b))) :rule nary_elim)": true,
+            "(step t1 (cl (= (and p q) (and (and true p) q))) :rule nary_elim)": true,
+            "(step t1 (cl (= (or p q) (or (or false p) q))) :rule nary_elim)": true,
+            "(step t1 (cl (= (+ a b c) (+ (+ (+ 1 a) b) c))) :rule nary_elim)": false,
+            "(step t1 (cl (= (and p q) (and (and false p) q))) :rule nary_elim)": false,
+            "(step t1 (cl (= (- a b) (- (- 0 a) b))) :rule nary_elim)": false,
         }
         "Right associative operators" {
             "(step t1 (cl (= (=> p q r s) (=> p (=> q (=> r s))))) :rule nary_elim)": true,
@@ -452,7 +498,7 @@ fn nary_elim() {
             "(step t1 (cl (= (=> p q r s) (=> (=> (=> p q) r) s))) :rule nary_elim)": false,
         }
         "Clause term is not of the correct form" {
-            "(step t1 (cl (= (or p q r s) (or (or (or p q) r) s))) :rule nary_elim)": false,
+            "(step t1 (cl (= (xor p q r s) (xor (xor (xor p q) r) s))) :rule nary_elim)": false,
             "(step t1 (cl (= (- a) (- a))) :rule nary_elim)": false,
             "(step t1 (cl (= (=> p (=> q (=> r s))) (=> p q r s))) :rule nary_elim)": false,
         }
@@ -523,5 +569,27 @@ fn bfun_elim() {
                 (ite a (g true true true) (g true false true))
             )) :rule bfun_elim :premises (h1))": true,
         }
+        "Wrong expansion" {
+            "(assume h1 (forall ((x Bool)) (f x)))
+            (step t1 (cl (and (f true) (f false))) :rule bfun_elim :premises (h1))": false,
+
+            "(assume h1 (forall ((x Bool)) (f x)))
+            (step t1 (cl (f false)) :rule bfun_elim :premises (h1))": false,
+
+            "(assume h1 (exists ((x Bool) (y Bool) (z Bool)) (g x y z)))
+            (step t1 (cl (or
+                (g false false false)
+                (g true false false)
+                (g false true false)
+                (g true true false)
+                (g false false true)
+                (g true false true)
+                (g true true true)
+                (g false true true)
+            )) :rule bfun_elim :premises (h1))": false,
+
+            "(assume h1 (f a))
+            (step t1 (cl (ite a (f false) (f true))) :rule bfun_elim :premises (h1))": false,
+        }
     }
 }
@@ -76,6 +76,11 @@ fn eq_congruent_pred() {
             (declare-fun q (Bool Bool) Bool)
             (declare-fun p-1 (Bool) Bool)
             (declare-fun p-3 (Bool Bool Bool) Bool)
+            (declare-fun i () Int)
+            (declare-fun j () Int)
+            (declare-fun r () Real)
+            (declare-fun s () Real)
+            (declare-fun mixed (Int Bool Real) Bool)
         ",
         "Simple working examples" {
             "(step t1 (cl (not (= a b)) (not (p-1 a)) (p-1 b)) :rule eq_congruent_pred)": true,
@@ -124,6 +129,23 @@ fn eq_congruent_pred() {
             "(step t1 (cl (not (= a x)) (not (= b y)) (not (p a b)) (p c z))
                 :rule eq_congruent_pred)": false,
         }
+        "Only some arguments differ" {
+            "(step t1 (cl (not (= a b)) (not (mixed i a r)) (mixed i b r))
+                :rule eq_congruent_pred)": true,
+            "(step t1 (cl (not (= i j)) (not (= r s)) (not (mixed i a r)) (mixed j a s))
+                :rule eq_congruent_pred)": true,
+            "(step t1 (cl (not (= i i)) (not (= a b)) (not (mixed i a r)) (mixed i b r))
+                :rule eq_congruent_pred)": true,
+
+            "(step t1 (cl (not (= i j)) (not (mixed i a r)) (mixed i b r))
+                :rule eq_congruent_pred)": false,
+            "(step t1 (cl (not (= a b)) (not (mixed i a r)) (mixed j b r))
+                :rule eq_congruent_pred)": false,
+            "(step t1 (cl (not (= r s)) (not (mixed i a r)) (mixed i b s))
+                :rule eq_congruent_pred)": false,
+            "(step t1 (cl (not (= a b)) (not (= a b)) (not (mixed i a r)) (mixed i b r))
+                :rule eq_congruent_pred)": false,
+        }
     }
 }
 
@@ -144,6 +166,10 @@ fn cong() {
             (declare-fun s () Bool)
             (declare-fun x () Real)
             (declare-fun y () Real)
+            (declare-fun i () Int)
+            (declare-fun j () Int)
+            (declare-fun m () (Array Int Real))
+            (declare-fun n () (Array Int Real))
         ",
         "Simple working examples" {
             "(assume h1 (= a b))
@@ -215,6 +241,23 @@ fn cong() {
             "(assume h1 (= a b)) (assume h2 (= c d))
             (step t3 (cl (= (= c a) (= d b))) :rule cong :premises (h1 h2))": true,
         }
+        "Array operations" {
+            "(assume h1 (= x y))
+            (step t2 (cl (= (store m i x) (store m i y))) :rule cong :premises (h1))": true,
+
+            "(assume h1 (= m n)) (assume h2 (= i j))
+            (step t3 (cl (= (select m i) (select n j))) :rule cong :premises (h1 h2))": true,
+
+            "(assume h1 (= i j))
+            (step t2 (cl (= (select (store m i x) 0) (select (store m j x) 0)))
+                :rule cong :premises (h1))": false,
+
+            "(assume h1 (= x y))
+            (step t2 (cl (= (store m i x) (store m j y))) :rule cong :premises (h1))": false,
+
+            "(assume h1 (= m n))
+            (step t2 (cl (= (store m i x) (store n j x))) :rule cong :premises (h1))": false,
+        }
     }
 }
 
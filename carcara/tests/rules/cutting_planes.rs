@@ -132,6 +132,20 @@ fn cp_multiplication() {
             r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2)) 1))
                (step t1 (cl (>= (* 2 x1) 2)) :rule cp_multiplication :premises (c1) :args (2))"#: false,
         }
+        "Coefficients beyond 64 bits" {
+            r#"(assume c1 (>= (+ (* 1 x1) (* 100000000000000000000 x2)) 3))
+               (step t1 (cl (>= (+ (* 100000000000000000000 x1) (* 10000000000000000000000000000000000000000 x2)) 300000000000000000000))
+                 :rule cp_multiplication :premises (c1) :args (100000000000000000000))"#: true,
+
+            // The products must be exact, so a difference in the last digit is detected
+            r#"(assume c1 (>= (+ (* 1 x1) (* 100000000000000000000 x2)) 3))
+               (step t1 (cl (>= (+ (* 100000000000000000000 x1) (* 10000000000000000000000000000000000000001 x2)) 300000000000000000000))
+                 :rule cp_multiplication :premises (c1) :args (100000000000000000000))"#: false,
+            r#"(assume c1 (>= (* 18446744073709551616 x1) 1))
+               (step t1 (cl (>= (* 36893488147419103232 x1) 2)) :rule cp_multiplication :premises (c1) :args (2))"#: true,
+            r#"(assume c1 (>= (* 18446744073709551616 x1) 1))
+               (step t1 (cl (>= (* 0 x1) 2)) :rule cp_multiplication :premises (c1) :args (2))"#: false,
+        }
         "Wrong product" {
             r#"(assume c1 (>= (* 1 x1) 1))
                (step t1 (cl (>= (* 3 x1) 2)) :rule cp_multiplication :premises (c1) :args (2))"#: false,
@@ -150,7 +164,18 @@ fn cp_multiplication() {
             r#"(assume c1 (>= (+ (* 1 x1) (* 2 (- 1 x2)) (* 3 x3) 0) 1))
                (step t1 (cl (>= (+ (* 2 x1) (* 4 (- 1 x2)) (* 6 x3) 0) 2)) :rule cp_multiplication :premises (c1) :args (2))"#: false,
         }
+        "Dropped literals" {
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2) (* 3 x3)) 1))
+               (step t1 (cl (>= (+ (* 2 x1) (* 6 x3)) 2)) :rule cp_multiplication :premises (c1) :args (2))"#: false,
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 (- 1 x2))) 1))
+               (step t1 (cl (>= (* 2 x1) 2)) :rule cp_multiplication :premises (c1) :args (2))"#: false,
 
+            // Literals whose product is zero may be dropped
+            r#"(assume c1 (>= (+ (* 1 x1) (* 0 x2)) 1))
+               (step t1 (cl (>= (* 2 x1) 2)) :rule cp_multiplication :premises (c1) :args (2))"#: true,
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2)) 1))
+               (step t1 (cl (>= 0 0)) :rule cp_multiplication :premises (c1) :args (0))"#: true,
+        }
     }
 }
 
@@ -167,6 +192,24 @@ fn cp_division() {
             r#"(assume c1 (>= (* 2 (- 1 x1)) 2))
                (step t1 (cl (>= (* 1 (- 1 x1)) 1)) :rule cp_division :premises (c1) :args (2) )"#: true,
         }
+        "Coefficients beyond 64 bits" {
+            r#"(assume c1 (>= (* 200000000000000000000 x1) 300000000000000000001))
+               (step t1 (cl (>= (* 2 x1) 4)) :rule cp_division :premises (c1) :args (100000000000000000000) )"#: true,
+            r#"(assume c1 (>= (* 200000000000000000000 x1) 300000000000000000001))
+               (step t1 (cl (>= (* 2 x1) 3)) :rule cp_division :premises (c1) :args (100000000000000000000) )"#: false,
+        }
+        "Negative constants" {
+            r#"(assume c1 (>= (* 4 x1) (- 3)))
+               (step t1 (cl (>= (* 2 x1) (- 1))) :rule cp_division :premises (c1) :args (2) )"#: true,
+            r#"(assume c1 (>= (* 4 x1) (- 3)))
+               (step t1 (cl (>= (* 1 x1) 0)) :rule cp_division :premises (c1) :args (4) )"#: true,
+            r#"(assume c1 (>= (* 4 x1) (- 3)))
+               (step t1 (cl (>= (* 2 x1) (- 1))) :rule cp_division :premises (c1) :args (3) )"#: true,
+            r#"(assume c1 (>= (* 4 x1) (- 3)))
+               (step t1 (cl (>= (* 2 x1) 0)) :rule cp_division :premises (c1) :args (3) )"#: false,
+            r#"(assume c1 (>= (* 4 x1) (- 3)))
+               (step t1 (cl (>= (* 2 x1) (- 2))) :rule cp_division :premises (c1) :args (2) )"#: false,
+        }
         "Wrong division" {
             r#"(assume c1 (>= (* 2 x1) 2))
                (step t1 (cl (>= (* 2 x1) 1)) :rule cp_division :premises (c1) :args (2) )"#: false,
@@ -216,6 +259,8 @@ fn cp_division() {
        "Trailing Zero" {
             r#"(assume c1 (>= (+ (* 2 x1) 0) 2))
                (step t1 (cl (>= (+ (* 1 x1) 0) 1)) :rule cp_division :premises (c1) :args (2) )"#: false,
+            r#"(assume c1 (>= (+ (* 2 x1) 0) 2))
+               (step t1 (cl (>= (* 1 x1) 1)) :rule cp_division :premises (c1) :args (2) )"#: false,
             r#"(assume c1 (>= (+ (* 2 (- 1 x1)) 0) 2))
                (step t1 (cl (>= (+ (* 1 (- 1 x1)) 0) 1)) :rule cp_division :premises (c1) :args (2) )"#: false,
         }
@@ -280,6 +325,52 @@ fn cp_saturation() {
     }
 }
 
+#[test]
+fn cp_weakening() {
+    test_cases! {
+        definitions = "
+            (declare-fun x1 () Int)
+            (declare-fun x2 () Int)
+            (declare-fun x3 () Int)
+        ",
+        "Simple working examples" {
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2)) 3))
+               (step t1 (cl (>= (* 1 x1) 1)) :rule cp_weakening :premises (c1))"#: true,
+
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2) (* 3 (- 1 x3))) 4))
+               (step t1 (cl (>= (+ (* 1 x1) (* 2 x2)) 1)) :rule cp_weakening :premises (c1))"#: true,
+
+            r#"(assume c1 (>= (* 2 x1) 2))
+               (step t1 (cl (>= 0 0)) :rule cp_weakening :premises (c1))"#: true,
+        }
+        "Constant not reduced by exactly the removed coefficient" {
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2)) 3))
+               (step t1 (cl (>= (* 1 x1) 0)) :rule cp_weakening :premises (c1))"#: false,
+        }
+        "Wrong number of removed literals" {
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2)) 3))
+               (step t1 (cl (>= (+ (* 1 x1) (* 2 x2)) 3)) :rule cp_weakening :premises (c1))"#: false,
+
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2) (* 3 x3)) 6))
+               (step t1 (cl (>= (* 1 x1) 1)) :rule cp_weakening :premises (c1))"#: false,
+        }
+        "Literals kept with a zero coefficient are removed" {
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2) (* 3 x3)) 6))
+               (step t1 (cl (>= (+ (* 0 x1) (* 2 x2)) 3)) :rule cp_weakening :premises (c1))"#: false,
+
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2)) 3))
+               (step t1 (cl (>= (+ (* 0 x1) (* 2 x2)) 2)) :rule cp_weakening :premises (c1))"#: true,
+        }
+        "Remaining coefficients changed" {
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2) (* 3 x3)) 6))
+               (step t1 (cl (>= (+ (* 2 x1) (* 2 x2)) 3)) :rule cp_weakening :premises (c1))"#: false,
+
+            r#"(assume c1 (>= (+ (* 1 x1) (* 2 x2)) 3))
+               (step t1 (cl (>= (* 1 (- 1 x1)) 1)) :rule cp_weakening :premises (c1))"#: false,
+        }
+    }
+}
+
 #[test]
 fn cp_literal() {
     test_cases! {
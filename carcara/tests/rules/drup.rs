@@ -46,5 +46,18 @@ fn drup() {
             (step t0 (cl a b) :rule or :premises (a3))
             (step t1 (cl) :rule drup :premises (a1 t0) :args ((cl)))": false,
         }
+
+        "Checking with \"drat\"" {
+            "(assume a1 (not a))
+            (assume a2 (not b))
+            (assume a3 (or a b))
+            (step t0 (cl a b) :rule or :premises (a3))
+            (step t1 (cl) :rule drat :premises (a1 a2 t0) :args ((cl)))": true,
+
+            "(assume a1 (not a))
+            (assume a3 (or a b))
+            (step t0 (cl a b) :rule or :premises (a3))
+            (step t1 (cl) :rule drat :premises (a1 t0) :args ((cl)))": false,
+        }
     }
 }
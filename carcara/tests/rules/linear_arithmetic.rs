@@ -92,6 +92,14 @@ fn la_disequality() {
             "(step t1 (cl (or (= x y) (not (<= y x)) (not (<= y x))))
                 :rule la_disequality)": false,
         }
+        "Inequalities in the wrong direction" {
+            "(step t1 (cl (or (= a b) (not (<= b a)) (not (<= a b))))
+                :rule la_disequality)": false,
+            "(step t1 (cl (or (= x y) (not (<= x y)) (not (<= x y))))
+                :rule la_disequality)": false,
+            "(step t1 (cl (or (= x y) (not (<= x y)) (not (<= y y))))
+                :rule la_disequality)": false,
+        }
     }
 }
 
@@ -129,14 +137,20 @@ fn la_tautology() {
             "(step t1 (cl (not (<= n (- n 1)))) :rule la_tautology)": true,
             "(step t1 (cl (< 0 (- (+ 1 n) n))) :rule la_tautology)": true,
             "(step t1 (cl (not (<= (+ 1 n) (- (+ 1 n) 1)))) :rule la_tautology)": true,
+
+            "(step t1 (cl (<= n 5)) :rule la_tautology)": false,
+            "(step t1 (cl (< n n)) :rule la_tautology)": false,
+            "(step t1 (cl (not (= n 5))) :rule la_tautology)": false,
         }
         "Second form" {
             "(step t1 (cl (or (not (<= x 5.0)) (<= x 6.0))) :rule la_tautology)": true,
+            "(step t1 (cl (or (not (<= x 6.0)) (<= x 5.0))) :rule la_tautology)": false,
 
             "(step t1 (cl (or (<= x 6.0) (not (<= x 6.0)))) :rule la_tautology)": true,
             "(step t1 (cl (or (<= x 6.1) (not (<= x 6.0)))) :rule la_tautology)": false,
 
             "(step t1 (cl (or (not (>= x 6.0)) (>= x 5.0))) :rule la_tautology)": true,
+            "(step t1 (cl (or (not (>= x 5.0)) (>= x 6.0))) :rule la_tautology)": false,
 
             "(step t1 (cl (or (>= x 5.0) (not (>= x 5.0)))) :rule la_tautology)": true,
             "(step t1 (cl (or (>= x 5.0) (not (>= x 5.1)))) :rule la_tautology)": false,
@@ -3,20 +3,27 @@ use carcara::{
     checker, parser,
 };
 use colored::{Color, Colorize};
-use std::io::Cursor;
+use std::{collections::HashSet, io::Cursor};
 
-fn run_tests(test_name: &str, definitions: &str, cases: &[(&str, bool)]) {
+/// The parser configuration used by the rule tests, unless a test case group overrides it.
+fn default_parser_config() -> parser::Config {
+    parser::Config {
+        apply_function_defs: true,
+        ..Default::default()
+    }
+}
+
+fn run_tests(
+    test_name: &str,
+    definitions: &str,
+    parser_config: parser::Config,
+    cases: &[(&str, bool)],
+) {
     for (i, (proof, expected)) in cases.iter().enumerate() {
         // This parses the definitions again for every case, which is not ideal
-        let (mut problem, mut proof, mut pool) = parser::parse_instance(
-            Cursor::new(definitions),
-            Cursor::new(proof),
-            parser::Config {
-                apply_function_defs: true,
-                ..Default::default()
-            },
-        )
-        .unwrap_or_else(|e| panic!("parser error during test \"{}\": {}", test_name, e));
+        let (mut problem, mut proof, mut pool) =
+            parser::parse_instance(Cursor::new(definitions), Cursor::new(proof), parser_config)
+                .unwrap_or_else(|e| panic!("parser error during test \"{}\": {}", test_name, e));
 
         // Since rule tests often use `assume` commands to introduce premises, we search the proof
         // for all `assume`d terms and retroactively add them as the problem premises, to avoid
@@ -76,30 +83,87 @@ fn run_tests(test_name: &str, definitions: &str, cases: &[(&str, bool)]) {
 macro_rules! test_cases {
     (
         definitions = $defs:expr,
+        parser_config = $config:expr,
         $($name:literal { $($proof:literal: $exp:literal,)* } )*
     ) => {{
         let definitions: &str = $defs;
+        let parser_config: carcara::parser::Config = $config;
         $({
             let name: &str = $name;
             let cases = [ $(($proof, $exp),)* ];
-            $crate::rules::run_tests(name, definitions, &cases);
+            $crate::rules::run_tests(name, definitions, parser_config, &cases);
         })*
     }};
+    (
+        definitions = $defs:expr,
+        $($name:literal { $($proof:literal: $exp:literal,)* } )*
+    ) => {
+        test_cases! {
+            definitions = $defs,
+            parser_config = $crate::rules::default_parser_config(),
+            $($name { $($proof: $exp,)* })*
+        }
+    };
+}
+
+/// Declares the rule test modules, and collects their sources in `RULE_TEST_SOURCES`, so the rules
+/// tested in each of them can be found by `assert_rule_has_tests`.
+macro_rules! rule_test_modules {
+    ($($module:ident,)*) => {
+        $(pub(super) mod $module;)*
+
+        const RULE_TEST_SOURCES: &[&str] = &[$(include_str!(concat!(stringify!($module), ".rs")),)*];
+    };
+}
+
+rule_test_modules! {
+    bitvectors,
+    clausification,
+    congruence,
+    cutting_planes,
+    drup,
+    extras,
+    linear_arithmetic,
+    pb_blasting,
+    quantifier,
+    reflexivity,
+    resolution,
+    simplification,
+    strings,
+    subproof,
+    tautology,
+    transitivity,
 }
 
-pub(super) mod bitvectors;
-pub(super) mod clausification;
-pub(super) mod congruence;
-pub(super) mod cutting_planes;
-pub(super) mod drup;
-pub(super) mod extras;
-pub(super) mod linear_arithmetic;
-pub(super) mod pb_blasting;
-pub(super) mod quantifier;
-pub(super) mod reflexivity;
-pub(super) mod resolution;
-pub(super) mod simplification;
-pub(super) mod strings;
-pub(super) mod subproof;
-pub(super) mod tautology;
-pub(super) mod transitivity;
+/// The rules that are accepted without any checking and make the proof holey. Since every test
+/// case already ends with a `hole` step, the rule tests can't tell these rules apart from a hole,
+/// so they are exempt from `assert_rule_has_tests`, and are tested in the checker tests instead.
+const HOLE_RULES: &[&str] = &["hole", "lia_generic"];
+
+/// Panics if any rule supported by the checker, other than the rules in `HOLE_RULES`, is never
+/// used in the rule tests. A rule is considered tested if some test case contains a step with
+/// `:rule <name>`, so every rule name, including aliases, must appear in at least one test case.
+fn assert_rule_has_tests() {
+    let tested: HashSet<&str> = RULE_TEST_SOURCES
+        .iter()
+        .flat_map(|source| source.split(":rule").skip(1))
+        .filter_map(|rest| {
+            rest.split(|c: char| c.is_whitespace() || c == '(' || c == ')')
+                .find(|s| !s.is_empty())
+        })
+        .collect();
+    let untested: Vec<_> = checker::ProofChecker::supported_rules()
+        .into_iter()
+        .filter(|rule| !tested.contains(rule) && !HOLE_RULES.contains(rule))
+        .collect();
+    assert!(
+        untested.is_empty(),
+        "the following rules have no test cases: {}",
+        untested.join(", ")
+    );
+}
+
+#[test]
+fn all_rules_have_tests() {
+    assert_rule_has_tests();
+}
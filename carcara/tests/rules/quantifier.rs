@@ -195,5 +195,26 @@ fn qnt_cnf() {
                 (forall ((p Bool)) (or p false))
             )) :rule qnt_cnf)": true,
         }
+        "Clauses that don't appear in the CNF" {
+            "(step t1 (cl (or
+                (not (forall ((p Bool) (q Bool) (r Bool)) (and (or p q) (or (not p) r))))
+                (forall ((p Bool) (q Bool) (r Bool)) (or (not p) r))
+            )) :rule qnt_cnf)": true,
+
+            "(step t1 (cl (or
+                (not (forall ((p Bool) (q Bool) (r Bool)) (and (or p q) (or (not p) r))))
+                (forall ((p Bool) (r Bool)) (or p r))
+            )) :rule qnt_cnf)": false,
+
+            "(step t1 (cl (or
+                (not (forall ((p Bool) (q Bool) (r Bool)) (and (or p q) (or (not p) r))))
+                (forall ((p Bool) (q Bool)) (or q p))
+            )) :rule qnt_cnf)": false,
+
+            "(step t1 (cl (or
+                (not (forall ((p Bool) (q Bool) (r Bool)) (and (or p q) (or (not p) r))))
+                (forall ((r Bool)) (not r))
+            )) :rule qnt_cnf)": false,
+        }
     }
 }
@@ -25,6 +25,19 @@ fn eq_reflexive() {
             "(step t1 (cl (= (f a b) (f b a))) :rule eq_reflexive)": false,
             "(step t1 (cl (= (+ a b) (+ b a))) :rule eq_reflexive)": false,
         }
+        "Terms are equal modulo context substitution" {
+            "(anchor :step t1 :args ((y Int) (:= (x Int) y)))
+            (step t1.t1 (cl (= x y)) :rule eq_reflexive)
+            (step t1 (cl (= (forall ((x Int)) (= x x)) (forall ((y Int)) (= y y)))) :rule hole)": true,
+
+            "(anchor :step t1 :args ((:= (x Int) a) (:= (y Int) a)))
+            (step t1.t1 (cl (= (f x b) (f y b))) :rule eq_reflexive)
+            (step t1 (cl (= (f a b) (f a b))) :rule hole)": true,
+
+            "(anchor :step t1 :args ((:= (x Int) a) (:= (y Int) b)))
+            (step t1.t1 (cl (= x y)) :rule eq_reflexive)
+            (step t1 (cl (= a b)) :rule hole)": false,
+        }
     }
 }
 
@@ -35,6 +48,7 @@ fn refl() {
             (declare-fun f (Real) Real)
             (declare-fun g (Real) Real)
             (declare-fun z () Real)
+            (declare-fun m () (Array Int Real))
         ",
         "Simple working examples" {
             "(anchor :step t1 :args ((y Real) (:= (x Real) y)))
@@ -84,5 +98,22 @@ fn refl() {
             )) :rule refl)
             (step t1 (cl) :rule hole)": true,
         }
+        "Array operations" {
+            "(anchor :step t1 :args ((y Real) (:= (x Real) y)))
+            (step t1.t1 (cl (= (store m 0 x) (store m 0 y))) :rule refl)
+            (step t1 (cl) :rule hole)": true,
+
+            "(anchor :step t1 :args ((y Real) (:= (x Real) y)))
+            (step t1.t1 (cl (= (select (store m 0 x) 1) (select (store m 0 y) 1))) :rule refl)
+            (step t1 (cl) :rule hole)": true,
+
+            "(anchor :step t1 :args ((y Real) (:= (x Real) y)))
+            (step t1.t1 (cl (= (store m 0 x) (store m 1 y))) :rule refl)
+            (step t1 (cl) :rule hole)": false,
+
+            "(anchor :step t1 :args ((y (Array Int Real)) (:= (x (Array Int Real)) y)))
+            (step t1.t1 (cl (= (select x 0) (select y 0))) :rule refl)
+            (step t1 (cl) :rule hole)": true,
+        }
     }
 }
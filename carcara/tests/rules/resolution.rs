@@ -8,6 +8,7 @@ fn resolution() {
             (declare-fun s () Bool)
             (declare-fun t () Bool)
             (declare-fun u () Bool)
+            (declare-fun f ((_ BitVec 4)) Bool)
         ",
         "Simple working examples" {
             "(assume h1 (not p))
@@ -61,6 +62,28 @@ fn resolution() {
             (step t3 (cl (not q) p (not p) (not (not (not p))) (not (not p)))
                 :rule resolution :premises (t1 t2))": true,
         }
+        "Double negations match their positive forms" {
+            "(step t1 (cl (not (not p)) q) :rule hole)
+            (step t2 (cl (not q)) :rule hole)
+            (step t3 (cl p) :rule resolution :premises (t1 t2))": true,
+
+            "(step t1 (cl (not (not p)) q) :rule hole)
+            (step t2 (cl (not q)) :rule hole)
+            (step t3 (cl p) :rule resolution :premises (t1 t2) :args (q true))": true,
+
+            "(step t1 (cl (not (not p)) q) :rule hole)
+            (step t2 (cl (not q) r) :rule hole)
+            (step t3 (cl (not (not (not (not p)))) r)
+                :rule resolution :premises (t1 t2) :args (q true))": true,
+
+            "(step t1 (cl (not (not p)) q) :rule hole)
+            (step t2 (cl (not q)) :rule hole)
+            (step t3 (cl (not p)) :rule resolution :premises (t1 t2) :args (q true))": false,
+
+            "(step t1 (cl (not (not p)) q) :rule hole)
+            (step t2 (cl (not q)) :rule hole)
+            (step t3 (cl (not (not (not p)))) :rule resolution :premises (t1 t2))": false,
+        }
         "Weird behaviour where leading negations sometimes are added to conclusion" {
             "(assume h1 (not p))
             (step t2 (cl p q) :rule hole)
@@ -106,6 +129,34 @@ fn resolution() {
                 :premises (t1 t2 t3 t4)
                 :args (q true (not r) true s false))": true,
         }
+        "Mismatched pivots in arguments" {
+            // When pivots are given, the checker doesn't fall back to reconstructing them, even if
+            // the conclusion could be obtained with other pivots
+            "(step t1 (cl p q r) :rule hole)
+            (step t2 (cl (not q) s) :rule hole)
+            (step t3 (cl p r s) :rule resolution :premises (t1 t2) :args (r true))": false,
+
+            "(step t1 (cl p q r) :rule hole)
+            (step t2 (cl (not q) s) :rule hole)
+            (step t3 (cl p r s) :rule resolution :premises (t1 t2) :args (q false))": false,
+
+            // The pivots must be given in the same order as the premises
+            "(step t1 (cl p q) :rule hole)
+            (step t2 (cl (not q) r) :rule hole)
+            (step t3 (cl (not r) s) :rule hole)
+            (step t4 (cl p s)
+                :rule resolution :premises (t1 t2 t3) :args (r true q true))": false,
+
+            // There must be exactly one pivot for each premise after the first
+            "(step t1 (cl p q) :rule hole)
+            (step t2 (cl (not q) r) :rule hole)
+            (step t3 (cl (not r) s) :rule hole)
+            (step t4 (cl p s) :rule resolution :premises (t1 t2 t3) :args (q true))": false,
+
+            "(step t1 (cl p q) :rule hole)
+            (step t2 (cl (not q) r) :rule hole)
+            (step t3 (cl p r) :rule resolution :premises (t1 t2) :args (q p))": false,
+        }
         "Only one pivot eliminated per clause" {
             "(step t1 (cl p q r) :rule hole)
             (step t2 (cl (not q) (not r)) :rule hole)
@@ -130,6 +181,36 @@ fn resolution() {
             "(assume h1 true)
             (step t2 (cl true) :rule resolution :premises (h1))": false,
         }
+        "Disjunctions in clauses are not flattened" {
+            "(step t1 (cl (or p q) r) :rule hole)
+            (step t2 (cl (not (or p q))) :rule hole)
+            (step t3 (cl r) :rule resolution :premises (t1 t2))": true,
+
+            "(step t1 (cl (or p q)) :rule hole)
+            (step t2 (cl (not p)) :rule hole)
+            (step t3 (cl q) :rule resolution :premises (t1 t2))": false,
+
+            "(step t1 (cl (or p q) p) :rule hole)
+            (step t2 (cl (not p)) :rule hole)
+            (step t3 (cl (or p q)) :rule resolution :premises (t1 t2))": true,
+
+            "(step t1 (cl (or p q) p) :rule hole)
+            (step t2 (cl (not p)) :rule hole)
+            (step t3 (cl q) :rule resolution :premises (t1 t2))": false,
+        }
+        "Bitvector literals written in different notations are the same term" {
+            "(assume h1 (f #x1))
+            (assume h2 (not (f #b0001)))
+            (step t3 (cl) :rule resolution :premises (h1 h2))": true,
+
+            "(assume h1 (f (_ bv1 4)))
+            (assume h2 (not (f #x1)))
+            (step t3 (cl) :rule resolution :premises (h1 h2))": true,
+
+            "(assume h1 (f #x1))
+            (assume h2 (not (f #b0010)))
+            (step t3 (cl) :rule resolution :premises (h1 h2))": false,
+        }
     }
 }
 
@@ -228,6 +309,16 @@ fn tautology() {
             "(step t1 (cl p (not (not s)) q r s) :rule hole)
             (step t2 (cl true) :rule tautology :premises (t1))": false,
         }
+        "Double negations are normalized" {
+            "(step t1 (cl (not (not p)) (not p)) :rule hole)
+            (step t2 (cl true) :rule tautology :premises (t1))": true,
+
+            "(step t1 (cl (not (not (not (not q)))) r (not (not (not q)))) :rule hole)
+            (step t2 (cl true) :rule tautology :premises (t1))": true,
+
+            "(step t1 (cl (not (not p)) p) :rule hole)
+            (step t2 (cl true) :rule tautology :premises (t1))": false,
+        }
     }
 }
 
@@ -250,6 +341,16 @@ fn contraction() {
             "(step t1 (cl p q r s) :rule hole)
             (step t2 (cl p q r s) :rule contraction :premises (t1))": true,
         }
+        "Double negations are normalized" {
+            "(step t1 (cl p (not (not p)) q) :rule hole)
+            (step t2 (cl p q) :rule contraction :premises (t1))": true,
+
+            "(step t1 (cl (not (not (not q))) (not q) p) :rule hole)
+            (step t2 (cl (not q) p) :rule contraction :premises (t1))": true,
+
+            "(step t1 (cl p (not (not p)) q) :rule hole)
+            (step t2 (cl (not p) q) :rule contraction :premises (t1))": false,
+        }
         "Number of premises != 1" {
             "(step t1 (cl p q) :rule contraction)": false,
 
@@ -272,6 +373,16 @@ fn contraction() {
             "(step t1 (cl p q q r) :rule hole)
             (step t2 (cl p r q) :rule contraction :premises (t1))": true,
         }
+        "Disjunctions in clauses are not flattened" {
+            "(step t1 (cl (or p q) (or p q) p) :rule hole)
+            (step t2 (cl (or p q) p) :rule contraction :premises (t1))": true,
+
+            "(step t1 (cl (or p q) p q) :rule hole)
+            (step t2 (cl (or p q)) :rule contraction :premises (t1))": false,
+
+            "(step t1 (cl (or p q) p) :rule hole)
+            (step t2 (cl p q) :rule contraction :premises (t1))": false,
+        }
         "Conclusion is missing terms" {
             "(step t1 (cl p q q r) :rule hole)
             (step t2 (cl p r) :rule contraction :premises (t1))": false,
@@ -258,17 +258,21 @@ fn implies_simplify() {
         "Transformation #2" {
             "(step t1 (cl (= (=> false p) true)) :rule implies_simplify)": true,
             "(step t1 (cl (= (=> false false) true)) :rule implies_simplify)": true,
+            "(step t1 (cl (= (=> false p) p)) :rule implies_simplify)": false,
         }
         "Transformation #3" {
             "(step t1 (cl (= (=> p true) true)) :rule implies_simplify)": true,
             "(step t1 (cl (= (=> false true) true)) :rule implies_simplify)": true,
+            "(step t1 (cl (= (=> p true) p)) :rule implies_simplify)": false,
         }
         "Transformation #4" {
             "(step t1 (cl (= (=> true p) p)) :rule implies_simplify)": true,
             "(step t1 (cl (= (=> true false) false)) :rule implies_simplify)": true,
+            "(step t1 (cl (= (=> true p) true)) :rule implies_simplify)": false,
         }
         "Transformation #5" {
             "(step t1 (cl (= (=> p false) (not p))) :rule implies_simplify)": true,
+            "(step t1 (cl (= (=> p false) p)) :rule implies_simplify)": false,
             "(step t1 (cl (= (=> false false) (not false))) :rule implies_simplify)": false,
             "(step t1 (cl (= (=> true false) (not true))) :rule implies_simplify)": false,
         }
@@ -337,6 +341,17 @@ fn equiv_simplify() {
             "(step t1 (cl (= (= (not (not false)) (not (not p))) (not p)))
                 :rule equiv_simplify)": true,
         }
+        "Symmetric equalities" {
+            "(step t1 (cl (= (= (= p q) (= q p)) true)) :rule equiv_simplify)": true,
+            "(step t1 (cl (= (= (= p q) (= p r)) true)) :rule equiv_simplify)": false,
+        }
+        "Nested equivalences" {
+            "(step t1 (cl (= (= (= p p) q) q)) :rule equiv_simplify)": true,
+            "(step t1 (cl (= (= r (= q false)) (= r (not q)))) :rule equiv_simplify)": true,
+            "(step t1 (cl (= (= (= (= p p) q) r) (= q r))) :rule equiv_simplify)": true,
+            "(step t1 (cl (= (= (= (= p q) (= q p)) r) r)) :rule equiv_simplify)": true,
+            "(step t1 (cl (= (= (= (= p q) q) r) (= p r))) :rule equiv_simplify)": false,
+        }
     }
 }
 
@@ -415,6 +430,10 @@ fn bool_simplify() {
                 (and (=> p q) r) (and p q)
             )) :rule bool_simplify)": false,
         }
+        "No pattern applies" {
+            "(step t1 (cl (= (and p q) (or p q))) :rule bool_simplify)": false,
+            "(step t1 (cl (= (or p q) (and p q))) :rule bool_simplify)": false,
+        }
         // TODO: Add tests that combine more than one transformation
     }
 }
@@ -422,7 +441,10 @@ fn bool_simplify() {
 #[test]
 fn qnt_simplify() {
     test_cases! {
-        definitions = "",
+        definitions = "
+            (declare-fun p () Bool)
+            (declare-fun f (Int) Bool)
+        ",
         "Simple working examples" {
             "(step t1 (cl (= (forall ((x Int)) false) false)) :rule qnt_simplify)": true,
             "(step t1 (cl (= (forall ((x Int) (p Bool)) true) true)) :rule qnt_simplify)": true,
@@ -436,6 +458,16 @@ fn qnt_simplify() {
         "Left and right terms don't match" {
             "(step t1 (cl (= (forall ((x Int)) false) true)) :rule qnt_simplify)": false,
         }
+        "Vacuous quantifiers" {
+            "(step t1 (cl (= (forall ((x Int)) p) p)) :rule qnt_simplify)": true,
+            "(step t1 (cl (= (exists ((x Int) (y Int)) (f 0)) (f 0))) :rule qnt_simplify)": true,
+            "(step t1 (cl (= (forall ((x Int)) (f x)) (f 0))) :rule qnt_simplify)": false,
+            "(step t1 (cl (= (forall ((x Int) (y Int)) (f y)) (f 0))) :rule qnt_simplify)": false,
+
+            // Unused bindings can't be removed one by one, that is done by `qnt_rm_unused`
+            "(step t1 (cl (= (forall ((x Int) (y Int)) (f y)) (forall ((y Int)) (f y))))
+                :rule qnt_simplify)": false,
+        }
     }
 }
 
@@ -466,6 +498,9 @@ fn div_simplify() {
         "Division by zero" {
             "(step t1 (cl (= (div 3 0) 1)) :rule div_simplify)": false,
             "(step t1 (cl (= (/ 3.0 0.0) 1.0)) :rule div_simplify)": false,
+            "(step t1 (cl (= (div 0 0) 1)) :rule div_simplify)": false,
+            "(step t1 (cl (= (/ 0.0 0.0) 1.0)) :rule div_simplify)": false,
+            "(step t1 (cl (= (div 3 0) 0)) :rule div_simplify)": false,
         }
         "Integer division" {
             "(step t1 (cl (= (div 8 3) 2)) :rule div_simplify)": true,
@@ -476,6 +511,11 @@ fn div_simplify() {
             "(step t1 (cl (= (div (- 8) 3) (- 2))) :rule div_simplify)": false,
             "(step t1 (cl (= (div 8 (- 3)) (- 3))) :rule div_simplify)": false,
             "(step t1 (cl (= (div (- 8) (- 3)) 2)) :rule div_simplify)": false,
+
+            "(step t1 (cl (= (div (- 7) 2) (- 4))) :rule div_simplify)": true,
+            "(step t1 (cl (= (div 7 (- 2)) (- 3))) :rule div_simplify)": true,
+            "(step t1 (cl (= (div (- 7) 2) (- 3))) :rule div_simplify)": false,
+            "(step t1 (cl (= (div 7 (- 2)) (- 4))) :rule div_simplify)": false,
         }
     }
 }
@@ -533,6 +573,14 @@ fn prod_simplify() {
             "(step t1 (cl (= (* x y 5.0 1.0 z 0.2 z) (* 1.0 x y z z)))
                 :rule prod_simplify)": false,
         }
+        "Factors in a different order" {
+            "(step t1 (cl (= (* i 2 k 3 j) (* 6 j i k))) :rule prod_simplify)": true,
+            "(step t1 (cl (= (* z 1.0 y x) (* x y z))) :rule prod_simplify)": true,
+            "(step t1 (cl (= (* x y 2.0 z z) (* 2.0 z x z y))) :rule prod_simplify)": true,
+
+            "(step t1 (cl (= (* x y 2.0 z z) (* 2.0 z x y))) :rule prod_simplify)": false,
+            "(step t1 (cl (= (* i 2 k 3 j) (* 6 j i i))) :rule prod_simplify)": false,
+        }
     }
 }
 
@@ -559,6 +607,9 @@ fn minus_simplify() {
             "(step t1 (cl (= (- x 0.0) x)) :rule minus_simplify)": true,
             "(step t1 (cl (= (- a 0) a)) :rule minus_simplify)": true,
             "(step t1 (cl (= (- 0.0 x) x)) :rule minus_simplify)": false,
+            "(step t1 (cl (= (- b 0) b)) :rule minus_simplify)": true,
+            "(step t1 (cl (= x (- x 0.0))) :rule minus_simplify)": true,
+            "(step t1 (cl (= (- x 0.0) (- x))) :rule minus_simplify)": false,
         }
         "Transformation #4" {
             "(step t1 (cl (= (- 0.0 x) (- x))) :rule minus_simplify)": true,
@@ -570,11 +621,23 @@ fn minus_simplify() {
             "(step t1 (cl (= (- (- x)) x)) :rule minus_simplify)": true,
             "(step t1 (cl (= x (- (- x)))) :rule minus_simplify)": true,
             "(step t1 (cl (= (- (- (+ a b))) (+ a b))) :rule minus_simplify)": true,
+            "(step t1 (cl (= (- (- 5)) 5)) :rule minus_simplify)": true,
+            "(step t1 (cl (= (- (- 5.0)) 5.0)) :rule minus_simplify)": true,
+            "(step t1 (cl (= (- (- 5)) (- 5))) :rule minus_simplify)": false,
+            "(step t1 (cl (= (- (- x)) (- x))) :rule minus_simplify)": false,
         }
         "Transformation #2 from \"unary_minus_simplify\"" {
             "(step t1 (cl (= (- 5.0) (- 5.0))) :rule minus_simplify)": true,
             "(step t1 (cl (= (- 0) 0)) :rule minus_simplify)": true,
             "(step t1 (cl (= 0.0 (- 0.0))) :rule minus_simplify)": true,
+            "(step t1 (cl (= (- 5) (- 5))) :rule minus_simplify)": true,
+            "(step t1 (cl (= (- 5) 5)) :rule minus_simplify)": false,
+            "(step t1 (cl (= (- x) x)) :rule minus_simplify)": false,
+        }
+        "Using the \"unary_minus_simplify\" name" {
+            "(step t1 (cl (= (- (- x)) x)) :rule unary_minus_simplify)": true,
+            "(step t1 (cl (= (- 0) 0)) :rule unary_minus_simplify)": true,
+            "(step t1 (cl (= (- (- x)) (- x))) :rule unary_minus_simplify)": false,
         }
     }
 }
@@ -629,6 +692,8 @@ fn comp_simplify() {
         definitions = "
             (declare-fun a () Int)
             (declare-fun b () Int)
+            (declare-fun x () Real)
+            (declare-fun y () Real)
         ",
         "Transformation #1" {
             "(step t1 (cl (= (< 1 2) true)) :rule comp_simplify)": true,
@@ -670,6 +735,37 @@ fn comp_simplify() {
             "(step t1 (cl (= (<= (/ 1.0 20.0) (/ 1.0 10.0)) true)) :rule comp_simplify)": true,
             "(step t1 (cl (= (<= (/ (- 1.0) 2.0) (/ (- 1.0) 4.0)) true)) :rule comp_simplify)": true,
         }
+        "Integer tightening" {
+            "(step t1 (cl (= (< a (+ b 1)) (<= a b))) :rule comp_simplify)": true,
+            "(step t1 (cl (= (< a (+ 1 b)) (<= a b))) :rule comp_simplify)": true,
+            "(step t1 (cl (= (< a (+ b 1)) (not (<= (+ b 1) a)))) :rule comp_simplify)": true,
+            "(step t1 (cl (= (< a (+ b 2)) (<= a b))) :rule comp_simplify)": false,
+        }
+        "Integer tightening is not applied to reals" {
+            "(step t1 (cl (= (< x (+ y 1.0)) (<= x y))) :rule comp_simplify)": false,
+            "(step t1 (cl (= (< x (+ y 1.0)) (not (<= (+ y 1.0) x)))) :rule comp_simplify)": true,
+        }
+    }
+}
+
+#[test]
+fn comp_simplify_int_real_subtyping() {
+    test_cases! {
+        definitions = "
+            (declare-fun a () Int)
+            (declare-fun y () Real)
+        ",
+        parser_config = carcara::parser::Config {
+            apply_function_defs: true,
+            allow_int_real_subtyping: true,
+            ..Default::default()
+        },
+        "Integer tightening is not applied to mixed Int and Real operands" {
+            "(step t1 (cl (= (< a (+ y 1)) (<= a y))) :rule comp_simplify)": false,
+            "(step t1 (cl (= (< a (+ 1 y)) (<= a y))) :rule comp_simplify)": false,
+            "(step t1 (cl (= (< a (+ y 1)) (not (<= (+ y 1) a)))) :rule comp_simplify)": true,
+            "(step t1 (cl (= (< y (+ a 1)) (<= y a))) :rule comp_simplify)": false,
+        }
     }
 }
 
@@ -705,6 +801,13 @@ fn ac_simp() {
             "(step t1 (cl (= (or p p q r s) (or p q r s))) :rule ac_simp)": true,
             "(step t1 (cl (= (and (and p q) (and q r)) (and p q r))) :rule ac_simp)": true,
             "(step t1 (cl (= (and (and p q) (and q r)) (and p q q r))) :rule ac_simp)": false,
+            "(step t1 (cl (= (and p p q) (and p q))) :rule ac_simp)": true,
+            "(step t1 (cl (= (and p p q) (and p p q))) :rule ac_simp)": false,
+        }
+        "Operands must be kept in order" {
+            "(step t1 (cl (= (and (and p q) r) (and p q r))) :rule ac_simp)": true,
+            "(step t1 (cl (= (and (and p q) r) (and r q p))) :rule ac_simp)": false,
+            "(step t1 (cl (= (or p (or q r)) (or q p r))) :rule ac_simp)": false,
         }
     }
 }
@@ -125,6 +125,21 @@ fn bind() {
             (step t1 (cl (= (forall ((x2 Real)) (= x1 x2))
                 (forall ((y1 Real) (y2 Real)) (= y1 y2)))) :rule bind)": false,
         }
+        "Right side binds a variable that was not introduced in the context" {
+            "(anchor :step t1 :args ((y Real) (:= (x Real) y)))
+            (step t1.t1 (cl (= p q)) :rule hole)
+            (step t1 (cl (= (forall ((x Real)) p) (forall ((w Real)) q))) :rule bind)": false,
+        }
+        "Right side doesn't bind the variable that the context renames to" {
+            "(anchor :step t1 :args ((y Real) (w Real) (:= (x Real) y)))
+            (step t1.t1 (cl (= p q)) :rule hole)
+            (step t1 (cl (= (forall ((x Real)) p) (forall ((w Real)) q))) :rule bind)": false,
+
+            "(anchor :step t1 :args ((y1 Real) (y2 Real) (:= (x1 Real) y1) (:= (x2 Real) y2)))
+            (step t1.t1 (cl (= (= x1 x2) (= y1 y2))) :rule hole)
+            (step t1 (cl (= (forall ((x1 Real) (x2 Real)) (= x1 x2))
+                (forall ((y2 Real) (y1 Real)) (= y1 y2)))) :rule bind)": true,
+        }
     }
 }
 
@@ -185,6 +200,26 @@ fn r#let() {
             (step t2.t1 (cl (= p (= i j))) :rule hole)
             (step t2 (cl (= (let ((a i)) p) q)) :rule let :premises (t1))": false,
         }
+        "Inner `let` terms that shadow a binding" {
+            "(step t1 (cl (= i x)) :rule hole)
+            (anchor :step t2 :args ((x Int) (:= (a Int) x)))
+            (step t2.t1 (cl (= (+ a (let ((a j)) a)) (+ x (let ((a j)) a)))) :rule refl)
+            (step t2 (cl (= (let ((a i)) (+ a (let ((a j)) a))) (+ x (let ((a j)) a))))
+                :rule let :premises (t1))": true,
+
+            "(step t1 (cl (= i x)) :rule hole)
+            (anchor :step t2 :args ((x Int) (:= (a Int) x)))
+            (step t2.t1 (cl (= (+ a (let ((b a)) b)) (+ x (let ((b x)) b)))) :rule refl)
+            (step t2 (cl (= (let ((a i)) (+ a (let ((b a)) b))) (+ x (let ((b x)) b))))
+                :rule let :premises (t1))": true,
+
+            // The inner `a` refers to the inner binding, so it may not be replaced by `x`
+            "(step t1 (cl (= i x)) :rule hole)
+            (anchor :step t2 :args ((x Int) (:= (a Int) x)))
+            (step t2.t1 (cl (= (+ a (let ((a j)) a)) (+ x (let ((a j)) x)))) :rule refl)
+            (step t2 (cl (= (let ((a i)) (+ a (let ((a j)) a))) (+ x (let ((a j)) x))))
+                :rule let :premises (t1))": false,
+        }
     }
 }
 
@@ -278,6 +313,17 @@ fn onepoint() {
                 (=> (not (= 0 0)) (=> (= 2 2) (=> (= 0 0) (= 1 2))))
             )) :rule onepoint)": true,
         }
+        "Wrong witness" {
+            "(anchor :step t1 :args ((:= (x Int) t)))
+            (step t1.t1 (cl (= (=> (= x u) p) (=> (= t u) p))) :rule hole)
+            (step t1 (cl (= (forall ((x Int)) (=> (= x u) p)) (=> (= t u) p)))
+                :rule onepoint)": false,
+
+            "(anchor :step t1 :args ((:= (x Int) t)))
+            (step t1.t1 (cl (= (and (= x u) p) (and (= t u) p))) :rule hole)
+            (step t1 (cl (= (exists ((x Int)) (and (= x u) p)) (and (= t u) p)))
+                :rule onepoint)": false,
+        }
     }
 }
 
@@ -287,6 +333,7 @@ fn sko_ex() {
         definitions = "
             (declare-fun p (Int) Bool)
             (declare-fun q (Int) Bool)
+            (declare-fun c () Int)
         ",
         "Simple working examples" {
             "(anchor :step t1 :args ((:= (x Int) (choice ((x Int)) (p x)))))
@@ -309,6 +356,27 @@ fn sko_ex() {
                    (choice ((y Int)) (= (choice ((x Int)) (exists ((y Int)) (= x y))) y)))
             )) :rule sko_ex)": true,
         }
+        "Choice term binds a different variable name" {
+            "(anchor :step t1 :args ((:= (x Int) (choice ((z Int)) (p z)))))
+            (step t1.t1 (cl (= (p x) (p (choice ((z Int)) (p z))))) :rule hole)
+            (step t1 (cl (= (exists ((x Int)) (p x)) (p (choice ((z Int)) (p z)))))
+                :rule sko_ex)": true,
+        }
+        "Skolem term is not the expected choice term" {
+            "(anchor :step t1 :args ((:= (x Int) c)))
+            (step t1.t1 (cl (= (p x) (p c))) :rule hole)
+            (step t1 (cl (= (exists ((x Int)) (p x)) (p c))) :rule sko_ex)": false,
+
+            "(anchor :step t1 :args ((:= (x Int) (choice ((x Int)) (q x)))))
+            (step t1.t1 (cl (= (p x) (p (choice ((x Int)) (q x))))) :rule hole)
+            (step t1 (cl (= (exists ((x Int)) (p x)) (p (choice ((x Int)) (q x)))))
+                :rule sko_ex)": false,
+
+            "(anchor :step t1 :args ((:= (x Int) (choice ((x Int)) (not (p x))))))
+            (step t1.t1 (cl (= (p x) (p (choice ((x Int)) (not (p x)))))) :rule hole)
+            (step t1 (cl (= (exists ((x Int)) (p x)) (p (choice ((x Int)) (not (p x))))))
+                :rule sko_ex)": false,
+        }
     }
 }
 
@@ -318,6 +386,7 @@ fn sko_forall() {
         definitions = "
             (declare-fun p (Int) Bool)
             (declare-fun q (Int) Bool)
+            (declare-fun c () Int)
         ",
         "Simple working examples" {
             "(anchor :step t1 :args ((:= (x Int) (choice ((x Int)) (not (p x))))))
@@ -344,5 +413,15 @@ fn sko_forall() {
                         (not (= (choice ((x Int)) (not (forall ((y Int)) (= x y)))) y))))
             )) :rule sko_forall)": true,
         }
+        "Skolem term is not the expected choice term" {
+            "(anchor :step t1 :args ((:= (x Int) c)))
+            (step t1.t1 (cl (= (p x) (p c))) :rule hole)
+            (step t1 (cl (= (forall ((x Int)) (p x)) (p c))) :rule sko_forall)": false,
+
+            "(anchor :step t1 :args ((:= (x Int) (choice ((x Int)) (p x)))))
+            (step t1.t1 (cl (= (p x) (p (choice ((x Int)) (p x))))) :rule hole)
+            (step t1 (cl (= (forall ((x Int)) (p x)) (p (choice ((x Int)) (p x)))))
+                :rule sko_forall)": false,
+        }
     }
 }
@@ -77,6 +77,10 @@ fn and_pos() {
             "(step t1 (cl (not (and p q r)) s) :rule and_pos)": false,
             "(step t1 (cl (not (and p (not q) r)) q) :rule and_pos)": false,
         }
+        "Index argument does not select the literal" {
+            "(step t1 (cl (not (and p q r)) r) :rule and_pos :args (1))": false,
+            "(step t1 (cl (not (and p q r)) r) :rule and_pos :args (3))": false,
+        }
     }
 }
 
@@ -134,6 +138,7 @@ fn or_pos() {
         "Terms don't match" {
             "(step t1 (cl (not (or p q r)) p q s) :rule or_pos)": false,
             "(step t1 (cl (not (or p q r s)) p r q s) :rule or_pos)": false,
+            "(step t1 (cl (not (or p q)) q p) :rule or_pos)": false,
         }
     }
 }
@@ -157,7 +162,10 @@ fn or_neg() {
         "Second term is not in \"or\" term" {
             "(step t1 (cl (or p q r) (not s)) :rule or_neg)": false,
             "(step t1 (cl (or p (not q) r) (not q)) :rule or_neg)": false,
-
+        }
+        "Index argument does not select the literal" {
+            "(step t1 (cl (or p q r) (not r)) :rule or_neg :args (0))": false,
+            "(step t1 (cl (or p q r) (not r)) :rule or_neg :args (5))": false,
         }
     }
 }
@@ -531,6 +539,16 @@ fn equiv1() {
             "(assume h1 (= p q))
             (step t2 (cl (not p) q) :rule equiv1 :premises (h1))": true,
         }
+        "Reversed equivalence" {
+            "(assume h1 (= q p))
+            (step t2 (cl (not p) q) :rule equiv1 :premises (h1))": true,
+
+            "(assume h1 (= q p))
+            (step t2 (cl (not q) p) :rule equiv1 :premises (h1))": true,
+
+            "(assume h1 (= q p))
+            (step t2 (cl p (not q)) :rule equiv1 :premises (h1))": false,
+        }
         "Conclusion clause is of the wrong form" {
             "(assume h1 (= p q))
             (step t2 (cl p (not q)) :rule equiv1 :premises (h1))": false,
@@ -555,6 +573,16 @@ fn equiv2() {
             "(assume h1 (= p q))
             (step t2 (cl p (not q)) :rule equiv2 :premises (h1))": true,
         }
+        "Reversed equivalence" {
+            "(assume h1 (= q p))
+            (step t2 (cl p (not q)) :rule equiv2 :premises (h1))": true,
+
+            "(assume h1 (= q p))
+            (step t2 (cl q (not p)) :rule equiv2 :premises (h1))": true,
+
+            "(assume h1 (= q p))
+            (step t2 (cl (not p) q) :rule equiv2 :premises (h1))": false,
+        }
         "Conclusion clause is of the wrong form" {
             "(assume h1 (= p q))
             (step t2 (cl (not p) q) :rule equiv2 :premises (h1))": false,
@@ -579,6 +607,13 @@ fn not_equiv1() {
             "(assume h1 (not (= p q)))
             (step t2 (cl p q) :rule not_equiv1 :premises (h1))": true,
         }
+        "Reversed equivalence" {
+            "(assume h1 (not (= q p)))
+            (step t2 (cl p q) :rule not_equiv1 :premises (h1))": true,
+
+            "(assume h1 (not (= q p)))
+            (step t2 (cl (not q) p) :rule not_equiv1 :premises (h1))": false,
+        }
         "Conclusion clause is of the wrong form" {
             "(assume h1 (not (= p q)))
             (step t2 (cl (not p) q) :rule not_equiv1 :premises (h1))": false,
@@ -600,6 +635,13 @@ fn not_equiv2() {
             "(assume h1 (not (= p q)))
             (step t2 (cl (not p) (not q)) :rule not_equiv2 :premises (h1))": true,
         }
+        "Reversed equivalence" {
+            "(assume h1 (not (= q p)))
+            (step t2 (cl (not p) (not q)) :rule not_equiv2 :premises (h1))": true,
+
+            "(assume h1 (not (= q p)))
+            (step t2 (cl p (not q)) :rule not_equiv2 :premises (h1))": false,
+        }
         "Conclusion clause is of the wrong form" {
             "(assume h1 (not (= p q)))
             (step t2 (cl p (not q)) :rule not_equiv2 :premises (h1))": false,
@@ -801,6 +843,27 @@ fn ite_intro() {
                 (and (ite p a b) (or (ite q c d) (ite (not p) b (not d))))
             )) :rule ite_intro)": true,
         }
+        "\"ite\" subterm introduced more than once" {
+            "(step t1 (cl (=
+                (or (ite p a b) (ite q c d))
+                (and
+                    (or (ite p a b) (ite q c d))
+                    (ite p (= a (ite p a b)) (= b (ite p a b)))
+                    (ite q (= c (ite q c d)) (= d (ite q c d)))
+                    (ite p (= (ite p a b) a) (= (ite p a b) b))
+                )
+            )) :rule ite_intro)": false,
+        }
+        "Introduced \"ite\" is not a subterm of the root term" {
+            "(step t1 (cl (=
+                (or (ite p a b) (ite q c d))
+                (and
+                    (or (ite p a b) (ite q c d))
+                    (ite p (= a (ite p a b)) (= b (ite p a b)))
+                    (ite q (= d (ite q d c)) (= c (ite q d c)))
+                )
+            )) :rule ite_intro)": false,
+        }
         "\"ite\" subterm may be skipped" {
             "(step t1 (cl (=
                 (or (ite p a b) (ite q c d) (ite q d a))
@@ -862,5 +925,16 @@ fn connective_def() {
             "(step t1 (cl (= (exists ((x Real)) p) (forall ((x Real)) (not p))))
                 :rule connective_def)": false,
         }
+        "Implication" {
+            "(step t1 (cl (= (=> p q) (or (not p) q))) :rule connective_def)": true,
+            "(step t1 (cl (= (=> p q) (or q (not p)))) :rule connective_def)": false,
+            "(step t1 (cl (= (=> p q) (or p (not q)))) :rule connective_def)": false,
+        }
+        "Unsupported connectives" {
+            "(step t1 (cl (= (and p q) (not (or (not p) (not q))))) :rule connective_def)": false,
+            "(step t1 (cl (= (xor p q r) (or (and (not p) q) (and p (not q)))))
+                :rule connective_def)": false,
+            "(step t1 (cl (= p (or (and (not p) q) (and p (not q))))) :rule connective_def)": false,
+        }
     }
 }
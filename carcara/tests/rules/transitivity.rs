@@ -36,6 +36,15 @@ fn eq_transitive() {
         "Clause is too small" {
             "(step t1 (cl (not (= a b)) (= a b)) :rule eq_transitive)": false,
         }
+        "Concluded equality in either orientation" {
+            "(step t1 (cl (not (= a b)) (not (= b c)) (= c a)) :rule eq_transitive)": true,
+
+            "(step t1 (cl (not (= a b)) (not (= b c)) (not (= c d)) (= d a))
+                :rule eq_transitive)": true,
+
+            "(step t1 (cl (not (= b a)) (not (= c b)) (not (= d c)) (= d a))
+                :rule eq_transitive)": true,
+        }
         "Clause terms in different orders" {
             "(step t1 (cl (not (= a b)) (not (= c d)) (not (= b c)) (= a d))
                 :rule eq_transitive)": true,
@@ -41,11 +41,31 @@ fn run_test(problem_path: &Path, proof_path: &Path) -> CarcaraResult<()> {
         elaborated: false,
         ignore_unknown_rules: false,
         allowed_rules: ["all_simplify".to_owned(), "rare_rewrite".to_owned()].into(),
+        record_skipped: false,
+        collect_warnings: false,
+        require_empty_clause: false,
+        forbid_trust: false,
+        normalize_ite: false,
+        expand_distinct: false,
+        strict: false,
+        check_sorts: false,
+        rule_overrides: Default::default(),
+        trace_sink: None,
+        on_step_begin: None,
+        on_step_end: None,
     };
 
     // First, we check the proof normally
     checker::ProofChecker::new(&mut pool, checker_config.clone()).check(&problem, &proof)?;
 
+    // It should also be accepted when it is checked while being parsed
+    check_file_streaming(
+        problem_path,
+        proof_path,
+        parser::Config::new(),
+        checker_config.clone(),
+    )?;
+
     // Then we elaborate it
     let config = elaborator::Config {
         lia_options: None,
@@ -58,6 +78,7 @@ fn run_test(problem_path: &Path, proof_path: &Path) -> CarcaraResult<()> {
     let elaborated = ast::Proof {
         constant_definitions: proof.constant_definitions.clone(),
         commands: elaborated_node.into_commands(),
+        comments: proof.comments.clone(),
     };
 
     // After that, we check the elaborated proof to make sure it is valid
@@ -96,7 +117,7 @@ fn test_file(proof_path: &str) {
             Error::Io(_) => "IO error".to_owned(),
             Error::Parser(_, (line, column)) => format!("parser error at {}:{}", line, column),
             Error::Checker { rule, step, .. } => format!("checker error at '{}' ({})", step, rule),
-            Error::DoesNotReachEmptyClause => format!("{}", e), // This one is already pretty short
+            Error::DoesNotReachEmptyClause | Error::StepNotFound(_) => format!("{}", e), // This one is already pretty short
         };
         panic!(
             "\"{}\" returned error: {}",
@@ -1,4 +1,4 @@
-use std::{fmt, io, path::PathBuf};
+use std::{error::Error, fmt, io, path::PathBuf};
 
 #[derive(Debug)]
 pub enum CliError {
@@ -25,7 +25,17 @@ impl From<carcara::Error> for CliError {
 impl fmt::Display for CliError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
-            CliError::CarcaraError(e) => write!(f, "{}", e),
+            CliError::CarcaraError(e) => {
+                // The underlying cause of a Carcara error is only reachable through its source
+                // chain, so we print the whole chain
+                write!(f, "{}", e)?;
+                let mut source = e.source();
+                while let Some(inner) = source {
+                    write!(f, ": {}", inner)?;
+                    source = inner.source();
+                }
+                Ok(())
+            }
             CliError::CantInferProblemFile(p) => {
                 write!(f, "can't infer problem file: {}", p.display())
             }
@@ -134,6 +134,11 @@ struct ParsingOptions {
     /// terms. In the future, this will be the default behaviour.
     #[clap(long)]
     parse_hole_args: bool,
+
+    /// Keeps the comments before each command in the proof, so they are printed back in commands
+    /// that output the proof.
+    #[clap(long)]
+    keep_comments: bool,
 }
 
 impl From<ParsingOptions> for parser::Config {
@@ -144,6 +149,7 @@ impl From<ParsingOptions> for parser::Config {
             allow_int_real_subtyping: val.allow_int_real_subtyping,
             strict: val.strict,
             parse_hole_args: val.parse_hole_args,
+            keep_comments: val.keep_comments,
         }
     }
 }
@@ -177,6 +183,25 @@ struct CheckingOptions {
     /// - the pivots for `resolution` steps must be given as arguments
     #[clap(arg_enum, long, default_value = "normal", verbatim_doc_comment)]
     check_granularity: CheckGranularity,
+
+    /// Compare terms modulo trivial simplifications of `ite` terms in the `ite_intro`, `ite1`,
+    /// `ite2` and `ite_simplify` rules.
+    #[clap(long)]
+    normalize_ite: bool,
+
+    /// Rewrite all `distinct` terms to their pairwise form before checking, so rules can match them
+    /// against their expansion.
+    #[clap(long)]
+    expand_distinct: bool,
+
+    /// Disable all lenient conveniences of the checker, such as implicit symmetry of equalities in
+    /// the `trans` and `cong` rules, and require every step to be fully explicit.
+    #[clap(long = "strict", conflicts_with = "normalize-ite")]
+    strict_checking: bool,
+
+    /// Reject any step that uses the `trust` or `trust_me` rules, which are never checked.
+    #[clap(long)]
+    forbid_trust: bool,
 }
 
 impl From<CheckingOptions> for checker::Config {
@@ -185,6 +210,18 @@ impl From<CheckingOptions> for checker::Config {
             elaborated: val.check_granularity == CheckGranularity::Elaborated,
             ignore_unknown_rules: val.ignore_unknown_rules || val.skip_unknown_rules,
             allowed_rules: val.allowed_rules.unwrap_or_default().into_iter().collect(),
+            record_skipped: false,
+            collect_warnings: false,
+            require_empty_clause: false,
+            forbid_trust: val.forbid_trust,
+            normalize_ite: val.normalize_ite,
+            expand_distinct: val.expand_distinct,
+            strict: val.strict_checking,
+            check_sorts: false,
+            rule_overrides: Default::default(),
+            trace_sink: None,
+            on_step_begin: None,
+            on_step_end: None,
         }
     }
 }
@@ -319,6 +356,11 @@ struct CheckCommandOptions {
     })]
     num_threads: usize,
 
+    /// Check only the step with the given id, along with the commands it transitively depends on.
+    /// All other commands are skipped, and `assume` commands are taken as given.
+    #[clap(long, value_name = "ID")]
+    only_step: Option<String>,
+
     #[clap(flatten)]
     stats: StatsOptions,
 
@@ -521,6 +563,11 @@ fn check_command(options: CheckCommandOptions) -> CliResult<bool> {
     let parser_config = options.parsing.into();
     let checker_config = options.checking.into();
     let collect_stats = options.stats.stats;
+    if let Some(target) = &options.only_step {
+        let (_, proof, mut pool) = parser::parse_instance(problem, proof, parser_config)?;
+        let mut checker = checker::ProofChecker::new(&mut pool, checker_config);
+        return checker.check_only(&proof, target).map_err(Into::into);
+    }
     if options.num_threads == 1 {
         check(problem, proof, parser_config, checker_config, collect_stats)
     } else {